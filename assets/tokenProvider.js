@@ -34,6 +34,23 @@ export const makeTokensProvider = () => {
       "FENCE",
       "ECALL",
       "EBREAK",
+      "MRET",
+      "CSRRW",
+      "CSRRS",
+      "CSRRC",
+      "CSRRWI",
+      "CSRRSI",
+      "CSRRCI",
+      "CSRR",
+      "CSRW",
+      "CSRS",
+      "CSRC",
+      "CSRWI",
+      "CSRSI",
+      "CSRCI",
+      "RDCYCLE",
+      "RDINSTRET",
+      "RDTIME",
       "SW",
       "SH",
       "SB",
@@ -47,6 +64,30 @@ export const makeTokensProvider = () => {
       "AUIPC",
       "JAL"
     ],
+    csrs: [
+      "mstatus",
+      "misa",
+      "mie",
+      "mtvec",
+      "mcountinhibit",
+      "mscratch",
+      "mepc",
+      "mcause",
+      "mtval",
+      "mip",
+      "mcycle",
+      "minstret",
+      "mcycleh",
+      "minstreth",
+      "cycle",
+      "instret",
+      "cycleh",
+      "instreth",
+      "mvendorid",
+      "marchid",
+      "mimpid",
+      "mhartid"
+    ],
     symbols: /[\.,\:]+/,
     escapes: /\\(?:[abfnrtv\\"'$]|x[0-9A-Fa-f]{1,4}|u[0-9A-Fa-f]{4}|U[0-9A-Fa-f]{8})/,
     tokenizer: {
@@ -58,6 +99,7 @@ export const makeTokensProvider = () => {
             cases: {
               this: "variable.predefined",
               "@keywords": { token: "keyword.$0" },
+              "@csrs": "variable.predefined",
               "@default": ""
             }
           }
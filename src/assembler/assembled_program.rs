@@ -3,8 +3,9 @@ use std::{
     str::FromStr,
 };
 use bimap::BiBTreeMap;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AssembledProgram {
     /// Map of instruction memory addresses to instruction bytes
     pub instruction_memory: BTreeMap<u32, u8>,
@@ -40,6 +41,20 @@ impl AssembledProgram {
         }
     }
 
+    /// Names a text address after the closest label at or before it, e.g. `loop+0x8`
+    pub fn symbolize(&self, address: u32) -> Option<String> {
+        nearest_label(&self.labels, address)
+    }
+
+    /// Names an address holding data after the closest data label at or
+    /// before it, e.g. `buffer+0x4`
+    pub fn symbolize_data(&self, address: u32) -> Option<String> {
+        if !self.data_memory.contains_key(&address) {
+            return None;
+        }
+        nearest_label(&self.data_labels, address)
+    }
+
     pub fn add_label(&mut self, label: String, address: u32, is_data: bool) {
         if is_data {
             self.data_labels.insert(label, address);
@@ -82,6 +97,17 @@ impl AssembledProgram {
     }
 }
 
+fn nearest_label(labels: &HashMap<String, u32>, address: u32) -> Option<String> {
+    let (label, base) = labels
+        .iter()
+        .filter(|(_, &base)| base <= address)
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))?;
+    Some(match address - base {
+        0 => label.clone(),
+        offset => format!("{label}+{offset:#x}"),
+    })
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Section {
     Data,
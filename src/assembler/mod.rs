@@ -10,7 +10,7 @@ use std::{
     str::FromStr,
 };
 
-use crate::isa::{Instruction, InstructionDefinition, InstructionFormat, Operands, ISA};
+use crate::isa::{Instruction, InstructionDefinition, InstructionFormat, Operands, CSR, ISA};
 
 #[derive(Debug)]
 struct DataItem {
@@ -261,6 +261,10 @@ fn parse_instruction(
         return Err("Empty instruction".to_string());
     }
 
+    if let Some(expanded) = expand_pseudo_instruction(&parts)? {
+        return parse_instruction(&expanded, text_labels, data_labels, current_address);
+    }
+
     let name = parts[0].to_uppercase();
     let def = ISA::from_str(&name)
         .map_err(|_| format!("Unknown instruction: {}", name))?
@@ -308,18 +312,20 @@ fn parse_r_type(parts: &[&str], def: InstructionDefinition) -> Result<Instructio
 fn parse_i_type(parts: &[&str], def: InstructionDefinition) -> Result<Instruction, String> {
     match def.opcode {
         0b0000011 => parse_load_type(&parts, def),
+        0b1110011 if def.funct3 != Some(0x0) => parse_csr_type(parts, def),
         0b1110011 => {
-            // Special handling for ECALL/EBREAK
+            // Special handling for ECALL/EBREAK/MRET
             if parts.len() != 1 {
-                return Err("ECALL/EBREAK instructions take no operands".to_string());
+                return Err("ECALL/EBREAK/MRET instructions take no operands".to_string());
             }
 
             let operands = Operands {
                 rd: 0,
                 rs1: 0,
-                imm: match parts[0] {
+                imm: match parts[0].to_uppercase().as_str() {
                     "ECALL" => 0,
                     "EBREAK" => 1,
+                    "MRET" => 0x302,
                     _ => unreachable!(),
                 },
                 ..Default::default()
@@ -369,6 +375,33 @@ fn parse_i_type(parts: &[&str], def: InstructionDefinition) -> Result<Instructio
     }
 }
 
+fn parse_csr_type(parts: &[&str], def: InstructionDefinition) -> Result<Instruction, String> {
+    if parts.len() != 4 {
+        return Err("CSR instructions need a register, a CSR and a source operand".to_string());
+    }
+
+    // CSRRWI, CSRRSI and CSRRCI take a 5-bit zero-extended immediate in place of rs1
+    let rs1 = if def.funct3.unwrap_or_default() & 0b100 != 0 {
+        let zimm = parse_immediate(parts[3])?;
+        if !(0..=31).contains(&zimm) {
+            return Err("CSR immediate out of range (0 to 31)".to_string());
+        }
+        zimm as u32
+    } else {
+        parse_register(parts[3])?
+    };
+
+    // The CSR number is unsigned, sign extend it so it fits the I-type immediate
+    let csr = parse_csr(parts[2])?;
+    let operands = Operands {
+        rd: parse_register(parts[1])?,
+        rs1,
+        imm: ((csr << 20) as i32) >> 20,
+        ..Default::default()
+    };
+    Ok(Instruction::from_def_operands(def, operands))
+}
+
 fn parse_load_type(parts: &[&str], def: InstructionDefinition) -> Result<Instruction, String> {
     if parts.len() != 3 {
         return Err("Load instructions need a register and a memory address".to_string());
@@ -517,6 +550,54 @@ fn parse_register(reg: &str) -> Result<u32, String> {
     }
 }
 
+fn parse_csr(csr: &str) -> Result<u32, String> {
+    if let Ok(csr) = CSR::from_str(csr.trim()) {
+        return Ok(csr.definition().address);
+    }
+
+    match parse_immediate(csr) {
+        Ok(num) if (0..=0xFFF).contains(&num) => Ok(num as u32),
+        Ok(_) => Err("CSR number out of range (0 to 4095)".to_string()),
+        Err(_) => Err(format!("Unknown CSR: {}", csr)),
+    }
+}
+
+/// Rewrites a pseudo-instruction into the base instruction it stands for
+fn expand_pseudo_instruction(parts: &[&str]) -> Result<Option<String>, String> {
+    let name = parts[0].to_lowercase();
+    let operands = &parts[1..];
+
+    let expected = match name.as_str() {
+        "csrr" | "csrw" | "csrs" | "csrc" | "csrwi" | "csrsi" | "csrci" => 2,
+        "rdcycle" | "rdinstret" | "rdtime" => 1,
+        _ => return Ok(None),
+    };
+    if operands.len() != expected {
+        return Err(format!(
+            "{} takes {} operand{}",
+            name,
+            expected,
+            if expected == 1 { "" } else { "s" }
+        ));
+    }
+
+    let expanded = match name.as_str() {
+        "csrr" => format!("csrrs {}, {}, x0", operands[0], operands[1]),
+        "csrw" => format!("csrrw x0, {}, {}", operands[0], operands[1]),
+        "csrs" => format!("csrrs x0, {}, {}", operands[0], operands[1]),
+        "csrc" => format!("csrrc x0, {}, {}", operands[0], operands[1]),
+        "csrwi" => format!("csrrwi x0, {}, {}", operands[0], operands[1]),
+        "csrsi" => format!("csrrsi x0, {}, {}", operands[0], operands[1]),
+        "csrci" => format!("csrrci x0, {}, {}", operands[0], operands[1]),
+        "rdcycle" => format!("csrrs {}, cycle, x0", operands[0]),
+        "rdinstret" => format!("csrrs {}, instret, x0", operands[0]),
+        // CVE2 doesn't implement the time CSR, reading it traps so it can be emulated
+        "rdtime" => format!("csrrs {}, 0xC01, x0", operands[0]),
+        _ => unreachable!(),
+    };
+    Ok(Some(expanded))
+}
+
 fn parse_immediate(value: &str) -> Result<i32, String> {
     let value = value.trim();
     let (is_negative, value) = if value.starts_with('-') {
@@ -543,6 +543,114 @@ fn test_EBREAK() {
     }
 }
 
+#[test]
+fn test_MRET() {
+    let program = ".text\nMRET";
+    let assembled_program = assemble(program).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let (inst_mem, _, _) = assembled_program.emulator_maps();
+
+    let expected_bytes = [0x73, 0x00, 0x20, 0x30];
+
+    for i in 0..4 {
+        assert_eq!(
+            inst_mem.get(&(i as u32)),
+            Some(&expected_bytes[i]),
+            "Mismatch at byte {} of MRET instruction",
+            i
+        );
+    }
+}
+
+#[test]
+fn test_CSRRW() {
+    let program = ".text\nCSRRW X5, mstatus, X6";
+    let assembled_program = assemble(program).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let (inst_mem, _, _) = assembled_program.emulator_maps();
+
+    let expected_bytes = [0xF3, 0x12, 0x03, 0x30];
+
+    for i in 0..4 {
+        assert_eq!(
+            inst_mem.get(&(i as u32)),
+            Some(&expected_bytes[i]),
+            "Mismatch at byte {} of CSRRW instruction",
+            i
+        );
+    }
+}
+
+#[test]
+fn test_CSRRSI() {
+    // CSR numbers above 0x7FF are unsigned
+    let program = ".text\nCSRRSI X1, 0xF14, 1";
+    let assembled_program = assemble(program).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let (inst_mem, _, _) = assembled_program.emulator_maps();
+
+    let expected_bytes = [0xF3, 0xE0, 0x40, 0xF1];
+
+    for i in 0..4 {
+        assert_eq!(
+            inst_mem.get(&(i as u32)),
+            Some(&expected_bytes[i]),
+            "Mismatch at byte {} of CSRRSI instruction",
+            i
+        );
+    }
+}
+
+#[test]
+fn assembler_csr_pseudo_instructions() {
+    let program = ".text
+        csrr x10, mcycle
+        csrw mscratch, x1
+        csrs mie, x2
+        csrc mie, x3
+        csrwi mstatus, 8
+        rdcycle x1
+        rdinstret x2
+        rdtime x3";
+    let assembled_program = assemble(program).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let (inst_mem, _, _) = assembled_program.emulator_maps();
+
+    let expected_32bit_instructions: Vec<(u32, u32)> = vec![
+        (0x00, 0xB0002573), // csrrs x10, mcycle, x0
+        (0x04, 0x34009073), // csrrw x0, mscratch, x1
+        (0x08, 0x30412073), // csrrs x0, mie, x2
+        (0x0C, 0x3041B073), // csrrc x0, mie, x3
+        (0x10, 0x30045073), // csrrwi x0, mstatus, 8
+        (0x14, 0xC00020F3), // csrrs x1, cycle, x0
+        (0x18, 0xC0202173), // csrrs x2, instret, x0
+        (0x1C, 0xC01021F3), // csrrs x3, time, x0
+    ];
+
+    for (addr, expected_instruction) in expected_32bit_instructions {
+        let actual_instruction = u32::from_le_bytes([
+            *inst_mem.get(&addr).unwrap(),
+            *inst_mem.get(&(addr + 1)).unwrap(),
+            *inst_mem.get(&(addr + 2)).unwrap(),
+            *inst_mem.get(&(addr + 3)).unwrap(),
+        ]);
+        assert_eq!(
+            actual_instruction, expected_instruction,
+            "Mismatch in reconstructed instruction at address 0x{:08X}",
+            addr
+        );
+    }
+}
+
+#[test]
+fn assembler_csr_errors() {
+    for program in [
+        ".text\ncsrrw x1, 0x1000, x2",
+        ".text\ncsrrw x1, -1, x2",
+        ".text\ncsrrw x1, notacsr, x2",
+        ".text\ncsrrwi x1, mstatus, 32",
+        ".text\ncsrr x1",
+    ] {
+        assert!(assemble(program).is_err(), "{:?} should not assemble", program);
+    }
+}
+
 #[test]
 fn test_BEQ() {
     let program = ".text\nlabel:\nBEQ X1, X2, label";
@@ -1294,3 +1402,24 @@ fn assembler_all_instructions() {
         }
     }
 }
+
+#[test]
+fn assembler_symbolize() {
+    let program = assemble(".text\nmain: addi x1, x0, 1\nloop: addi x1, x1, 1\naddi x1, x1, 1").unwrap();
+    assert_eq!(program.symbolize(0), Some("main".to_string()));
+    assert_eq!(program.symbolize(4), Some("loop".to_string()));
+    assert_eq!(program.symbolize(8), Some("loop+0x4".to_string()));
+
+    let program = assemble(".text 0x10\nmain: addi x1, x0, 1").unwrap();
+    assert_eq!(program.symbolize(0), None);
+}
+
+#[test]
+fn assembler_symbolize_data() {
+    let program = assemble(".data 0x100\nbuffer: .word 1\n.word 2\n.text\nmain: addi x1, x0, 1").unwrap();
+    assert_eq!(program.symbolize_data(0x100), Some("buffer".to_string()));
+    assert_eq!(program.symbolize_data(0x106), Some("buffer+0x6".to_string()));
+    // only addresses holding data are named
+    assert_eq!(program.symbolize_data(0x108), None);
+    assert_eq!(program.symbolize_data(0), None);
+}
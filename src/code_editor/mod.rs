@@ -17,7 +17,6 @@ pub fn CodeEditor(
     line_highlights: ReadOnlySignal<Vec<LineHighlight>>,
 ) -> Element {
     // basic model
-    // TODO: support external changes to source being reflected in the model
     let model = use_signal(|| {
         monaco::api::TextModel::create(source.peek().as_str(), Some("riscv"), None).unwrap()
     });
@@ -32,6 +31,14 @@ pub fn CodeEditor(
         })
     });
 
+    // external changes to source, like a restored snapshot
+    use_effect(move || {
+        let source = source.read();
+        if model.peek().get_value() != *source {
+            model.peek().set_value(&source);
+        }
+    });
+
     // basic options
     let options = use_signal(|| {
         let options = monaco::api::CodeEditorOptions::default()
@@ -17,6 +17,8 @@ use wasm_bindgen::{JsCast, JsValue};
 pub struct LineHighlight {
     pub line: usize,
     pub css_class: &'static str,
+    /// Shown when hovering the line
+    pub hover_message: Option<String>,
 }
 
 /// The monaco editor directly wrapped
@@ -65,6 +67,7 @@ pub fn MonacoEditor(
                     new_decor.push(&line_decoration(
                         line_highlight.line,
                         line_highlight.css_class,
+                        line_highlight.hover_message.as_deref(),
                     ));
                 }
 
@@ -86,7 +89,11 @@ pub fn MonacoEditor(
 }
 
 // Example usage function
-pub fn line_decoration(line_number: usize, color: &'static str) -> IModelDeltaDecoration {
+pub fn line_decoration(
+    line_number: usize,
+    color: &'static str,
+    hover_message: Option<&str>,
+) -> IModelDeltaDecoration {
     let decoration: IModelDeltaDecoration = new_object().into();
     let range = Range::new(line_number as f64, 0.0, line_number as f64, 1.0);
     decoration.set_range(&IRange::from(range.dyn_into::<JsValue>().unwrap()));
@@ -95,6 +102,12 @@ pub fn line_decoration(line_number: usize, color: &'static str) -> IModelDeltaDe
     options.set_is_whole_line(Some(true));
     options.set_z_index(Some(9999.0));
     options.set_class_name(Some(color));
+    if let Some(message) = hover_message {
+        // an IMarkdownString
+        let markdown = new_object();
+        js_sys::Reflect::set(&markdown, &"value".into(), &message.into()).unwrap();
+        options.set_hover_message(&markdown);
+    }
 
     decoration.set_options(&options);
 
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use super::config::RegionTiming;
+
+/// Most random stall cycles added to a single request
+const MAX_RANDOM_STALLS: u32 = 16;
+
+/// A request the memory has seen but not yet responded to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transaction {
+    pub address: u32,
+    /// Cycles until the request is granted
+    pub wait: u32,
+    /// Cycles from the grant until the response
+    pub latency: u32,
+    pub granted: bool,
+}
+
+/// The memory side of an instruction or data bus: a request is granted after
+/// the wait states of the region it addresses, and the response is valid
+/// `latency` cycles after the grant. With the default timing both happen in
+/// the cycle of the request.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Bus {
+    pub transaction: Option<Transaction>,
+    /// xorshift state for the random stalls
+    rng: u32,
+}
+
+impl Bus {
+    fn random_percent(&mut self) -> u32 {
+        if self.rng == 0 {
+            self.rng = 0x2545_F491;
+        }
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        self.rng % 100
+    }
+
+    /// Advances the bus by a cycle of a request for `address`, returning
+    /// whether the request is granted and whether the response is valid in
+    /// this cycle. A request for another address abandons the outstanding one.
+    /// `timing` is only asked when a new transaction starts, so a cache
+    /// behind the bus sees each access once.
+    pub fn step(&mut self, address: u32, timing: impl FnOnce() -> RegionTiming, stall_percent: u32) -> (bool, bool) {
+        let mut transaction = match self.transaction {
+            Some(transaction) if transaction.address == address => transaction,
+            _ => {
+                let timing = timing();
+                let mut stalls = 0;
+                while stalls < MAX_RANDOM_STALLS && self.random_percent() < stall_percent {
+                    stalls += 1;
+                }
+                Transaction {
+                    address,
+                    wait: timing.wait_states + stalls,
+                    latency: timing.latency.max(1),
+                    granted: false,
+                }
+            }
+        };
+
+        let mut granted = false;
+        if !transaction.granted {
+            if transaction.wait > 0 {
+                transaction.wait -= 1;
+                self.transaction = Some(transaction);
+                return (false, false);
+            }
+            transaction.granted = true;
+            granted = true;
+        }
+
+        transaction.latency -= 1;
+        if transaction.latency == 0 {
+            self.transaction = None;
+            (granted, true)
+        } else {
+            self.transaction = Some(transaction);
+            (granted, false)
+        }
+    }
+
+    /// Drops the outstanding request, like a trap does
+    pub fn cancel(&mut self) {
+        self.transaction = None;
+    }
+}
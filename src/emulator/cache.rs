@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+use super::config::RegionTiming;
+use super::memory_map::RegionKind;
+use super::EmulatorState;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Replacement {
+    /// Evict the line used longest ago
+    #[default]
+    Lru,
+    /// Evict the line filled longest ago
+    Fifo,
+    Random,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WritePolicy {
+    /// Stores only update the cache, dirty lines are written back on eviction
+    #[default]
+    WriteBack,
+    /// Stores always go to memory, a store miss doesn't allocate a line
+    WriteThrough,
+}
+
+/// Geometry and policies of a cache, sizes are in bytes and powers of two
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheConfig {
+    pub size: u32,
+    pub associativity: u32,
+    pub block_size: u32,
+    pub replacement: Replacement,
+    pub write_policy: WritePolicy,
+    /// Cycles from the request until a hit responds
+    pub hit_latency: u32,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            size: 256,
+            associativity: 2,
+            block_size: 16,
+            replacement: Replacement::default(),
+            write_policy: WritePolicy::default(),
+            hit_latency: 1,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Whether the sizes are powers of two, blocks hold at least a word and
+    /// the capacity fits at least one set
+    pub fn is_valid(&self) -> bool {
+        let powers = [self.size, self.associativity, self.block_size].iter().all(|n| n.is_power_of_two());
+        let set_size = self.associativity.checked_mul(self.block_size);
+        powers && self.block_size >= 4 && set_size.is_some_and(|set_size| set_size <= self.size)
+    }
+
+    pub fn sets(&self) -> u32 {
+        self.size / (self.block_size * self.associativity)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Line {
+    pub valid: bool,
+    pub dirty: bool,
+    pub tag: u32,
+    /// Access count when the line was last used, for LRU
+    pub last_used: u64,
+    /// Access count when the line was filled, for FIFO
+    pub filled: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStatistics {
+    pub hits: u64,
+    pub misses: u64,
+    /// Dirty lines written back to memory
+    pub writebacks: u64,
+}
+
+impl CacheStatistics {
+    pub fn hit_rate(&self) -> Option<f64> {
+        let accesses = self.hits + self.misses;
+        (accesses != 0).then(|| self.hits as f64 / accesses as f64)
+    }
+}
+
+/// Outcome of a cache access
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheAccess {
+    pub set: usize,
+    /// Line that was hit or filled, `None` for a store miss that didn't allocate
+    pub way: Option<usize>,
+    pub hit: bool,
+    /// A dirty line was evicted and written back
+    pub writeback: bool,
+}
+
+/// Tags of a set-associative cache. Only the timing is modeled, the data
+/// always stays in memory.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cache {
+    pub config: CacheConfig,
+    pub sets: Vec<Vec<Line>>,
+    pub statistics: CacheStatistics,
+    pub last_access: Option<CacheAccess>,
+    accesses: u64,
+    /// xorshift state for random replacement
+    rng: u32,
+}
+
+impl Cache {
+    pub fn new(config: CacheConfig) -> Cache {
+        assert!(config.is_valid(), "impossible cache shape {config:?}");
+        Cache {
+            config,
+            sets: vec![vec![Line::default(); config.associativity as usize]; config.sets() as usize],
+            statistics: CacheStatistics::default(),
+            last_access: None,
+            accesses: 0,
+            rng: 0x2545_F491,
+        }
+    }
+
+    /// Set index and tag of an address
+    pub fn locate(&self, address: u32) -> (usize, u32) {
+        let block = address / self.config.block_size;
+        let sets = self.config.sets();
+        ((block % sets) as usize, block / sets)
+    }
+
+    fn victim(&mut self, set: usize) -> usize {
+        let lines = &self.sets[set];
+        if let Some(way) = lines.iter().position(|line| !line.valid) {
+            return way;
+        }
+        let oldest = |key: fn(&Line) -> u64| {
+            (0..lines.len()).min_by_key(|&way| key(&lines[way])).unwrap_or(0)
+        };
+        match self.config.replacement {
+            Replacement::Lru => oldest(|line| line.last_used),
+            Replacement::Fifo => oldest(|line| line.filled),
+            Replacement::Random => {
+                self.rng ^= self.rng << 13;
+                self.rng ^= self.rng >> 17;
+                self.rng ^= self.rng << 5;
+                self.rng as usize % lines.len()
+            }
+        }
+    }
+
+    /// Looks up the block holding `address`, filling it on a miss
+    pub fn access(&mut self, address: u32, write: bool) -> CacheAccess {
+        self.accesses += 1;
+        let (set, tag) = self.locate(address);
+        let write_back = self.config.write_policy == WritePolicy::WriteBack;
+
+        let hit = self.sets[set].iter().position(|line| line.valid && line.tag == tag);
+        let access = if let Some(way) = hit {
+            let line = &mut self.sets[set][way];
+            line.last_used = self.accesses;
+            line.dirty |= write && write_back;
+            self.statistics.hits += 1;
+            CacheAccess {
+                set,
+                way: Some(way),
+                hit: true,
+                writeback: false,
+            }
+        } else if write && !write_back {
+            self.statistics.misses += 1;
+            CacheAccess {
+                set,
+                way: None,
+                hit: false,
+                writeback: false,
+            }
+        } else {
+            self.statistics.misses += 1;
+            let way = self.victim(set);
+            let line = &mut self.sets[set][way];
+            let writeback = line.valid && line.dirty;
+            *line = Line {
+                valid: true,
+                dirty: write,
+                tag,
+                last_used: self.accesses,
+                filled: self.accesses,
+            };
+            self.statistics.writebacks += writeback as u64;
+            CacheAccess {
+                set,
+                way: Some(way),
+                hit: false,
+                writeback,
+            }
+        };
+        self.last_access = Some(access);
+        access
+    }
+
+    /// Bus timing of an access given the memory's timing: a hit answers after
+    /// the hit latency, a miss reads the block from memory one word per cycle
+    /// after the first, after writing back a dirty victim the same way.
+    /// Stores to a write-through cache always wait for the memory.
+    pub fn timing(&mut self, address: u32, write: bool, memory: RegionTiming) -> RegionTiming {
+        let access = self.access(address, write);
+        if write && self.config.write_policy == WritePolicy::WriteThrough {
+            return memory;
+        }
+        if access.hit {
+            return RegionTiming {
+                wait_states: 0,
+                latency: self.config.hit_latency.max(1),
+            };
+        }
+        let block = memory.wait_states + memory.latency.max(1) + self.config.block_size / 4 - 1;
+        RegionTiming {
+            wait_states: if access.writeback { block } else { 0 },
+            latency: block,
+        }
+    }
+}
+
+/// Bus timing of an access to a region through an optional cache, device
+/// registers are never cached
+pub(super) fn timing(
+    cache: &mut Option<Cache>,
+    memory: RegionTiming,
+    kind: Option<RegionKind>,
+    address: u32,
+    write: bool,
+) -> RegionTiming {
+    match cache {
+        Some(cache) if kind != Some(RegionKind::Mmio) => cache.timing(address, write, memory),
+        _ => memory,
+    }
+}
+
+impl EmulatorState {
+    /// Replaces a cache whose configuration changed with an empty one
+    pub fn sync_caches(&mut self) {
+        let sync = |cache: &mut Option<Cache>, config: Option<CacheConfig>| {
+            if cache.as_ref().map(|cache| cache.config) != config {
+                *cache = config.map(Cache::new);
+            }
+        };
+        sync(&mut self.icache, self.config.icache);
+        sync(&mut self.dcache, self.config.dcache);
+    }
+}
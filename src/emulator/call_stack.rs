@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::EmulatorState;
+
+/// `ra` and the alternate link register `t0`, see the RAS hints for JAL/JALR in the spec
+pub fn is_link(register: usize) -> bool {
+    register == 1 || register == 5
+}
+
+/// Registers the callee has to preserve (`ra` is saved by a non-leaf function)
+pub fn is_saved_register(register: usize) -> bool {
+    matches!(register, 1 | 8 | 9 | 18..=27)
+}
+
+/// A function activation tracked through the JAL/JALR link convention
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    /// Address of the called function
+    pub target: u32,
+    /// Address of the call instruction, `None` for the program's entry point
+    pub call_site: Option<u32>,
+    /// Link address the call returns to
+    pub return_address: u32,
+    /// `sp` on entry, the frame's stack region lies below it
+    pub sp: u32,
+    /// Registers at the call, to check what the function has to preserve
+    pub registers: [u32; 32],
+    /// Stack slots the function saved registers to: address -> register
+    pub saved: BTreeMap<u32, usize>,
+}
+
+/// Shadow call stack maintained alongside execution, innermost frame last
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CallStack {
+    pub frames: Vec<Frame>,
+}
+
+impl CallStack {
+    pub fn push(
+        &mut self,
+        target: u32,
+        call_site: Option<u32>,
+        return_address: u32,
+        registers: [u32; 32],
+    ) {
+        self.frames.push(Frame {
+            target,
+            call_site,
+            return_address,
+            sp: registers[2],
+            registers,
+            saved: BTreeMap::new(),
+        });
+    }
+
+    /// Returns to `target`, unwinding every frame above the one that returns
+    /// there. A return that doesn't match any frame leaves the stack alone.
+    pub fn pop(&mut self, target: u32) -> Option<Frame> {
+        let index = self
+            .frames
+            .iter()
+            .rposition(|frame| frame.return_address == target)?;
+        let frame = self.frames.remove(index);
+        self.frames.truncate(index);
+        Some(frame)
+    }
+
+    /// Records a word store of a saved register into the innermost frame
+    pub(super) fn record_store(&mut self, address: u32, register: usize) {
+        if let Some(frame) = self.frames.last_mut() {
+            if is_saved_register(register) && address < frame.sp {
+                frame.saved.insert(address, register);
+            }
+        }
+    }
+
+    /// Stack region `(low, high)` of every frame, innermost last; the
+    /// innermost frame extends down to the current `sp`
+    pub fn regions(&self, sp: u32) -> Vec<(u32, u32)> {
+        let lows = self.frames.iter().skip(1).map(|frame| frame.sp).chain([sp]);
+        self.frames
+            .iter()
+            .zip(lows)
+            .map(|(frame, low)| (low.min(frame.sp), frame.sp))
+            .collect()
+    }
+}
+
+impl EmulatorState {
+    /// Applies the call/return hints of a jump from ID to `target`: a link
+    /// `rd` is a call, a link `rs1` (JALR only) that isn't also `rd` is a return
+    pub(super) fn track_jump(&mut self, rd: usize, rs1: Option<usize>, target: u32) {
+        let pc = self.pipeline.ID_pc;
+        let check = self.config.check_calling_convention;
+
+        if rs1.is_some_and(|rs1| is_link(rs1) && rs1 != rd) {
+            if let Some(frame) = self.call_stack.pop(target) {
+                if check {
+                    self.checker.check_return(pc, &frame, &self.x.x);
+                }
+            }
+        }
+        if is_link(rd) {
+            if check {
+                self.checker.check_call(pc, self.call_stack.frames.last(), &self.x.x);
+            }
+            self.call_stack.push(target, Some(pc), pc.wrapping_add(4), self.x.x);
+        }
+    }
+}
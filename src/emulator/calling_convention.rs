@@ -0,0 +1,136 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::call_stack::Frame;
+use crate::bits;
+use crate::isa::{Instruction, InstructionDefinition, InstructionFormat};
+
+/// ABI names of the registers, used in violation messages
+pub const ABI_NAMES: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
+/// `s0`-`s11`, which a function has to restore before returning
+const CALLEE_SAVED: [usize; 12] = [8, 9, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27];
+
+/// Temporaries and argument registers a call may leave holding garbage,
+/// `a0` and `a1` hold the return value
+const CALLER_SAVED: [usize; 13] = [5, 6, 7, 12, 13, 14, 15, 16, 17, 28, 29, 30, 31];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViolationKind {
+    /// A callee-saved register differs from its value at the call
+    CalleeSavedNotRestored(usize),
+    /// `sp` differs from its value at the call
+    StackPointerNotRestored,
+    /// A call overwrote `ra` before the function saved it to the stack
+    ReturnAddressNotSaved,
+    /// A caller-saved register was read after a call without being written
+    ReadAfterCall(usize),
+    /// `sp` wasn't 16 byte aligned at a call, holds `sp`
+    MisalignedStack(u32),
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViolationKind::CalleeSavedNotRestored(register) => write!(
+                f,
+                "callee-saved register {} was not restored before returning",
+                ABI_NAMES[*register]
+            ),
+            ViolationKind::StackPointerNotRestored => {
+                write!(f, "sp was not restored before returning")
+            }
+            ViolationKind::ReturnAddressNotSaved => {
+                write!(f, "call overwrites ra, which was not saved on the stack")
+            }
+            ViolationKind::ReadAfterCall(register) => write!(
+                f,
+                "caller-saved register {} is read after a call without being set, the callee may have changed it",
+                ABI_NAMES[*register]
+            ),
+            ViolationKind::MisalignedStack(sp) => {
+                write!(f, "sp ({sp:#010x}) is not 16 byte aligned at a call")
+            }
+        }
+    }
+}
+
+/// A calling convention violation by the instruction at `pc`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    pub pc: u32,
+    pub kind: ViolationKind,
+}
+
+/// Checks execution against the RISC-V calling convention, using the calls
+/// and returns tracked by the shadow call stack
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CallingConventionChecker {
+    /// Every distinct violation in the order it occurred
+    pub violations: Vec<Violation>,
+    /// Caller-saved registers not written since the last return
+    stale: [bool; 32],
+}
+
+impl CallingConventionChecker {
+    fn report(&mut self, pc: u32, kind: ViolationKind) {
+        let violation = Violation { pc, kind };
+        if !self.violations.contains(&violation) {
+            self.violations.push(violation);
+        }
+    }
+
+    /// Checks the registers an instruction reads, called once when it enters ID
+    pub(super) fn check_instruction(&mut self, pc: u32, instr: Instruction) {
+        let Some(def) = InstructionDefinition::from_instr(instr) else {
+            return;
+        };
+        let csr_immediate = def.opcode == 0b1110011 && bits!(instr.funct3(), 2) == 1;
+        let (reads, writes): (&[u8], bool) = match def.format {
+            InstructionFormat::R => (&[instr.rs1(), instr.rs2()], true),
+            InstructionFormat::I if csr_immediate => (&[], true),
+            InstructionFormat::I => (&[instr.rs1()], true),
+            InstructionFormat::S | InstructionFormat::B => (&[instr.rs1(), instr.rs2()], false),
+            InstructionFormat::U | InstructionFormat::J => (&[], true),
+        };
+
+        for &register in reads {
+            if self.stale[register as usize] {
+                self.report(pc, ViolationKind::ReadAfterCall(register as usize));
+            }
+        }
+        if writes {
+            self.stale[instr.rd() as usize] = false;
+        }
+    }
+
+    /// Checks a call from `caller` (the innermost frame, if any)
+    pub(super) fn check_call(&mut self, pc: u32, caller: Option<&Frame>, registers: &[u32; 32]) {
+        if caller.is_some_and(|frame| !frame.saved.values().any(|&register| register == 1)) {
+            self.report(pc, ViolationKind::ReturnAddressNotSaved);
+        }
+        if !registers[2].is_multiple_of(16) {
+            self.report(pc, ViolationKind::MisalignedStack(registers[2]));
+        }
+    }
+
+    /// Checks a return from `frame`, after which the caller-saved registers are stale
+    pub(super) fn check_return(&mut self, pc: u32, frame: &Frame, registers: &[u32; 32]) {
+        if registers[2] != frame.sp {
+            self.report(pc, ViolationKind::StackPointerNotRestored);
+        }
+        for register in CALLEE_SAVED {
+            if registers[register] != frame.registers[register] {
+                self.report(pc, ViolationKind::CalleeSavedNotRestored(register));
+            }
+        }
+        for register in CALLER_SAVED {
+            self.stale[register] = true;
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use super::mmio::MemoryMapped;
+
+/// Base address of the core local interruptor, the usual SiFive/QEMU location
+pub const CLINT_BASE: u32 = 0x0200_0000;
+const CLINT_SIZE: u32 = 0x1_0000;
+
+const MSIP: u32 = 0x0000;
+const MTIMECMP: u32 = 0x4000;
+const MTIMECMPH: u32 = 0x4004;
+const MTIME: u32 = 0xBFF8;
+const MTIMEH: u32 = 0xBFFC;
+
+/// Core local interruptor providing the machine timer and software interrupts
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Clint {
+    pub msip: bool,
+    /// Incremented once per clock
+    pub mtime: u64,
+    pub mtimecmp: u64,
+}
+
+impl Default for Clint {
+    fn default() -> Self {
+        Self {
+            msip: false,
+            mtime: 0,
+            // no timer interrupt until software programs a compare value
+            mtimecmp: u64::MAX,
+        }
+    }
+}
+
+impl Clint {
+    pub fn tick(&mut self) {
+        self.mtime = self.mtime.wrapping_add(1);
+    }
+
+    pub fn timer_pending(&self) -> bool {
+        self.mtime >= self.mtimecmp
+    }
+}
+
+fn set_low(value: &mut u64, low: u32) {
+    *value = *value & !0xFFFF_FFFF | low as u64;
+}
+
+fn set_high(value: &mut u64, high: u32) {
+    *value = *value & 0xFFFF_FFFF | (high as u64) << 32;
+}
+
+impl Clint {
+    fn register(&self, address: u32) -> u32 {
+        match address - CLINT_BASE {
+            MSIP => self.msip as u32,
+            MTIMECMP => self.mtimecmp as u32,
+            MTIMECMPH => (self.mtimecmp >> 32) as u32,
+            MTIME => self.mtime as u32,
+            MTIMEH => (self.mtime >> 32) as u32,
+            _ => 0,
+        }
+    }
+
+    fn set_register(&mut self, address: u32, value: u32) {
+        match address - CLINT_BASE {
+            MSIP => self.msip = value & 1 != 0,
+            MTIMECMP => set_low(&mut self.mtimecmp, value),
+            MTIMECMPH => set_high(&mut self.mtimecmp, value),
+            MTIME => set_low(&mut self.mtime, value),
+            MTIMEH => set_high(&mut self.mtime, value),
+            _ => (),
+        }
+    }
+}
+
+impl MemoryMapped for Clint {
+    const NAME: &'static str = "CLINT";
+
+    fn base(&self) -> u32 {
+        CLINT_BASE
+    }
+
+    fn size(&self) -> u32 {
+        CLINT_SIZE
+    }
+
+    fn read(&mut self, address: u32) -> u32 {
+        self.register(address)
+    }
+
+    fn write(&mut self, address: u32, value: u32, mask: u32) {
+        let value = self.register(address) & !mask | value & mask;
+        self.set_register(address, value);
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+use super::cache::CacheConfig;
+use super::loader::HALT_ADDRESS;
+use super::memory_map::{RegionKind, HEAP_SIZE, STACK_SIZE, STACK_TOP};
+use super::prediction::PredictorConfig;
+
+/// How loads and stores to addresses that aren't naturally aligned are handled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MisalignedAccess {
+    /// Like CVE2's LSU, an access crossing a word boundary is split into two
+    /// bus transactions and takes an extra cycle
+    #[default]
+    Split,
+    /// Raise a load/store address misaligned exception
+    Trap,
+}
+
+/// How many cycles instructions spend in ID/EX
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimingModel {
+    /// Instructions take the cycles the handlers need: taken branches and
+    /// jumps two, loads and stores one more per bus transaction
+    #[default]
+    Approximate,
+    /// The per-instruction cycle counts documented for CVE2
+    CycleAccurate,
+}
+
+/// How long the memory behind a region takes to answer a bus request
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionTiming {
+    /// Cycles a request waits before it is granted
+    pub wait_states: u32,
+    /// Cycles from the grant until the response is valid, at least one
+    pub latency: u32,
+}
+
+impl Default for RegionTiming {
+    fn default() -> Self {
+        Self {
+            wait_states: 0,
+            latency: 1,
+        }
+    }
+}
+
+/// Timing of the instruction and data buses
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BusConfig {
+    /// Timing by the kind of region addressed, other kinds and unmapped
+    /// addresses answer in the cycle of the request
+    pub regions: BTreeMap<RegionKind, RegionTiming>,
+    /// Chance in percent that an instruction fetch waits another cycle for its grant
+    pub instr_stall_percent: u32,
+    /// Chance in percent that a load or store waits another cycle for its grant
+    pub data_stall_percent: u32,
+}
+
+impl BusConfig {
+    pub fn timing(&self, kind: Option<RegionKind>) -> RegionTiming {
+        kind.and_then(|kind| self.regions.get(&kind))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Where the program loader reserves the stack and heap, and the return
+/// address it gives the entry point
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoaderConfig {
+    /// Initial stack pointer, the stack grows down from here
+    pub stack_top: u32,
+    pub stack_size: u32,
+    pub heap_size: u32,
+    /// Execution halts once it returns here from the entry point
+    pub halt_address: u32,
+}
+
+impl Default for LoaderConfig {
+    fn default() -> Self {
+        Self {
+            stack_top: STACK_TOP,
+            stack_size: STACK_SIZE,
+            heap_size: HEAP_SIZE,
+            halt_address: HALT_ADDRESS,
+        }
+    }
+}
+
+/// Options that change how the core behaves, kept across reassembly
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmulatorConfig {
+    pub misaligned_access: MisalignedAccess,
+    pub loader: LoaderConfig,
+    /// Report calling convention violations at calls and returns
+    pub check_calling_convention: bool,
+    pub timing: TimingModel,
+    pub bus: BusConfig,
+    /// Instruction cache between the fetch stage and memory, none if `None`
+    #[serde(default, deserialize_with = "cache_config")]
+    pub icache: Option<CacheConfig>,
+    /// Data cache between the LSU and memory, none if `None`
+    #[serde(default, deserialize_with = "cache_config")]
+    pub dcache: Option<CacheConfig>,
+    /// Branch predictor, none if `None`
+    pub predictor: Option<PredictorConfig>,
+}
+
+/// Saved and shared configurations are checked for cache shapes that can't exist
+fn cache_config<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<CacheConfig>, D::Error> {
+    match Option::<CacheConfig>::deserialize(deserializer)? {
+        Some(config) if !config.is_valid() => Err(D::Error::custom(format!(
+            "impossible cache of {} B with {} ways of {} B blocks",
+            config.size, config.associativity, config.block_size
+        ))),
+        config => Ok(config),
+    }
+}
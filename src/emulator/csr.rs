@@ -0,0 +1,62 @@
+use super::EmulatorState;
+use crate::isa::CSR;
+
+impl EmulatorState {
+    /// Reads a CSR, registers that were never written hold their reset value
+    pub fn read_csr(&self, csr: CSR) -> u32 {
+        let csr = csr.alias_of().unwrap_or(csr);
+        match csr {
+            // pending interrupts come straight from the interrupt inputs
+            CSR::MIP => {
+                let datapath = &self.pipeline.datapath;
+                (datapath.irq_software_i as u32) << 3
+                    | (datapath.irq_timer_i as u32) << 7
+                    | (datapath.irq_external_i as u32) << 11
+                    | (datapath.irq_fast_i as u32) << 16
+            }
+            _ => {
+                let def = csr.definition();
+                self.csr.get(&def.address).copied().unwrap_or(def.reset)
+            }
+        }
+    }
+
+    /// Writes a CSR, only the bits in its write mask change
+    pub fn write_csr(&mut self, csr: CSR, value: u32) {
+        let def = csr.definition();
+        if def.write_mask == 0 {
+            return;
+        }
+
+        let old = self.read_csr(csr);
+        self.csr
+            .insert(def.address, old & !def.write_mask | value & def.write_mask);
+    }
+
+    /// Reads a 64-bit counter from its low and high halves
+    pub fn read_counter(&self, low: CSR, high: CSR) -> u64 {
+        (self.read_csr(high) as u64) << 32 | self.read_csr(low) as u64
+    }
+
+    fn write_counter(&mut self, low: CSR, high: CSR, value: u64) {
+        self.write_csr(low, value as u32);
+        self.write_csr(high, (value >> 32) as u32);
+    }
+
+    /// Advances mcycle every clock and minstret for each retired instruction.
+    /// `before` holds both counters from the start of the clock, a counter the
+    /// instruction wrote explicitly keeps the written value instead.
+    pub(super) fn update_counters(&mut self, before: (u64, u64), retired: bool) {
+        let inhibit = self.read_csr(CSR::MCOUNTINHIBIT);
+
+        let mcycle = self.read_counter(CSR::MCYCLE, CSR::MCYCLEH);
+        if mcycle == before.0 && inhibit & 0b001 == 0 {
+            self.write_counter(CSR::MCYCLE, CSR::MCYCLEH, mcycle.wrapping_add(1));
+        }
+
+        let minstret = self.read_counter(CSR::MINSTRET, CSR::MINSTRETH);
+        if retired && minstret == before.1 && inhibit & 0b100 == 0 {
+            self.write_counter(CSR::MINSTRET, CSR::MINSTRETH, minstret.wrapping_add(1));
+        }
+    }
+}
@@ -1,8 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use super::bus::Bus;
+
 /// Struct representing the datapath for the `cve2_top` module.
 /// Taken from https://github.com/openhwgroup/cve2/blob/main/rtl/cve2_top.sv
 
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct CVE2Datapath {
     // Clock and Reset
     pub clk_i: bool,  // Input clock signal.
@@ -78,12 +82,21 @@ impl Default for CVE2Datapath {
 }
 
 #[allow(non_snake_case)]
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
 pub struct CVE2Pipeline {
     pub IF: u32,    // Instruction Fetch Buffer
     pub IF_pc: u32, // Program Counter for the IF stage
     pub ID: u32,    // Instruction Decode Buffer
     pub ID_pc: u32, // Program Counter for the ID stage
+    pub IF_err: bool, // Fetching the instruction in IF failed
+    pub IF_valid: bool, // IF holds the instruction fetched from IF_pc
+    pub ID_err: bool, // Fetching the instruction in ID failed
+    pub LSU_rdata: u32, // Data loaded by the first half of a split access
+    pub stall_cycles: u32, // Cycles the timing model still holds the instruction in ID
+    pub fetched: bool, // IF held an instruction during the last clock
+    pub flushed: bool, // The last clock trapped, squashing IF and ID
+    pub instr_bus: Bus, // Memory side of the instruction bus
+    pub data_bus: Bus, // Memory side of the data bus
     pub datapath: CVE2Datapath,
 }
 
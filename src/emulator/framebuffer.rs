@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use super::mmio::MemoryMapped;
+
+/// Base address of the pixel framebuffer
+pub const FRAMEBUFFER_BASE: u32 = 0x2000_0000;
+pub const FRAMEBUFFER_WIDTH: usize = 32;
+pub const FRAMEBUFFER_HEIGHT: usize = 32;
+
+/// Row-major pixels, one `0x00RRGGBB` word each
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Framebuffer {
+    pub pixels: Vec<u32>,
+}
+
+impl Default for Framebuffer {
+    fn default() -> Self {
+        Self {
+            pixels: vec![0; FRAMEBUFFER_WIDTH * FRAMEBUFFER_HEIGHT],
+        }
+    }
+}
+
+impl MemoryMapped for Framebuffer {
+    const NAME: &'static str = "Framebuffer";
+
+    fn base(&self) -> u32 {
+        FRAMEBUFFER_BASE
+    }
+
+    fn size(&self) -> u32 {
+        (4 * FRAMEBUFFER_WIDTH * FRAMEBUFFER_HEIGHT) as u32
+    }
+
+    fn read(&mut self, address: u32) -> u32 {
+        self.pixels[(address - FRAMEBUFFER_BASE) as usize / 4]
+    }
+
+    fn write(&mut self, address: u32, value: u32, mask: u32) {
+        let pixel = &mut self.pixels[(address - FRAMEBUFFER_BASE) as usize / 4];
+        *pixel = *pixel & !mask | value & mask & 0x00FF_FFFF;
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use super::mmio::MemoryMapped;
+
+/// Base address of the board I/O registers
+pub const GPIO_BASE: u32 = 0x1000_1000;
+const GPIO_SIZE: u32 = 0x10;
+
+/// One bit per LED, LED 0 is the rightmost
+const LEDS: u32 = 0x0;
+/// One byte of segments per digit, digit 0 is the rightmost.
+/// Bits 0-6 drive segments a-g and bit 7 the decimal point.
+const SEVEN_SEGMENT: u32 = 0x4;
+/// One bit per DIP switch (read-only)
+const SWITCHES: u32 = 0x8;
+/// One bit per push button (read-only)
+const BUTTONS: u32 = 0xC;
+
+pub const LED_COUNT: usize = 16;
+pub const DIGIT_COUNT: usize = 4;
+pub const SWITCH_COUNT: usize = 16;
+pub const BUTTON_COUNT: usize = 4;
+
+/// LEDs and seven-segment displays driven by stores, switches and buttons read by loads
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Gpio {
+    pub leds: u16,
+    pub seven_segment: [u8; DIGIT_COUNT],
+    pub switches: u16,
+    pub buttons: u8,
+}
+
+impl Gpio {
+    fn register(&self, address: u32) -> u32 {
+        match address - GPIO_BASE {
+            LEDS => self.leds as u32,
+            SEVEN_SEGMENT => u32::from_le_bytes(self.seven_segment),
+            SWITCHES => self.switches as u32,
+            BUTTONS => self.buttons as u32,
+            _ => 0,
+        }
+    }
+}
+
+impl MemoryMapped for Gpio {
+    const NAME: &'static str = "GPIO";
+
+    fn base(&self) -> u32 {
+        GPIO_BASE
+    }
+
+    fn size(&self) -> u32 {
+        GPIO_SIZE
+    }
+
+    fn read(&mut self, address: u32) -> u32 {
+        self.register(address)
+    }
+
+    fn write(&mut self, address: u32, value: u32, mask: u32) {
+        let value = self.register(address) & !mask | value & mask;
+        match address - GPIO_BASE {
+            LEDS => self.leds = value as u16,
+            SEVEN_SEGMENT => self.seven_segment = value.to_le_bytes(),
+            _ => (),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 #![allow(non_snake_case)]
 
-use super::{EmulatorState, InstructionHandler};
-use crate::isa::Instruction;
+use super::{EmulatorState, Exception, InstructionHandler, MisalignedAccess};
+use crate::isa::{Instruction, CSR};
 use crate::{bitmask, bits};
 
 pub fn get_handler(instr: Instruction) -> Result<InstructionHandler, ()> {
@@ -52,6 +52,10 @@ pub fn get_handler(instr: Instruction) -> Result<InstructionHandler, ()> {
             0b0000_0000_0001_00000_000_00000_1110011 => Ok(EBREAK),
             _ => Err(()),
         },
+        (0b1110011, 0b000, 0b0011000) => match instr.raw() {
+            0b0011_0000_0010_00000_000_00000_1110011 => Ok(MRET),
+            _ => Err(()),
+        },
         (0b1110011, 0b001, _) => Ok(CSRRW),
         (0b1110011, 0b010, _) => Ok(CSRRS),
         (0b1110011, 0b011, _) => Ok(CSRRC),
@@ -75,24 +79,34 @@ fn AUIPC(instr: &Instruction, state: &mut EmulatorState) {
     
     // immediate should already be shifted by the appropriate amount by encoder
     let immediate = instr.immediate().unwrap() as i32;
-    let result = state.pipeline.ID_pc as i32 + immediate;
+    let result = state.pipeline.ID_pc.wrapping_add_signed(immediate);
 
-    state.x[rd] = result as u32;
+    state.x[rd] = result;
+}
+
+/// Raises an instruction address misaligned exception for a jump or taken
+/// branch to `target`, without the C extension targets are word aligned
+fn misaligned_target(state: &mut EmulatorState, target: u32) -> bool {
+    let misaligned = target & 0b11 != 0;
+    if misaligned {
+        state.exception = Some(Exception::InstructionAddressMisaligned(target));
+    }
+    misaligned
 }
 
 fn JAL(instr: &Instruction, state: &mut EmulatorState) {
-    // TODO: Push onto Return Address stack when rd = x1 or x5
     if state.pipeline.datapath.id_multicycle == 0 {
         let immed = (instr.immediate()).unwrap();
-        let new_pc = state.pipeline.ID_pc.checked_add_signed(immed).unwrap();
+        let new_pc = state.pipeline.ID_pc.wrapping_add_signed(immed);
 
-        // if unaligned on 4-byte boundary
-        if new_pc & 0x00000003 != 0x00 {
-            panic!("JAL instruction immediate it not on a 4-byte boundary");
+        if misaligned_target(state, new_pc) {
+            return;
         }
+
         // stores pc+4 into rd
         let rd = instr.rd() as usize;
-        state.x[rd] = state.pipeline.ID_pc + 4;
+        state.x[rd] = state.pipeline.ID_pc.wrapping_add(4);
+        state.track_jump(rd, None, new_pc);
 
         // update PC
         state.pipeline.datapath.instr_addr_o = new_pc;
@@ -105,19 +119,18 @@ fn JAL(instr: &Instruction, state: &mut EmulatorState) {
 }
 
 fn JALR(instr: &Instruction, state: &mut EmulatorState) {
-    // TODO: Push onto RAS
     if state.pipeline.datapath.id_multicycle == 0 {
         let immed = (instr.immediate()).unwrap();
-        let new_pc = (state.x[instr.rs1() as usize] as i32 + immed) as u32 & bitmask!(31;1);
+        let new_pc = state.x[instr.rs1() as usize].wrapping_add_signed(immed) & bitmask!(31;1);
 
-        // if unaligned on 4-byte boundary
-        if new_pc & 0x003 != 0x00 {
-            panic!("JALR target addess is not on a 4-byte boundary");
+        if misaligned_target(state, new_pc) {
+            return;
         }
 
         // stores pc+4 into rd
         let rd = instr.rd() as usize;
-        state.x[rd] = state.pipeline.ID_pc + 4;
+        state.x[rd] = state.pipeline.ID_pc.wrapping_add(4);
+        state.track_jump(rd, Some(instr.rs1() as usize), new_pc);
 
         // update PC
         state.pipeline.datapath.instr_addr_o = new_pc;
@@ -132,14 +145,12 @@ fn JALR(instr: &Instruction, state: &mut EmulatorState) {
 fn BEQ(instr: &Instruction, state: &mut EmulatorState) {
     if state.pipeline.datapath.id_multicycle == 0 {
         let immed = (instr.immediate()).unwrap();
-        let new_pc = state.pipeline.ID_pc.checked_add_signed(immed).unwrap();
-
-        // if unaligned on 4-byte boundary
-        if new_pc & 0x003 != 0x00 {
-            panic!("BEQ instruction immediate it not on a 4-byte boundary");
-        }
+        let new_pc = state.pipeline.ID_pc.wrapping_add_signed(immed);
 
         if state.x[instr.rs1() as usize] == state.x[instr.rs2() as usize] {
+            if misaligned_target(state, new_pc) {
+                return;
+            }
             // update PC
             state.pipeline.datapath.instr_addr_o = new_pc;
             state.pipeline.datapath.fetch_enable_i = false;
@@ -154,14 +165,12 @@ fn BEQ(instr: &Instruction, state: &mut EmulatorState) {
 fn BNE(instr: &Instruction, state: &mut EmulatorState) {
     if state.pipeline.datapath.id_multicycle == 0 {
         let immed = (instr.immediate()).unwrap();
-        let new_pc = state.pipeline.ID_pc.checked_add_signed(immed).unwrap();
-
-        // if unaligned on 4-byte boundary
-        if new_pc & 0x003 != 0x00 {
-            panic!("BNE instruction immediate it not on a 4-byte boundary");
-        }
+        let new_pc = state.pipeline.ID_pc.wrapping_add_signed(immed);
 
         if state.x[instr.rs1() as usize] != state.x[instr.rs2() as usize] {
+            if misaligned_target(state, new_pc) {
+                return;
+            }
             // update PC
             state.pipeline.datapath.instr_addr_o = new_pc;
             state.pipeline.datapath.fetch_enable_i = false;
@@ -176,14 +185,12 @@ fn BNE(instr: &Instruction, state: &mut EmulatorState) {
 fn BLT(instr: &Instruction, state: &mut EmulatorState) {
     if state.pipeline.datapath.id_multicycle == 0 {
         let immed = (instr.immediate()).unwrap();
-        let new_pc = state.pipeline.ID_pc.checked_add_signed(immed).unwrap();
-
-        // if unaligned on 4-byte boundary
-        if new_pc & 0x003 != 0x00 {
-            panic!("BLT instruction immediate it not on a 4-byte boundary");
-        }
+        let new_pc = state.pipeline.ID_pc.wrapping_add_signed(immed);
 
         if (state.x[instr.rs1() as usize] as i32) < state.x[instr.rs2() as usize] as i32 {
+            if misaligned_target(state, new_pc) {
+                return;
+            }
             // update PC
             state.pipeline.datapath.instr_addr_o = new_pc;
             state.pipeline.datapath.fetch_enable_i = false;
@@ -198,14 +205,12 @@ fn BLT(instr: &Instruction, state: &mut EmulatorState) {
 fn BGE(instr: &Instruction, state: &mut EmulatorState) {
     if state.pipeline.datapath.id_multicycle == 0 {
         let immed = (instr.immediate()).unwrap();
-        let new_pc = state.pipeline.ID_pc.checked_add_signed(immed).unwrap();
-
-        // if unaligned on 4-byte boundary
-        if new_pc & 0x003 != 0x00 {
-            panic!("BGE instruction immediate it not on a 4-byte boundary");
-        }
+        let new_pc = state.pipeline.ID_pc.wrapping_add_signed(immed);
 
         if (state.x[instr.rs1() as usize] as i32) >= state.x[instr.rs2() as usize] as i32 {
+            if misaligned_target(state, new_pc) {
+                return;
+            }
             // update PC
             state.pipeline.datapath.instr_addr_o = new_pc;
             state.pipeline.datapath.fetch_enable_i = false;
@@ -220,18 +225,12 @@ fn BGE(instr: &Instruction, state: &mut EmulatorState) {
 fn BLTU(instr: &Instruction, state: &mut EmulatorState) {
     if state.pipeline.datapath.id_multicycle == 0 {
         let immed = (instr.immediate()).unwrap();
-        let new_pc = state.pipeline.ID_pc.checked_add_signed(immed).unwrap();
-
-        // if unaligned on 4-byte boundary
-        if new_pc & 0x003 != 0x00 {
-            panic!("BLTU instruction immediate it not on a 4-byte boundary");
-        }
+        let new_pc = state.pipeline.ID_pc.wrapping_add_signed(immed);
 
         if state.x[instr.rs1() as usize] < state.x[instr.rs2() as usize] {
-            // stores pc+4 into rd
-            let rd = instr.rd() as usize;
-            state.x[rd] = state.pipeline.datapath.instr_addr_o + 4;
-
+            if misaligned_target(state, new_pc) {
+                return;
+            }
             // update PC
             state.pipeline.datapath.instr_addr_o = new_pc;
             state.pipeline.datapath.fetch_enable_i = false;
@@ -246,18 +245,12 @@ fn BLTU(instr: &Instruction, state: &mut EmulatorState) {
 fn BGEU(instr: &Instruction, state: &mut EmulatorState) {
     if state.pipeline.datapath.id_multicycle == 0 {
         let immed = (instr.immediate()).unwrap();
-        let new_pc = state.pipeline.ID_pc.checked_add_signed(immed).unwrap();
-
-        // if unaligned on 4-byte boundary
-        if new_pc & 0x003 != 0x00 {
-            panic!("BGEU instruction immediate it not on a 4-byte boundary");
-        }
+        let new_pc = state.pipeline.ID_pc.wrapping_add_signed(immed);
 
         if state.x[instr.rs1() as usize] >= state.x[instr.rs2() as usize] {
-            // stores pc+4 into rd
-            let rd = instr.rd() as usize;
-            state.x[rd] = state.pipeline.datapath.instr_addr_o + 4;
-
+            if misaligned_target(state, new_pc) {
+                return;
+            }
             // update PC
             state.pipeline.datapath.instr_addr_o = new_pc;
             state.pipeline.datapath.fetch_enable_i = false;
@@ -269,209 +262,160 @@ fn BGEU(instr: &Instruction, state: &mut EmulatorState) {
     }
 }
 
-fn LB(instr: &Instruction, state: &mut EmulatorState) {
-    if state.pipeline.datapath.id_multicycle == 0 {
-        let address: u32 = (state.x[instr.rs1() as usize] as i32
-            + instr.immediate().unwrap()) as u32;
-
-        // set data on pipline
-        let byte_enable = [true, false, false, false];
-        state.pipeline.datapath.data_req_o = true;
-        state.pipeline.datapath.data_addr_o = address;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = byte_enable;
-        state.pipeline.datapath.id_multicycle = 1;
-        state.pipeline.datapath.fetch_enable_i = false;
-    } else {
-        let read_value = state.pipeline.datapath.data_rdata_i;
-        state.x[instr.rd() as usize] = bitmask!(31;8) * bits!(read_value, 7) | read_value;
-        
-        state.pipeline.datapath.data_req_o = false;
-        state.pipeline.datapath.data_addr_o = 0;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = [false; 4];
-        state.pipeline.datapath.id_multicycle = 0;
-        state.pipeline.datapath.fetch_enable_i = true;
+/// Byte enables for `size` bytes starting at `offset` in a word, followed by
+/// those for the bytes that spill over into the next word
+fn data_lanes(offset: u32, size: u32) -> ([bool; 4], [bool; 4]) {
+    let bytes = offset..offset + size;
+    (
+        std::array::from_fn(|i| bytes.contains(&(i as u32))),
+        std::array::from_fn(|i| bytes.contains(&(i as u32 + 4))),
+    )
+}
+
+/// Puts a data transaction on the bus, the bus is word aligned and the
+/// byte enables select the lanes that are accessed
+fn data_request(state: &mut EmulatorState, address: u32, byte_enable: [bool; 4], wdata: Option<u32>) {
+    state.pipeline.datapath.data_req_o = true;
+    state.pipeline.datapath.data_addr_o = address & !0b11;
+    state.pipeline.datapath.data_we_o = wdata.is_some();
+    state.pipeline.datapath.data_be_o = byte_enable;
+    state.pipeline.datapath.data_wdata_o = wdata.unwrap_or(0);
+}
+
+/// Waits for the response to the outstanding data request, taking back the
+/// request once the memory granted it. Returns whether the response is valid.
+fn data_response(state: &mut EmulatorState) -> bool {
+    let datapath = &mut state.pipeline.datapath;
+    if datapath.data_gnt_i && !datapath.data_rvalid_i {
+        datapath.data_req_o = false;
+    }
+    datapath.data_rvalid_i
+}
+
+fn data_release(state: &mut EmulatorState) {
+    state.pipeline.datapath.data_req_o = false;
+    state.pipeline.datapath.data_addr_o = 0;
+    state.pipeline.datapath.data_we_o = false;
+    state.pipeline.datapath.data_be_o = [false; 4];
+    state.pipeline.datapath.data_wdata_o = 0;
+    state.pipeline.datapath.id_multicycle = 0;
+    state.pipeline.datapath.fetch_enable_i = true;
+}
+
+/// Loads `size` bytes once the memory responds, with the default bus timing
+/// the data is on the bus in the second cycle. An access crossing a word
+/// boundary is split like CVE2's LSU does, taking another transaction, unless
+/// misaligned accesses are configured to trap.
+fn load(instr: &Instruction, state: &mut EmulatorState, size: u32, signed: bool) {
+    let address: u32 =
+        state.x[instr.rs1() as usize].wrapping_add_signed(instr.immediate().unwrap());
+    let offset = address & 0b11;
+    let (first, second) = data_lanes(offset, size);
+    let split = second.contains(&true);
+
+    let multicycle = state.pipeline.datapath.id_multicycle;
+    if multicycle != 0 && !data_response(state) {
+        return;
+    }
+    match multicycle {
+        0 => {
+            if !address.is_multiple_of(size) && state.config.misaligned_access == MisalignedAccess::Trap {
+                state.exception = Some(Exception::LoadAddressMisaligned(address));
+                return;
+            }
+            data_request(state, address, first, None);
+            state.pipeline.datapath.id_multicycle = 1;
+            state.pipeline.datapath.fetch_enable_i = false;
+        }
+        1 if split => {
+            state.pipeline.LSU_rdata = state.pipeline.datapath.data_rdata_i;
+            data_request(state, address.wrapping_add(4), second, None);
+            state.pipeline.datapath.id_multicycle = 2;
+        }
+        _ => {
+            // move the addressed lanes down to the low bytes
+            let rdata = if split {
+                state.pipeline.LSU_rdata >> (8 * offset)
+                    | state.pipeline.datapath.data_rdata_i << (8 * (4 - offset))
+            } else {
+                state.pipeline.datapath.data_rdata_i >> (8 * offset)
+            };
+            let unused = 32 - 8 * size;
+            state.x[instr.rd() as usize] = if signed {
+                ((rdata << unused) as i32 >> unused) as u32
+            } else {
+                rdata << unused >> unused
+            };
+            data_release(state);
+        }
     }
 }
 
-fn LH(instr: &Instruction, state: &mut EmulatorState) {
-    if state.pipeline.datapath.id_multicycle == 0 {
-        let address: u32 = (state.x[instr.rs1() as usize] as i32
-            + instr.immediate().unwrap()) as u32;
-
-        // set data on pipline
-        let byte_enable = [true, true, false, false];
-        state.pipeline.datapath.data_req_o = true;
-        state.pipeline.datapath.data_addr_o = address;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = byte_enable;
-        state.pipeline.datapath.id_multicycle = 1;
-        state.pipeline.datapath.fetch_enable_i = false;
-    } else {
-        let read_value = state.pipeline.datapath.data_rdata_i;
-        state.x[instr.rd() as usize] = bitmask!(31;16) * bits!(read_value, 15) | read_value;
-        
-        state.pipeline.datapath.data_req_o = false;
-        state.pipeline.datapath.data_addr_o = 0;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = [false; 4];
-        state.pipeline.datapath.id_multicycle = 0;
-        state.pipeline.datapath.fetch_enable_i = true;
+/// Stores the low `size` bytes of rs2, rotated into the addressed lanes. Waits
+/// for the bus the same way a load does.
+fn store(instr: &Instruction, state: &mut EmulatorState, size: u32) {
+    let address: u32 =
+        state.x[instr.rs1() as usize].wrapping_add_signed(instr.immediate().unwrap());
+    let offset = address & 0b11;
+    let (first, second) = data_lanes(offset, size);
+    let split = second.contains(&true);
+    let wdata = state.x[instr.rs2() as usize].rotate_left(8 * offset);
+
+    let multicycle = state.pipeline.datapath.id_multicycle;
+    if multicycle != 0 && !data_response(state) {
+        return;
     }
+    match multicycle {
+        0 => {
+            if !address.is_multiple_of(size) && state.config.misaligned_access == MisalignedAccess::Trap {
+                state.exception = Some(Exception::StoreAddressMisaligned(address));
+                return;
+            }
+            if size == 4 {
+                state.call_stack.record_store(address, instr.rs2() as usize);
+            }
+            data_request(state, address, first, Some(wdata));
+            state.pipeline.datapath.id_multicycle = 1;
+            state.pipeline.datapath.fetch_enable_i = false;
+        }
+        1 if split => {
+            data_request(state, address.wrapping_add(4), second, Some(wdata));
+            state.pipeline.datapath.id_multicycle = 2;
+        }
+        _ => data_release(state),
+    }
+}
+
+fn LB(instr: &Instruction, state: &mut EmulatorState) {
+    load(instr, state, 1, true);
+}
+
+fn LH(instr: &Instruction, state: &mut EmulatorState) {
+    load(instr, state, 2, true);
 }
 
 fn LW(instr: &Instruction, state: &mut EmulatorState) {
-    if state.pipeline.datapath.id_multicycle == 0 {
-        let address: u32 = (state.x[instr.rs1() as usize] as i32
-            + instr.immediate().unwrap()) as u32;
-
-        // set data on pipline
-        let byte_enable = [true, true, true, true];
-        state.pipeline.datapath.data_req_o = true;
-        state.pipeline.datapath.data_addr_o = address;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = byte_enable;
-        state.pipeline.datapath.id_multicycle = 1;
-        state.pipeline.datapath.fetch_enable_i = false;
-    } else {
-        let read_value = state.pipeline.datapath.data_rdata_i;
-        
-        state.pipeline.datapath.data_req_o = false;
-        state.pipeline.datapath.data_addr_o = 0;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = [false; 4];
-        state.x[instr.rd() as usize] = read_value;
-        state.pipeline.datapath.id_multicycle = 0;
-        state.pipeline.datapath.fetch_enable_i = true;
-    }
+    load(instr, state, 4, true);
 }
 
 fn LBU(instr: &Instruction, state: &mut EmulatorState) {
-    if state.pipeline.datapath.id_multicycle == 0 {
-        let address: u32 = (state.x[instr.rs1() as usize] as i32
-            + instr.immediate().unwrap()) as u32;
-
-        // set data on pipline
-        let byte_enable = [true, false, false, false];
-        state.pipeline.datapath.data_req_o = true;
-        state.pipeline.datapath.data_addr_o = address;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = byte_enable;
-        state.pipeline.datapath.id_multicycle = 1;
-        state.pipeline.datapath.fetch_enable_i = false;
-    } else {
-        let read_value = state.pipeline.datapath.data_rdata_i;
-        
-        state.pipeline.datapath.data_req_o = false;
-        state.pipeline.datapath.data_addr_o = 0;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = [false; 4];
-        state.x[instr.rd() as usize] = read_value;
-        state.pipeline.datapath.id_multicycle = 0;
-        state.pipeline.datapath.fetch_enable_i = true;
-    }
+    load(instr, state, 1, false);
 }
 
 fn LHU(instr: &Instruction, state: &mut EmulatorState) {
-    if state.pipeline.datapath.id_multicycle == 0 {
-        let address: u32 = (state.x[instr.rs1() as usize] as i32
-            + instr.immediate().unwrap()) as u32;
-
-        // set data on pipline
-        let byte_enable = [true, true, false, false];
-        state.pipeline.datapath.data_req_o = true;
-        state.pipeline.datapath.data_addr_o = address;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = byte_enable;
-        state.pipeline.datapath.id_multicycle = 1;
-        state.pipeline.datapath.fetch_enable_i = false;
-    } else {
-        let read_value = state.pipeline.datapath.data_rdata_i;
-        
-        state.pipeline.datapath.data_req_o = false;
-        state.pipeline.datapath.data_addr_o = 0;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = [false; 4];
-        state.x[instr.rd() as usize] = read_value;
-        state.pipeline.datapath.id_multicycle = 0;
-        state.pipeline.datapath.fetch_enable_i = true;
-    }
+    load(instr, state, 2, false);
 }
 
 fn SB(instr: &Instruction, state: &mut EmulatorState) {
-    if state.pipeline.datapath.id_multicycle == 0 {
-        let data = state.x[instr.rs2() as usize];
-        let address: u32 =
-            (state.x[instr.rs1() as usize] as i32 + instr.immediate().unwrap()) as u32;
-
-        // set data on pipline
-        state.pipeline.datapath.data_req_o = true;
-        state.pipeline.datapath.data_addr_o = address;
-        state.pipeline.datapath.data_we_o = true;
-        state.pipeline.datapath.data_be_o = [true, false, false, false]; // access LSB only
-        state.pipeline.datapath.data_wdata_o = data;
-        state.pipeline.datapath.id_multicycle = 1;
-        state.pipeline.datapath.fetch_enable_i = false;
-    } else {
-        state.pipeline.datapath.data_req_o = false;
-        state.pipeline.datapath.data_addr_o = 0;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = [false; 4];
-        state.pipeline.datapath.data_wdata_o = 0;
-        state.pipeline.datapath.id_multicycle = 0;
-        state.pipeline.datapath.fetch_enable_i = true;
-    }
+    store(instr, state, 1);
 }
 
 fn SH(instr: &Instruction, state: &mut EmulatorState) {
-    if state.pipeline.datapath.id_multicycle == 0 {
-        let data = state.x[instr.rs2() as usize];
-        let address: u32 =
-            (state.x[instr.rs1() as usize] as i32 + instr.immediate().unwrap()) as u32;
-
-        // set data on pipline
-        state.pipeline.datapath.data_req_o = true;
-        state.pipeline.datapath.data_addr_o = address;
-        state.pipeline.datapath.data_we_o = true;
-        state.pipeline.datapath.data_be_o = [true, true, false, false]; 
-        state.pipeline.datapath.data_wdata_o = data;
-        state.pipeline.datapath.id_multicycle = 1;
-        state.pipeline.datapath.fetch_enable_i = false;
-    } else {
-        state.pipeline.datapath.data_req_o = false;
-        state.pipeline.datapath.data_addr_o = 0;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = [false; 4];
-        state.pipeline.datapath.data_wdata_o = 0;
-        state.pipeline.datapath.id_multicycle = 0;
-        state.pipeline.datapath.fetch_enable_i = true;
-    }
+    store(instr, state, 2);
 }
 
 fn SW(instr: &Instruction, state: &mut EmulatorState) {
-    if state.pipeline.datapath.id_multicycle == 0 {
-        let data = state.x[instr.rs2() as usize];
-        let address: u32 =
-            (state.x[instr.rs1() as usize] as i32 + instr.immediate().unwrap()) as u32;
-
-        // set data on pipline
-        state.pipeline.datapath.data_req_o = true;
-        state.pipeline.datapath.data_addr_o = address;
-        state.pipeline.datapath.data_we_o = true;
-        state.pipeline.datapath.data_be_o = [true, true, true, true]; 
-        state.pipeline.datapath.data_wdata_o = data;
-        state.pipeline.datapath.id_multicycle = 1;
-        state.pipeline.datapath.fetch_enable_i = false;
-    } else {
-        state.pipeline.datapath.data_req_o = false;
-        state.pipeline.datapath.data_addr_o = 0;
-        state.pipeline.datapath.data_we_o = false;
-        state.pipeline.datapath.data_be_o = [false; 4];
-        state.pipeline.datapath.data_wdata_o = 0;
-        state.pipeline.datapath.id_multicycle = 0;
-        state.pipeline.datapath.fetch_enable_i = true;
-    }
+    store(instr, state, 4);
 }
 
 fn ADDI(instr: &Instruction, state: &mut EmulatorState) {
@@ -482,7 +426,7 @@ fn ADDI(instr: &Instruction, state: &mut EmulatorState) {
     // must match sign
     let rs = state.x[rs] as i32;
 
-    state.x[rd] = (rs + immediate) as u32;
+    state.x[rd] = rs.wrapping_add(immediate) as u32;
 }
 
 fn SLTI(instr: &Instruction, state: &mut EmulatorState) {
@@ -542,8 +486,12 @@ fn SRxI(instr: &Instruction, state: &mut EmulatorState) {
     let immediate = instr.immediate().unwrap() as u32;
 
     let shamt = immediate & 0x1F;
-    state.x[rd] = state.x[rs] >> (immediate & 0x1F)
-        | (bitmask!(31;32-shamt) * bits!(state.x[rs], 31) * bits!(instr.raw(), 30));
+    // bit 30 distinguishes SRAI from SRLI
+    state.x[rd] = if bits!(instr.raw(), 30) == 1 {
+        (state.x[rs] as i32 >> shamt) as u32
+    } else {
+        state.x[rs] >> shamt
+    };
 }
 
 fn ADD(instr: &Instruction, state: &mut EmulatorState) {
@@ -551,7 +499,7 @@ fn ADD(instr: &Instruction, state: &mut EmulatorState) {
     let rs1 = instr.rs1() as usize;
     let rs2 = instr.rs2() as usize;
 
-    state.x[rd] = (state.x[rs1] as i32 + state.x[rs2] as i32) as u32;
+    state.x[rd] = state.x[rs1].wrapping_add(state.x[rs2]);
 }
 
 fn SUB(instr: &Instruction, state: &mut EmulatorState) {
@@ -559,7 +507,7 @@ fn SUB(instr: &Instruction, state: &mut EmulatorState) {
     let rs1 = instr.rs1() as usize;
     let rs2 = instr.rs2() as usize;
 
-    state.x[rd] = (state.x[rs1] as i32 - state.x[rs2] as i32) as u32;
+    state.x[rd] = state.x[rs1].wrapping_sub(state.x[rs2]);
 }
 
 fn SLL(instr: &Instruction, state: &mut EmulatorState) {
@@ -654,13 +602,16 @@ fn FENCE_TSO(instr: &Instruction, state: &mut EmulatorState) {
 
 #[allow(unused_variables)]
 fn PAUSE(instr: &Instruction, state: &mut EmulatorState) {
-    todo!()
+    /*
+     * Hint that the hart is spinning, there are no other harts to yield
+     * to so it executes as a NOP (Zihintpause)
+     */
 }
 
 #[allow(unused_variables)]
 fn ECALL(instr: &Instruction, state: &mut EmulatorState) {
-    todo!()
-    /* System call */
+    /* System call, the trap handler services it */
+    state.exception = Some(Exception::EnvironmentCall);
 }
 
 #[allow(unused_variables)]
@@ -669,97 +620,80 @@ fn EBREAK(instr: &Instruction, state: &mut EmulatorState) {
     state.pipeline.datapath.debug_req_i = true;
 }
 
-fn CSRRW(instr: &Instruction, state: &mut EmulatorState) {
-    let csr = instr.immediate().unwrap() as u32;
-    let rd = instr.rd() as usize;
-    let rs1 = instr.rs1() as usize;
-
-    // if rd = x0, CSR shall do nothing
-    if rd == 0 {
-        return;
+#[allow(unused_variables)]
+fn MRET(instr: &Instruction, state: &mut EmulatorState) {
+    if state.pipeline.datapath.id_multicycle == 0 {
+        // restore MIE from MPIE and set MPIE
+        let mstatus = state.read_csr(CSR::MSTATUS);
+        let mpie = bits!(mstatus, 7);
+        state.write_csr(CSR::MSTATUS, mstatus & !(1 << 3) | mpie << 3 | 1 << 7);
+
+        // return to the address saved on trap entry
+        state.pipeline.datapath.instr_addr_o = state.read_csr(CSR::MEPC);
+        state.nmi_mode = false;
+        state.pipeline.datapath.fetch_enable_i = false;
+        state.pipeline.datapath.id_multicycle = 1;
+    } else {
+        state.pipeline.datapath.id_multicycle = 0;
+        state.pipeline.datapath.fetch_enable_i = true;
     }
+}
 
-    let tmp = if state.csr.contains_key(&csr) {
-        state.csr[&csr]
-    } else {
-        0
+/// Common behavior of the Zicsr instructions: rd gets the old CSR value and,
+/// if `write` is set, the CSR is replaced with `op(old)`.
+/// Accessing a CSR that doesn't exist or writing a read-only one is illegal.
+fn csr_access(
+    instr: &Instruction,
+    state: &mut EmulatorState,
+    write: bool,
+    op: impl Fn(u32) -> u32,
+) {
+    let csr = match CSR::from_address(instr.csr()) {
+        Some(csr) if !(write && csr.is_read_only()) => csr,
+        _ => {
+            state.exception = Some(Exception::IllegalInstruction(instr.raw()));
+            return;
+        }
     };
 
-    state.csr.insert(csr, state.x[rs1]);
-    state.x[rd] = tmp;
+    let old = state.read_csr(csr);
+    if write {
+        state.write_csr(csr, op(old));
+    }
+    state.x[instr.rd() as usize] = old;
 }
 
-fn CSRRS(instr: &Instruction, state: &mut EmulatorState) {
-    let csr = instr.immediate().unwrap() as u32;
-    let rd = instr.rd() as usize;
-    let rs1 = instr.rs1() as usize;
-
-    let tmp = if state.csr.contains_key(&csr) {
-        state.csr[&csr]
-    } else {
-        0
-    };
+fn CSRRW(instr: &Instruction, state: &mut EmulatorState) {
+    // the write happens even when rd = x0
+    let value = state.x[instr.rs1() as usize];
+    csr_access(instr, state, true, |_| value);
+}
 
-    state.csr.insert(csr, state.x[rs1] | tmp);
-    state.x[rd] = tmp;
+fn CSRRS(instr: &Instruction, state: &mut EmulatorState) {
+    // rs1 = x0 reads without writing
+    let mask = state.x[instr.rs1() as usize];
+    csr_access(instr, state, instr.rs1() != 0, |old| old | mask);
 }
 
 fn CSRRC(instr: &Instruction, state: &mut EmulatorState) {
-    let csr = instr.immediate().unwrap() as u32;
-    let rd = instr.rd() as usize;
-    let rs1 = instr.rs1() as usize;
-
-    let tmp = if state.csr.contains_key(&csr) {
-        state.csr[&csr]
-    } else {
-        0
-    };
-
-    state.csr.insert(csr, tmp & !state.x[rs1]);
-    state.x[rd] = tmp;
+    // rs1 = x0 reads without writing
+    let mask = state.x[instr.rs1() as usize];
+    csr_access(instr, state, instr.rs1() != 0, |old| old & !mask);
 }
 
 fn CSRRWI(instr: &Instruction, state: &mut EmulatorState) {
-    let csr = instr.immediate().unwrap() as u32;
-    let rd = instr.rd() as usize;
     let zimm = instr.rs1() as u32;
-
-    let tmp = if state.csr.contains_key(&csr) {
-        state.csr[&csr]
-    } else {
-        0
-    };
-
-    state.csr.insert(csr, zimm);
-    state.x[rd] = tmp;
+    csr_access(instr, state, true, |_| zimm);
 }
 
 fn CSRRSI(instr: &Instruction, state: &mut EmulatorState) {
-    let csr = instr.immediate().unwrap() as u32;
-    let rd = instr.rd() as usize;
+    // zimm = 0 reads without writing
     let zimm = instr.rs1() as u32;
-
-    let tmp = if state.csr.contains_key(&csr) {
-        state.csr[&csr]
-    } else {
-        0
-    };
-
-    state.csr.insert(csr, tmp | zimm);
-    state.x[rd] = tmp;
+    csr_access(instr, state, zimm != 0, |old| old | zimm);
 }
 
 fn CSRRCI(instr: &Instruction, state: &mut EmulatorState) {
-    let csr = instr.immediate().unwrap() as u32;
-    let rd = instr.rd() as usize;
+    // zimm = 0 reads without writing
     let zimm = instr.rs1() as u32;
-
-    let tmp = if state.csr.contains_key(&csr) {
-        state.csr[&csr]
-    } else {
-        0
-    };
-
-    state.csr.insert(csr, tmp & !zimm);
-    state.x[rd] = tmp;
+    csr_access(instr, state, zimm != 0, |old| old & !zimm);
 }
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use super::EmulatorState;
+use crate::assembler::AssembledProgram;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditTarget {
+    Register(usize),
+    /// A word of data memory
+    Memory(u32),
+}
+
+/// A value changed by hand in the debugger
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edit {
+    /// Clock cycle the edit was made at
+    pub cycle: u64,
+    pub target: EditTarget,
+    pub old: u32,
+    pub new: u32,
+}
+
+fn read_word(memory: &AssembledProgram, address: u32) -> u32 {
+    u32::from_le_bytes(std::array::from_fn(|i| {
+        memory.data_memory.get(&(address + i as u32)).copied().unwrap_or_default()
+    }))
+}
+
+fn write_word(memory: &mut AssembledProgram, address: u32, value: u32) {
+    for (i, byte) in value.to_le_bytes().into_iter().enumerate() {
+        memory.data_memory.insert(address + i as u32, byte);
+    }
+}
+
+impl EmulatorState {
+    fn record_edit(&mut self, target: EditTarget, old: u32, new: u32) {
+        self.edits.push(Edit {
+            cycle: self.statistics.cycles,
+            target,
+            old,
+            new,
+        });
+    }
+
+    /// Sets a register, writes to `x0` are ignored like any other
+    pub fn edit_register(&mut self, register: usize, value: u32) {
+        if register == 0 {
+            return;
+        }
+        let old = self.x[register];
+        self.x[register] = value;
+        self.record_edit(EditTarget::Register(register), old, value);
+    }
+
+    /// Sets the data memory word at `address`, which is word aligned
+    pub fn edit_memory(&mut self, program: &mut AssembledProgram, address: u32, value: u32) {
+        let address = address & !0b11;
+        let old = read_word(program, address);
+        write_word(program, address, value);
+        self.record_edit(EditTarget::Memory(address), old, value);
+    }
+
+    /// Reverts the most recent edit
+    pub fn undo_edit(&mut self, program: &mut AssembledProgram) -> Option<Edit> {
+        let edit = self.edits.pop()?;
+        match edit.target {
+            EditTarget::Register(register) => self.x[register] = edit.old,
+            EditTarget::Memory(address) => write_word(program, address, edit.old),
+        }
+        Some(edit)
+    }
+
+    /// Registers the last clock wrote a new value to
+    pub fn changed_registers(&self) -> Vec<usize> {
+        (1..32).filter(|&i| self.x[i] != self.previous_x[i]).collect()
+    }
+
+    /// Addresses of the bytes the last clock stored to
+    pub fn written_bytes(&self) -> Vec<u32> {
+        let datapath = &self.pipeline.datapath;
+        if !datapath.data_req_o || !datapath.data_we_o || datapath.data_err_i {
+            return Vec::new();
+        }
+        (0..4)
+            .filter(|&i| datapath.data_be_o[i])
+            .map(|i| datapath.data_addr_o.wrapping_add(i as u32))
+            .collect()
+    }
+}
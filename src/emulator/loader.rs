@@ -0,0 +1,68 @@
+use super::config::LoaderConfig;
+use super::{EmulatorState, RegionKind};
+use crate::assembler::{AssembledProgram, Section};
+
+/// Default return address of the entry point, nothing is mapped here
+pub const HALT_ADDRESS: u32 = 0xFFFF_FFF0;
+
+/// Labels tried in order for the entry point, falling back to the start of `.text`
+const ENTRY_LABELS: [&str; 2] = ["_start", "main"];
+
+/// Offset of `gp` into `.data`, so the first 4KiB are in reach of a 12-bit immediate
+const GLOBAL_POINTER_OFFSET: u32 = 0x800;
+
+/// Address execution starts at for a program
+pub fn entry_point(program: &AssembledProgram) -> u32 {
+    ENTRY_LABELS
+        .iter()
+        .find_map(|label| program.labels.get(*label).copied())
+        .unwrap_or_else(|| program.get_section_start(Section::Text))
+}
+
+impl EmulatorState {
+    /// Prepares a reset state to run a program: maps its memory, starts
+    /// fetching at the entry point and sets up `sp`, `gp` and `ra` the way
+    /// the calling convention expects
+    pub fn load_program(&mut self, program: &AssembledProgram) {
+        let loader = &self.config.loader;
+        let stack_pointer = loader.stack_top & !0xF;
+        let halt_address = loader.halt_address;
+        let entry = entry_point(program);
+
+        self.memory_map = self.program_memory_map(program);
+        self.pipeline.datapath.instr_addr_o = entry;
+        self.x[1] = halt_address;
+        self.x[2] = stack_pointer;
+        self.x[3] = program.get_section_start(Section::Data) + GLOBAL_POINTER_OFFSET;
+        self.call_stack.push(entry, None, halt_address, self.x.x);
+    }
+
+    /// Moves the stack and heap along with their regions in the memory map,
+    /// rejecting a layout that overlaps the program or the devices. `sp` and
+    /// `ra` start from the new values when the program is loaded again.
+    pub fn set_loader(&mut self, loader: LoaderConfig) -> Result<(), String> {
+        if loader.halt_address & 0b11 != 0 {
+            return Err(format!("The halt address {:#010x} isn't word aligned", loader.halt_address));
+        }
+        let mut memory_map = self.memory_map.clone();
+        for region in &mut memory_map.regions {
+            match region.kind {
+                RegionKind::Heap => region.size = loader.heap_size,
+                RegionKind::Stack => {
+                    region.base = loader.stack_top.wrapping_sub(loader.stack_size);
+                    region.size = loader.stack_size;
+                }
+                _ => (),
+            }
+        }
+        memory_map.check_loader_regions()?;
+        self.memory_map = memory_map;
+        self.config.loader = loader;
+        Ok(())
+    }
+
+    /// Whether the program returned from its entry point to the halt address
+    pub fn halted(&self) -> bool {
+        self.pipeline.ID_pc == self.config.loader.halt_address
+    }
+}
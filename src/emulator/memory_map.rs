@@ -0,0 +1,252 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+use super::mmio::MemoryMapped;
+use super::{Clint, EmulatorState, Framebuffer, Gpio, Uart};
+use crate::assembler::AssembledProgram;
+
+/// Default top of the stack region, the stack grows down from here
+pub const STACK_TOP: u32 = 0x8000_0000;
+pub const STACK_SIZE: u32 = 0x1_0000;
+pub const HEAP_SIZE: u32 = 0x1_0000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RegionKind {
+    Text,
+    Data,
+    Heap,
+    Stack,
+    Mmio,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Permissions {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl Permissions {
+    pub const RX: Permissions = Permissions {
+        read: true,
+        write: false,
+        execute: true,
+    };
+    pub const RW: Permissions = Permissions {
+        read: true,
+        write: true,
+        execute: false,
+    };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Region {
+    #[serde(deserialize_with = "region_name")]
+    pub name: RegionName,
+    pub kind: RegionKind,
+    pub base: u32,
+    pub size: u32,
+    pub permissions: Permissions,
+}
+
+impl Region {
+    pub fn contains(&self, address: u32) -> bool {
+        address.wrapping_sub(self.base) < self.size
+    }
+
+    /// Whether the two regions share an address, regions wrapping past the
+    /// end of the address space are taken to run on past it
+    pub fn overlaps(&self, other: &Region) -> bool {
+        let end = |region: &Region| region.base as u64 + region.size as u64;
+        (self.base as u64) < end(other) && (other.base as u64) < end(self)
+    }
+
+    pub fn allows(&self, access: Access) -> bool {
+        match access {
+            Access::Read => self.permissions.read,
+            Access::Write => self.permissions.write,
+            Access::Execute => self.permissions.execute,
+        }
+    }
+}
+
+/// Regions of the address space a program may access.
+///
+/// Instruction and data memory are separate, so text and data regions may
+/// overlap; an access is allowed when any region holding it permits it.
+/// An empty map disables checking and behaves like flat memory.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryMap {
+    pub regions: Vec<Region>,
+}
+
+impl MemoryMap {
+    /// Checks every enabled byte of an access, returning the region that
+    /// permits it (`None` for an empty map) or `Err` for an access fault
+    pub fn check(
+        &self,
+        address: u32,
+        byte_enable: [bool; 4],
+        access: Access,
+    ) -> Result<Option<&Region>, ()> {
+        if self.regions.is_empty() {
+            return Ok(None);
+        }
+
+        let mut permitting = None;
+        for i in (0..4).filter(|&i| byte_enable[i]) {
+            let addr = address.wrapping_add(i as u32);
+            // where text overlaps another region the access goes to data memory
+            let region = self
+                .regions
+                .iter()
+                .filter(|region| region.contains(addr) && region.allows(access))
+                .min_by_key(|region| region.kind == RegionKind::Text)
+                .ok_or(())?;
+            permitting.get_or_insert(region);
+        }
+        Ok(permitting)
+    }
+
+    /// Checks that the heap and stack the loader reserves fit in the address
+    /// space and stay clear of the program's sections, the devices and each other
+    pub fn check_loader_regions(&self) -> Result<(), String> {
+        let reserved = self
+            .regions
+            .iter()
+            .filter(|region| matches!(region.kind, RegionKind::Heap | RegionKind::Stack));
+        for region in reserved {
+            if region.base as u64 + region.size as u64 > 1 << 32 {
+                return Err(format!("The {} doesn't fit in the address space", region.name));
+            }
+            let overlapping = self
+                .regions
+                .iter()
+                .find(|other| other.name != region.name && other.overlaps(region));
+            if let Some(other) = overlapping {
+                return Err(format!("The {} overlaps {}", region.name, other.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Regions are named after their section or device. Spelled as an alias so
+/// the derived `Deserialize` doesn't borrow it from the input.
+type RegionName = &'static str;
+
+/// A snapshot's region names are matched against the section and device names
+fn region_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<RegionName, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    [".text", ".data", "heap", "stack", Clint::NAME, Uart::NAME, Gpio::NAME, Framebuffer::NAME]
+        .into_iter()
+        .find(|known| *known == name)
+        .ok_or_else(|| D::Error::custom(format!("unknown memory region {name}")))
+}
+
+fn device<D: MemoryMapped>(device: &D) -> Region {
+    Region {
+        name: D::NAME,
+        kind: RegionKind::Mmio,
+        base: device.base(),
+        size: device.size(),
+        permissions: Permissions::RW,
+    }
+}
+
+/// Smallest `(base, size)` covering the addresses, rounded out to whole words
+fn extent<'a>(mut addresses: impl DoubleEndedIterator<Item = &'a u32>) -> Option<(u32, u32)> {
+    let first = *addresses.next()? & !0b11;
+    let last = addresses.next_back().copied().unwrap_or(first) | 0b11;
+    Some((first, last - first + 1))
+}
+
+impl EmulatorState {
+    /// The memory map for a program: its text and data sections, a heap
+    /// after the data, the stack below the configured stack top and every device
+    pub fn program_memory_map(&self, program: &AssembledProgram) -> MemoryMap {
+        let loader = &self.config.loader;
+        let mut regions = Vec::new();
+
+        let text = extent(program.instruction_memory.keys());
+        if let Some((base, size)) = text {
+            regions.push(Region {
+                name: ".text",
+                kind: RegionKind::Text,
+                base,
+                size,
+                permissions: Permissions::RX,
+            });
+        }
+
+        let data = extent(program.data_memory.keys());
+        if let Some((base, size)) = data {
+            regions.push(Region {
+                name: ".data",
+                kind: RegionKind::Data,
+                base,
+                size,
+                permissions: Permissions::RW,
+            });
+        }
+
+        // the heap starts past both sections so stray writes to text still fault
+        let end = |section: Option<(u32, u32)>| section.map_or(0, |(base, size)| base + size);
+        let heap_base = (end(text).max(end(data)) + 0xF) & !0xF;
+        regions.push(Region {
+            name: "heap",
+            kind: RegionKind::Heap,
+            base: heap_base,
+            size: loader.heap_size,
+            permissions: Permissions::RW,
+        });
+        regions.push(Region {
+            name: "stack",
+            kind: RegionKind::Stack,
+            base: loader.stack_top.wrapping_sub(loader.stack_size),
+            size: loader.stack_size,
+            permissions: Permissions::RW,
+        });
+
+        regions.push(device(&self.clint));
+        regions.push(device(&self.uart));
+        regions.push(device(&self.gpio));
+        regions.push(device(&self.framebuffer));
+
+        MemoryMap { regions }
+    }
+
+    /// Moves the UART registers, along with its region in the memory map.
+    /// The registers are words and can't share addresses with the other
+    /// devices, or with the program's regions when there is a map.
+    pub fn set_uart_base(&mut self, base: u32) -> Result<(), String> {
+        if base & 0b11 != 0 {
+            return Err(format!("The UART base {base:#010x} isn't word aligned"));
+        }
+        let moved = Region { base, ..device(&self.uart) };
+        if base as u64 + moved.size as u64 > 1 << 32 {
+            return Err(format!("The UART at {base:#010x} doesn't fit in the address space"));
+        }
+        let devices = [device(&self.clint), device(&self.gpio), device(&self.framebuffer)];
+        let overlapping = devices
+            .iter()
+            .chain(&self.memory_map.regions)
+            .find(|region| region.name != Uart::NAME && region.overlaps(&moved));
+        if let Some(region) = overlapping {
+            return Err(format!("The UART at {base:#010x} overlaps {}", region.name));
+        }
+
+        self.uart.base = base;
+        let uart = self.memory_map.regions.iter_mut().find(|r| r.name == Uart::NAME);
+        if let Some(region) = uart {
+            region.base = self.uart.base;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use super::EmulatorState;
+
+/// A device whose registers are mapped into the data address space.
+/// Registers are 32 bits wide and accessed at word aligned addresses.
+pub trait MemoryMapped {
+    /// Name shown for the device's region in the memory map
+    const NAME: &'static str;
+
+    fn base(&self) -> u32;
+    fn size(&self) -> u32;
+
+    fn contains(&self, address: u32) -> bool {
+        address.wrapping_sub(self.base()) < self.size()
+    }
+
+    /// Reads a register, which may have side effects such as popping a FIFO
+    fn read(&mut self, address: u32) -> u32;
+    /// Writes the bits of `value` selected by `mask` into a register
+    fn write(&mut self, address: u32, value: u32, mask: u32);
+}
+
+/// Accesses the bytes of a device like `rw_memory` accesses memory, each
+/// register touched by the enabled bytes is read or written exactly once
+pub fn rw_mmio(
+    device: &mut impl MemoryMapped,
+    address: u32,
+    byte_enable: [bool; 4],
+    wenable: bool,
+    wdata: u32,
+) -> u32 {
+    let mut rdata_bytes: [u8; 4] = [0; 4];
+    let wdata_bytes = wdata.to_le_bytes();
+
+    // group the enabled bytes by the register holding them: (register, value, mask)
+    let mut registers: Vec<(u32, u32, u32)> = Vec::new();
+    for i in (0..4).filter(|&i| byte_enable[i]) {
+        let addr = address.wrapping_add(i as u32);
+        let register = addr & !0b11;
+        let shift = (addr & 0b11) * 8;
+
+        if registers.last().map(|&(r, _, _)| r) != Some(register) {
+            let value = if wenable { 0 } else { device.read(register) };
+            registers.push((register, value, 0));
+        }
+
+        let (_, value, mask) = registers.last_mut().unwrap();
+        if wenable {
+            *value |= (wdata_bytes[i] as u32) << shift;
+            *mask |= 0xFF << shift;
+        } else {
+            rdata_bytes[i] = (*value >> shift) as u8;
+        }
+    }
+
+    if wenable {
+        for (register, value, mask) in registers {
+            device.write(register, value, mask);
+        }
+    }
+    u32::from_le_bytes(rdata_bytes)
+}
+
+impl EmulatorState {
+    /// Accesses the device mapped at `address`, `None` if no device claims it
+    pub(super) fn rw_devices(
+        &mut self,
+        address: u32,
+        byte_enable: [bool; 4],
+        wenable: bool,
+        wdata: u32,
+    ) -> Option<u32> {
+        if self.clint.contains(address) {
+            Some(rw_mmio(&mut self.clint, address, byte_enable, wenable, wdata))
+        } else if self.uart.contains(address) {
+            Some(rw_mmio(&mut self.uart, address, byte_enable, wenable, wdata))
+        } else if self.gpio.contains(address) {
+            Some(rw_mmio(&mut self.gpio, address, byte_enable, wenable, wdata))
+        } else if self.framebuffer.contains(address) {
+            Some(rw_mmio(&mut self.framebuffer, address, byte_enable, wenable, wdata))
+        } else {
+            None
+        }
+    }
+}
@@ -1,22 +1,65 @@
+mod bus;
+mod cache;
+mod call_stack;
+mod calling_convention;
+mod clint;
+mod config;
+mod csr;
 mod datapath;
+mod framebuffer;
+mod gpio;
 mod handlers;
+mod history;
+mod loader;
+mod memory_map;
+mod mmio;
+mod prediction;
+mod snapshot;
+mod statistics;
+mod timeline;
+mod timing;
+mod trap;
+mod uart;
+mod waveform;
 
+#[cfg(test)]
+mod riscv_tests;
 #[cfg(test)]
 mod tests;
 
 use crate::assembler::AssembledProgram;
-use crate::isa::Instruction;
+use crate::isa::{Instruction, InstructionDefinition, CSR};
+use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
     ops::{Index, IndexMut},
 };
 
+pub use cache::{Cache, CacheConfig, Replacement, WritePolicy};
+pub use call_stack::CallStack;
+use calling_convention::CallingConventionChecker;
+pub use calling_convention::ABI_NAMES;
+pub use clint::Clint;
+pub use config::{EmulatorConfig, LoaderConfig, MisalignedAccess, TimingModel};
 use datapath::CVE2Pipeline;
+pub use framebuffer::{Framebuffer, FRAMEBUFFER_HEIGHT, FRAMEBUFFER_WIDTH};
+pub use gpio::{Gpio, BUTTON_COUNT, DIGIT_COUNT, LED_COUNT, SWITCH_COUNT};
 use handlers::get_handler;
+pub use history::{Edit, EditTarget};
+use memory_map::Access;
+pub use memory_map::{MemoryMap, RegionKind};
+pub use prediction::{BranchPredictor, PredictorConfig, PredictorKind};
+pub use snapshot::Snapshot;
+pub use statistics::{InstructionClass, Statistics};
+pub use timeline::{PipelineTimeline, StageActivity, TIMELINE_LENGTH};
+use timeline::CycleRecord;
+pub use trap::Exception;
+pub use uart::Uart;
+pub use waveform::{Waveform, SIGNALS, WAVEFORM_LENGTH};
 
 pub type InstructionHandler = fn(&Instruction, &mut EmulatorState);
 
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
 pub struct RegisterFile {
     pub x: [u32; 32],
 }
@@ -40,11 +83,37 @@ impl IndexMut<usize> for RegisterFile {
     }
 }
 
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
 pub struct EmulatorState {
     pub x: RegisterFile,
     pub csr: BTreeMap<u32, u32>,
     pub pipeline: CVE2Pipeline,
+    /// Exception raised by the instruction in ID during the current clock
+    pub exception: Option<Exception>,
+    pub statistics: Statistics,
+    pub clint: Clint,
+    pub uart: Uart,
+    pub gpio: Gpio,
+    pub framebuffer: Framebuffer,
+    pub memory_map: MemoryMap,
+    /// Set while handling a non-maskable interrupt, cleared by MRET
+    pub nmi_mode: bool,
+    pub config: EmulatorConfig,
+    pub call_stack: CallStack,
+    pub checker: CallingConventionChecker,
+    /// Values changed by hand in the debugger, oldest first
+    pub edits: Vec<Edit>,
+    /// Registers before the last clock, to tell which ones it changed
+    pub previous_x: [u32; 32],
+    /// Pipeline occupancy over the most recent cycles
+    pub timeline: PipelineTimeline,
+    /// Datapath ports over the most recent cycles
+    pub waveform: Waveform,
+    /// Caches as configured in `config`, rebuilt empty when it changes
+    pub icache: Option<Cache>,
+    pub dcache: Option<Cache>,
+    /// Branch predictor as configured in `config`, rebuilt untrained when it changes
+    pub predictor: Option<BranchPredictor>,
 }
 
 fn rw_memory(memory: &mut BTreeMap<u32, u8>, address: u32, byte_enable: [bool; 4], wenable: bool, wdata: u32) -> Result<u32, ()> {
@@ -52,7 +121,7 @@ fn rw_memory(memory: &mut BTreeMap<u32, u8>, address: u32, byte_enable: [bool; 4
     let wdata_bytes = wdata.to_le_bytes();
     let success = (0usize..4usize).all(|i| {
         if byte_enable[i] {
-            let addr = address + i as u32;
+            let addr = address.wrapping_add(i as u32);
             rdata_bytes[i] = if wenable {
                 memory.insert(addr, wdata_bytes[i]).unwrap_or_default()
             } else {
@@ -72,71 +141,234 @@ fn rw_memory(memory: &mut BTreeMap<u32, u8>, address: u32, byte_enable: [bool; 4
 }
 
 pub fn clock(org_state: &EmulatorState, program: &mut AssembledProgram) -> EmulatorState {
+    let mut next_state = step(org_state, program);
+    if !org_state.halted() {
+        let record = CycleRecord::new(org_state, &next_state);
+        next_state.timeline.push(record);
+        next_state.waveform.push(org_state.statistics.cycles, next_state.pipeline.datapath);
+    }
+    next_state
+}
+
+fn step(org_state: &EmulatorState, program: &mut AssembledProgram) -> EmulatorState {
     let mut next_state = org_state.clone();
+    next_state.previous_x = org_state.x.x;
 
-    // Load the fetched instruction into the instr_rdata lines
-    if next_state.pipeline.datapath.instr_req_o {
-        // Read the next instruction into the instruction fetch register
-        match rw_memory(
-                &mut program.instruction_memory,
-                next_state.pipeline.datapath.instr_addr_o,
-                [true; 4],
-                false,
-                0,
-            ) {
-            Ok(instr) => {
-                next_state.pipeline.datapath.instr_rdata_i = instr;
-                next_state.pipeline.datapath.instr_gnt_i = true;
-                next_state.pipeline.datapath.instr_rvalid_i = true;
-                next_state.pipeline.datapath.instr_err_i = false;
-
-                next_state.pipeline.IF = next_state.pipeline.datapath.instr_rdata_i;
-                next_state.pipeline.IF_pc = next_state.pipeline.datapath.instr_addr_o;
-            }
-            Err(_) => {
-                next_state.pipeline.datapath.instr_gnt_i = true;
-                next_state.pipeline.datapath.instr_rvalid_i = false;
-                next_state.pipeline.datapath.instr_err_i = true;
+    // A program that returned from its entry point stays put
+    if next_state.halted() {
+        return next_state;
+    }
+    next_state.sync_caches();
+    next_state.sync_predictor();
+
+    // Load the fetched instruction into the instr_rdata lines once the
+    // memory responds, an instruction already in IF isn't fetched again
+    next_state.pipeline.flushed = false;
+    next_state.pipeline.datapath.instr_gnt_i = false;
+    next_state.pipeline.datapath.instr_rvalid_i = false;
+    let address = next_state.pipeline.datapath.instr_addr_o;
+    let holding = next_state.pipeline.IF_valid && next_state.pipeline.IF_pc == address;
+    if next_state.pipeline.datapath.instr_req_o && !holding {
+        let region = next_state.memory_map.check(address, [true; 4], Access::Execute);
+        let kind = region.ok().flatten().map(|region| region.kind);
+        let config = &next_state.config;
+        let icache = &mut next_state.icache;
+        let timing = || cache::timing(icache, config.bus.timing(kind), kind, address, false);
+        let (granted, responded) = next_state.pipeline.instr_bus.step(address, timing, config.bus.instr_stall_percent);
+        next_state.pipeline.datapath.instr_gnt_i = granted;
+
+        if responded {
+            let fetched = region.and_then(|_| rw_memory(&mut program.instruction_memory, address, [true; 4], false, 0));
+            match fetched {
+                Ok(instr) => {
+                    next_state.pipeline.datapath.instr_rdata_i = instr;
+                    next_state.pipeline.datapath.instr_rvalid_i = true;
+                    next_state.pipeline.datapath.instr_err_i = false;
+
+                    next_state.pipeline.IF = next_state.pipeline.datapath.instr_rdata_i;
+                    next_state.pipeline.IF_pc = address;
+                    next_state.pipeline.IF_err = false;
+                }
+                Err(_) => {
+                    next_state.pipeline.datapath.instr_err_i = true;
+
+                    // the fault is raised if the instruction reaches ID
+                    next_state.pipeline.IF = 0;
+                    next_state.pipeline.IF_pc = address;
+                    next_state.pipeline.IF_err = true;
+                }
             }
+            next_state.pipeline.IF_valid = true;
         }
     }
+    next_state.pipeline.fetched = next_state.pipeline.IF_valid;
+
+    // The CLINT drives the software and timer interrupt lines
+    next_state.clint.tick();
+    next_state.pipeline.datapath.irq_software_i = next_state.clint.msip;
+    next_state.pipeline.datapath.irq_timer_i = next_state.clint.timer_pending();
 
     // Decode the instruction in the instruction decode register
     let instr = Instruction::from_raw(next_state.pipeline.ID);
+    let counters = (
+        next_state.read_counter(CSR::MCYCLE, CSR::MCYCLEH),
+        next_state.read_counter(CSR::MINSTRET, CSR::MINSTRETH),
+    );
+
+    // A pending interrupt replaces the instruction in ID
+    let interrupt = trap::pending_interrupt(&next_state);
+    if next_state.pipeline.ID_err && interrupt.is_none() {
+        next_state.exception = Some(Exception::InstructionAccessFault(next_state.pipeline.ID_pc));
+    } else if next_state.pipeline.stall_cycles > 0 {
+        // the handler is done, the timing model holds the instruction in ID
+        next_state.pipeline.stall_cycles -= 1;
+        if next_state.pipeline.stall_cycles == 0 {
+            next_state.pipeline.datapath.id_multicycle = 0;
+            next_state.pipeline.datapath.fetch_enable_i = true;
+        }
+    } else if interrupt.is_none() {
+        // check the registers an instruction reads once, when it enters ID
+        if next_state.config.check_calling_convention && org_state.pipeline.datapath.id_multicycle == 0 {
+            next_state.checker.check_instruction(next_state.pipeline.ID_pc, instr);
+        }
+        match get_handler(instr) {
+            // an empty decode stage (after reset or a trap) holds all zeros
+            Err(()) if instr.raw() == 0 => (),
+            Err(()) => next_state.exception = Some(Exception::IllegalInstruction(instr.raw())),
+            Ok(handler) => handler(&instr, &mut next_state),
+        };
 
-    match get_handler(instr) {
-        Err(()) => println!("Invalid Instruction {}", instr.raw()),
-        Ok(handler) => handler(&instr, &mut next_state),
-    };
-
-    // Perform any requested memory read/write
-    if next_state.pipeline.datapath.data_req_o {
-        match rw_memory(
-            &mut program.data_memory,
-            next_state.pipeline.datapath.data_addr_o,
-            next_state.pipeline.datapath.data_be_o,
-            next_state.pipeline.datapath.data_we_o,
-            next_state.pipeline.datapath.data_wdata_o,
-        ) {
-            Ok(rdata) => {
-                next_state.pipeline.datapath.data_rdata_i = rdata;
-                next_state.pipeline.datapath.data_gnt_i = true;
-                next_state.pipeline.datapath.data_rvalid_i = true;
-                next_state.pipeline.datapath.data_err_i = false;
+        // a branch or jump resolves in its first cycle, the predictor may
+        // decide how long it takes
+        let predicted = org_state.pipeline.datapath.id_multicycle == 0
+            && next_state.exception.is_none()
+            && next_state.resolve_prediction(instr);
+
+        // stall for the cycles CVE2 takes beyond what the handler needed
+        if !predicted
+            && next_state.config.timing == TimingModel::CycleAccurate
+            && next_state.exception.is_none()
+            && next_state.pipeline.datapath.fetch_enable_i
+        {
+            let extra = next_state.extra_cycles(instr, org_state.pipeline.datapath.id_multicycle);
+            if extra > 0 {
+                next_state.pipeline.stall_cycles = extra;
+                next_state.pipeline.datapath.id_multicycle = 1;
+                next_state.pipeline.datapath.fetch_enable_i = false;
             }
-            Err(_) => {
-                next_state.pipeline.datapath.data_gnt_i = true;
-                next_state.pipeline.datapath.data_rvalid_i = false;
-                next_state.pipeline.datapath.data_err_i = true;
+        }
+    }
+
+    // The instruction retires once it no longer stalls the fetch
+    let retired = interrupt.is_none()
+        && next_state.exception.is_none()
+        && instr.raw() != 0
+        && next_state.pipeline.datapath.fetch_enable_i;
+    next_state.update_counters(counters, retired);
+    next_state.statistics.cycles += 1;
+    if retired {
+        if let Some(def) = InstructionDefinition::from_instr(instr) {
+            // a branch only spends a second cycle in ID when it is taken,
+            // unless the predictor decided its timing
+            let resolved = next_state.predictor.as_mut().and_then(|predictor| predictor.resolved.take());
+            let taken = resolved.unwrap_or(org_state.pipeline.datapath.id_multicycle != 0);
+            next_state.statistics.retire(&def, taken);
+        }
+    }
+
+    // Enter the trap handler instead of continuing with the interrupted or faulting instruction
+    if let Some(interrupt) = interrupt {
+        trap::take_interrupt(&mut next_state, interrupt);
+        return next_state;
+    }
+    if let Some(exception) = next_state.exception.take() {
+        trap::take_exception(&mut next_state, exception);
+        return next_state;
+    }
+
+    // Perform the requested memory read/write once the memory responds, the
+    // core may take back the request after it is granted
+    next_state.pipeline.datapath.data_gnt_i = false;
+    next_state.pipeline.datapath.data_rvalid_i = false;
+    if next_state.pipeline.datapath.data_req_o || next_state.pipeline.data_bus.transaction.is_some() {
+        let datapath = next_state.pipeline.datapath;
+        let access = if datapath.data_we_o { Access::Write } else { Access::Read };
+        let region = next_state
+            .memory_map
+            .check(datapath.data_addr_o, datapath.data_be_o, access)
+            .map(|region| region.map(|region| region.kind));
+        let kind = region.ok().flatten();
+        let config = &next_state.config;
+        let dcache = &mut next_state.dcache;
+        let timing = || cache::timing(dcache, config.bus.timing(kind), kind, datapath.data_addr_o, datapath.data_we_o);
+        let (granted, responded) = next_state.pipeline.data_bus.step(datapath.data_addr_o, timing, config.bus.data_stall_percent);
+        next_state.pipeline.datapath.data_gnt_i = granted;
+
+        if responded {
+            // Memory mapped devices take precedence over memory, loads from
+            // the text region read the instructions
+            let result = region.and_then(|kind| {
+                match next_state.rw_devices(
+                    datapath.data_addr_o,
+                    datapath.data_be_o,
+                    datapath.data_we_o,
+                    datapath.data_wdata_o,
+                ) {
+                    Some(rdata) => Ok(rdata),
+                    None => rw_memory(
+                        if kind == Some(RegionKind::Text) {
+                            &mut program.instruction_memory
+                        } else {
+                            &mut program.data_memory
+                        },
+                        datapath.data_addr_o,
+                        datapath.data_be_o,
+                        datapath.data_we_o,
+                        datapath.data_wdata_o,
+                    ),
+                }
+            });
+            match result {
+                Ok(rdata) => {
+                    next_state.pipeline.datapath.data_rdata_i = rdata;
+                    next_state.pipeline.datapath.data_rvalid_i = true;
+                    next_state.pipeline.datapath.data_err_i = false;
+                }
+                Err(_) => {
+                    next_state.pipeline.datapath.data_err_i = true;
+
+                    // the load or store is still in ID, it faults instead of
+                    // completing; report the first byte it accessed
+                    let lane = datapath.data_be_o.iter().position(|&enabled| enabled).unwrap_or(0);
+                    let address = datapath.data_addr_o.wrapping_add(lane as u32);
+                    let exception = match access {
+                        Access::Write => Exception::StoreAccessFault(address),
+                        _ => Exception::LoadAccessFault(address),
+                    };
+                    trap::take_exception(&mut next_state, exception);
+                    return next_state;
+                }
             }
         }
     }
 
     // Only load the next instruction if the fetch is enabled
     if next_state.pipeline.datapath.fetch_enable_i {
-        next_state.pipeline.ID = next_state.pipeline.IF;
-        next_state.pipeline.ID_pc = next_state.pipeline.IF_pc;
-        next_state.pipeline.datapath.instr_addr_o += 4;
+        // a jump or branch in this cycle leaves a stale instruction in IF
+        let expected = next_state.pipeline.IF_pc == next_state.pipeline.datapath.instr_addr_o;
+        if next_state.pipeline.IF_valid && expected {
+            next_state.pipeline.ID = next_state.pipeline.IF;
+            next_state.pipeline.ID_pc = next_state.pipeline.IF_pc;
+            next_state.pipeline.ID_err = next_state.pipeline.IF_err;
+            next_state.pipeline.IF_valid = false;
+            let (pc, instr) = (next_state.pipeline.IF_pc, Instruction::from_raw(next_state.pipeline.IF));
+            next_state.pipeline.datapath.instr_addr_o = next_state.predict_next(pc, instr).unwrap_or(pc.wrapping_add(4));
+        } else {
+            // the fetch is still waiting for the memory or IF is refetched, ID
+            // gets a bubble
+            next_state.pipeline.ID = 0;
+            next_state.pipeline.ID_err = false;
+        }
     }
     return next_state;
 }
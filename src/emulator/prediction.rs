@@ -0,0 +1,260 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::call_stack::is_link;
+use super::{EmulatorState, InstructionClass};
+use crate::isa::{Instruction, InstructionDefinition};
+
+const JAL_OPCODE: u8 = 0b1101111;
+
+/// How the next fetch address is guessed for a branch or jump
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PredictorKind {
+    /// Every branch and jump falls through
+    #[default]
+    NotTaken,
+    /// Backward branches (loops) are taken, forward ones aren't
+    Btfn,
+    /// A table of the last outcome of each branch
+    OneBit,
+    /// A table of saturating counters, a branch has to mispredict twice to flip
+    TwoBit,
+    /// A branch target buffer, branches and jumps it holds are taken
+    Btb,
+}
+
+impl PredictorKind {
+    pub const ALL: [PredictorKind; 5] = [
+        PredictorKind::NotTaken,
+        PredictorKind::Btfn,
+        PredictorKind::OneBit,
+        PredictorKind::TwoBit,
+        PredictorKind::Btb,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PredictorKind::NotTaken => "Static not-taken",
+            PredictorKind::Btfn => "BTFN",
+            PredictorKind::OneBit => "1-bit BHT",
+            PredictorKind::TwoBit => "2-bit BHT",
+            PredictorKind::Btb => "BTB",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredictorConfig {
+    pub kind: PredictorKind,
+    /// Entries of the history table or BTB, indexed by the word address
+    pub table_size: u32,
+    /// Entries of the return-address stack, none if 0
+    pub return_stack_depth: u32,
+    /// Fetch from the predicted address and pay `mispredict_penalty` on a
+    /// mispredict, instead of the redirect every taken branch costs
+    pub apply_penalty: bool,
+    pub mispredict_penalty: u32,
+}
+
+impl Default for PredictorConfig {
+    fn default() -> Self {
+        Self {
+            kind: PredictorKind::default(),
+            table_size: 16,
+            return_stack_depth: 0,
+            apply_penalty: false,
+            mispredict_penalty: 2,
+        }
+    }
+}
+
+/// Predictions and how many of them were right, by kind of instruction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredictorStatistics {
+    pub branches: u64,
+    pub branches_correct: u64,
+    pub jumps: u64,
+    pub jumps_correct: u64,
+    /// Returns are also counted as jumps
+    pub returns: u64,
+    pub returns_correct: u64,
+}
+
+impl PredictorStatistics {
+    pub fn accuracy(&self) -> Option<f64> {
+        let predictions = self.branches + self.jumps;
+        (predictions != 0).then(|| (self.branches_correct + self.jumps_correct) as f64 / predictions as f64)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BtbEntry {
+    pub pc: u32,
+    pub target: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BranchPredictor {
+    pub config: PredictorConfig,
+    /// Last outcome (1-bit) or saturating counter (2-bit), taken from 2 up
+    pub history: Vec<u8>,
+    pub btb: Vec<Option<BtbEntry>>,
+    /// Return addresses of the calls in progress, the latest last
+    pub return_stack: VecDeque<u32>,
+    pub statistics: PredictorStatistics,
+    /// Predicted address after the branch or jump in ID
+    pub predicted: Option<u32>,
+    /// Outcome of the branch in ID once resolved, until it retires
+    pub resolved: Option<bool>,
+}
+
+impl BranchPredictor {
+    pub fn new(config: PredictorConfig) -> BranchPredictor {
+        let entries = config.table_size.max(1) as usize;
+        BranchPredictor {
+            config,
+            // not taken, weakly for the 2-bit counters
+            history: vec![(config.kind == PredictorKind::TwoBit) as u8; entries],
+            btb: vec![None; entries],
+            return_stack: VecDeque::new(),
+            statistics: PredictorStatistics::default(),
+            predicted: None,
+            resolved: None,
+        }
+    }
+
+    fn index(&self, pc: u32) -> usize {
+        (pc >> 2) as usize % self.history.len()
+    }
+
+    fn btb_target(&self, pc: u32) -> Option<u32> {
+        self.btb[self.index(pc)]
+            .filter(|entry| entry.pc == pc)
+            .map(|entry| entry.target)
+    }
+
+    /// Address to fetch after the instruction at `pc`, `None` if it isn't a
+    /// branch or jump
+    pub fn predict(&self, pc: u32, instr: Instruction) -> Option<u32> {
+        let def = InstructionDefinition::from_instr(instr)?;
+        let fall_through = pc.wrapping_add(4);
+        let direct = || pc.wrapping_add_signed(instr.immediate().unwrap_or(4));
+        match InstructionClass::from_definition(&def) {
+            InstructionClass::Branch => {
+                let taken = match self.config.kind {
+                    PredictorKind::NotTaken => false,
+                    PredictorKind::Btfn => instr.immediate().is_ok_and(|offset| offset < 0),
+                    PredictorKind::OneBit => self.history[self.index(pc)] != 0,
+                    PredictorKind::TwoBit => self.history[self.index(pc)] >= 2,
+                    PredictorKind::Btb => return Some(self.btb_target(pc).unwrap_or(fall_through)),
+                };
+                Some(if taken { direct() } else { fall_through })
+            }
+            InstructionClass::Jump => {
+                let rd = instr.rd() as usize;
+                let rs1 = instr.rs1() as usize;
+                let is_return = def.opcode != JAL_OPCODE && is_link(rs1) && rs1 != rd;
+                if is_return && self.config.return_stack_depth != 0 {
+                    if let Some(&address) = self.return_stack.back() {
+                        return Some(address);
+                    }
+                }
+                Some(match self.config.kind {
+                    PredictorKind::NotTaken => fall_through,
+                    PredictorKind::Btb => self.btb_target(pc).unwrap_or(fall_through),
+                    // the target of JAL is known once it is decoded
+                    _ if def.opcode == JAL_OPCODE => direct(),
+                    _ => fall_through,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Trains the predictor with the outcome of the branch or jump at `pc`,
+    /// returning whether the prediction was right
+    pub fn resolve(&mut self, pc: u32, instr: Instruction, taken: bool, next: u32) -> bool {
+        let correct = self.predicted.take() == Some(next);
+        self.resolved = Some(taken);
+        let Some(def) = InstructionDefinition::from_instr(instr) else {
+            return correct;
+        };
+        let index = self.index(pc);
+
+        if InstructionClass::from_definition(&def) == InstructionClass::Branch {
+            self.statistics.branches += 1;
+            self.statistics.branches_correct += correct as u64;
+            self.history[index] = match self.config.kind {
+                PredictorKind::OneBit => taken as u8,
+                _ if taken => (self.history[index] + 1).min(3),
+                _ => self.history[index].saturating_sub(1),
+            };
+        } else {
+            let rd = instr.rd() as usize;
+            let rs1 = instr.rs1() as usize;
+            self.statistics.jumps += 1;
+            self.statistics.jumps_correct += correct as u64;
+            if def.opcode != JAL_OPCODE && is_link(rs1) && rs1 != rd {
+                self.statistics.returns += 1;
+                self.statistics.returns_correct += correct as u64;
+                self.return_stack.pop_back();
+            }
+            if is_link(rd) && self.config.return_stack_depth != 0 {
+                if self.return_stack.len() == self.config.return_stack_depth as usize {
+                    self.return_stack.pop_front();
+                }
+                self.return_stack.push_back(pc.wrapping_add(4));
+            }
+        }
+
+        self.btb[index] = taken.then_some(BtbEntry { pc, target: next });
+        correct
+    }
+}
+
+impl EmulatorState {
+    /// Replaces the branch predictor with an untrained one when its
+    /// configuration changed
+    pub fn sync_predictor(&mut self) {
+        if self.predictor.as_ref().map(|predictor| predictor.config) != self.config.predictor {
+            self.predictor = self.config.predictor.map(BranchPredictor::new);
+        }
+    }
+
+    /// Predicts the instruction entering ID, returning the address to fetch
+    /// next if the prediction steers the fetch
+    pub(super) fn predict_next(&mut self, pc: u32, instr: Instruction) -> Option<u32> {
+        let predictor = self.predictor.as_mut()?;
+        predictor.predicted = predictor.predict(pc, instr);
+        predictor.predicted.filter(|_| predictor.config.apply_penalty)
+    }
+
+    /// Resolves the branch or jump in ID after the first cycle of its handler,
+    /// which redirects the fetch if it is taken. Returns whether the
+    /// predictor decided the instruction's timing.
+    pub(super) fn resolve_prediction(&mut self, instr: Instruction) -> bool {
+        let Some(predictor) = self.predictor.as_mut() else {
+            return false;
+        };
+        if predictor.predicted.is_none() {
+            return false;
+        }
+        let pc = self.pipeline.ID_pc;
+        let taken = !self.pipeline.datapath.fetch_enable_i;
+        let next = if taken { self.pipeline.datapath.instr_addr_o } else { pc.wrapping_add(4) };
+        let correct = predictor.resolve(pc, instr, taken, next);
+        if !predictor.config.apply_penalty {
+            return false;
+        }
+
+        // the fetch already followed the prediction, a mispredict refetches
+        let penalty = if correct { 0 } else { predictor.config.mispredict_penalty };
+        let datapath = &mut self.pipeline.datapath;
+        datapath.instr_addr_o = next;
+        datapath.fetch_enable_i = penalty == 0;
+        datapath.id_multicycle = (penalty != 0) as u32;
+        self.pipeline.stall_cycles = penalty;
+        true
+    }
+}
@@ -0,0 +1,63 @@
+//! Runs the rv32ui conformance suite vendored in `test-files/riscv-tests`.
+//!
+//! Each test program is assembled and clocked headlessly until its final
+//! `ECALL` traps. The riscv-tests pass/fail signature is then read from `gp`
+//! (x3): 1 means every case passed, otherwise `gp >> 1` is the number of the
+//! first failing case.
+
+use super::*;
+use crate::assembler::{assemble, Section};
+
+/// Upper bound on clock cycles before a test is considered hung
+const MAX_CYCLES: usize = 100_000;
+
+#[derive(Debug, PartialEq)]
+enum TestResult {
+    Pass,
+    Fail { test_case: u32 },
+    Timeout,
+}
+
+fn run_test(source: &str) -> TestResult {
+    let mut program = assemble(source).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let mut state = EmulatorState::default();
+    state.pipeline.datapath.instr_addr_o = program.get_section_start(Section::Text);
+    state.memory_map = state.program_memory_map(&program);
+
+    for _ in 0..MAX_CYCLES {
+        state = clock(&state, &mut program);
+        if state.read_csr(CSR::MCAUSE) == Exception::EnvironmentCall.cause() {
+            let gp = state.x[3];
+            return if gp == 1 {
+                TestResult::Pass
+            } else {
+                TestResult::Fail { test_case: gp >> 1 }
+            };
+        }
+    }
+
+    TestResult::Timeout
+}
+
+macro_rules! riscv_tests {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                let source = include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/test-files/riscv-tests/rv32ui/",
+                    stringify!($name),
+                    ".s"
+                ));
+                assert_eq!(run_test(source), TestResult::Pass);
+            }
+        )*
+    };
+}
+
+riscv_tests!(
+    add, addi, and, andi, auipc, beq, bge, bgeu, blt, bltu, bne, jal, jalr, lb, lbu, lh, lhu, lui,
+    lw, or, ori, sb, sh, simple, sll, slli, slt, slti, sltiu, sltu, sra, srai, srl, srli, sub, sw,
+    xor, xori,
+);
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use super::framebuffer::{FRAMEBUFFER_HEIGHT, FRAMEBUFFER_WIDTH};
+use super::EmulatorState;
+use crate::assembler::AssembledProgram;
+
+/// Version written into snapshots, bumped whenever the saved state changes shape
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// A whole session: the source, the program with the memory as the run left
+/// it, and the core with its pipeline, CSRs, devices and configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub source: String,
+    /// `None` if the source wasn't assembled yet
+    pub program: Option<AssembledProgram>,
+    pub state: EmulatorState,
+}
+
+impl Snapshot {
+    pub fn new(source: &str, program: Option<&AssembledProgram>, state: &EmulatorState) -> Snapshot {
+        Snapshot {
+            version: SNAPSHOT_VERSION,
+            source: source.to_string(),
+            program: program.cloned(),
+            state: state.clone(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("snapshot maps are keyed by strings, numbers or unit variants")
+    }
+
+    pub fn from_json(json: &str) -> Result<Snapshot, String> {
+        // check the version first, a snapshot of another version may not parse
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+        let header: Header = serde_json::from_str(json).map_err(|e| format!("Not a snapshot: {e}"))?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(format!(
+                "Snapshot version {} isn't supported, expected version {SNAPSHOT_VERSION}",
+                header.version
+            ));
+        }
+        let snapshot: Snapshot = serde_json::from_str(json).map_err(|e| format!("Invalid snapshot: {e}"))?;
+        check_shapes(&snapshot.state).map_err(|e| format!("Invalid snapshot: {e}"))?;
+        Ok(snapshot)
+    }
+}
+
+/// Checks the sizes the emulator indexes by without bounds checks, which an
+/// edited snapshot can get wrong even though it parses
+fn check_shapes(state: &EmulatorState) -> Result<(), String> {
+    let pixels = FRAMEBUFFER_WIDTH * FRAMEBUFFER_HEIGHT;
+    if state.framebuffer.pixels.len() != pixels {
+        return Err(format!(
+            "the framebuffer has {} pixels instead of {pixels}",
+            state.framebuffer.pixels.len()
+        ));
+    }
+    for (name, cache) in [("instruction", &state.icache), ("data", &state.dcache)] {
+        let Some(cache) = cache else { continue };
+        let config = cache.config;
+        let shaped = config.is_valid()
+            && cache.sets.len() == config.sets() as usize
+            && cache.sets.iter().all(|set| set.len() == config.associativity as usize);
+        if !shaped {
+            return Err(format!("the {name} cache doesn't have the shape of its configuration"));
+        }
+    }
+    if let Some(predictor) = &state.predictor {
+        if predictor.history.is_empty() || predictor.btb.len() != predictor.history.len() {
+            return Err("the branch predictor tables are empty or of different sizes".to_string());
+        }
+    }
+    Ok(())
+}
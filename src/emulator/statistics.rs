@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+use crate::isa::{InstructionDefinition, InstructionFormat, ISA};
+
+/// Broad grouping of instructions for the instruction mix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InstructionClass {
+    Arithmetic,
+    Load,
+    Store,
+    Branch,
+    Jump,
+    System,
+}
+
+impl InstructionClass {
+    pub fn from_definition(def: &InstructionDefinition) -> InstructionClass {
+        match def.opcode {
+            0b0000011 => InstructionClass::Load,
+            0b0100011 => InstructionClass::Store,
+            0b1100011 => InstructionClass::Branch,
+            0b1101111 | 0b1100111 => InstructionClass::Jump,
+            0b1110011 | 0b0001111 => InstructionClass::System,
+            _ => InstructionClass::Arithmetic,
+        }
+    }
+}
+
+/// Execution statistics, independent of the software visible counters
+/// so that writes to `mcycle`/`minstret` or `mcountinhibit` don't skew them
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    pub cycles: u64,
+    pub retired: u64,
+    /// Retired instructions by mnemonic
+    #[serde(deserialize_with = "mnemonic_counts")]
+    pub instructions: BTreeMap<&'static str, u64>,
+    pub formats: BTreeMap<InstructionFormat, u64>,
+    pub classes: BTreeMap<InstructionClass, u64>,
+    pub branches_taken: u64,
+    pub branches_not_taken: u64,
+    pub loads: u64,
+    pub stores: u64,
+}
+
+/// The mnemonics are the names of the ISA's definitions, a snapshot's are
+/// looked up to get them back
+fn mnemonic_counts<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<&'static str, u64>, D::Error> {
+    BTreeMap::<String, u64>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, count)| match ISA::from_str(&name) {
+            Ok(isa) => Ok((isa.definition()._name, count)),
+            Err(_) => Err(D::Error::custom(format!("unknown instruction {name}"))),
+        })
+        .collect()
+}
+
+impl Statistics {
+    /// Cycles per retired instruction, `None` until something has retired
+    pub fn cpi(&self) -> Option<f64> {
+        (self.retired != 0).then(|| self.cycles as f64 / self.retired as f64)
+    }
+
+    /// Records a retired instruction, `taken` is only meaningful for branches
+    pub(super) fn retire(&mut self, def: &InstructionDefinition, taken: bool) {
+        let class = InstructionClass::from_definition(def);
+
+        self.retired += 1;
+        *self.instructions.entry(def._name).or_default() += 1;
+        *self.formats.entry(def.format).or_default() += 1;
+        *self.classes.entry(class).or_default() += 1;
+
+        match class {
+            InstructionClass::Branch if taken => self.branches_taken += 1,
+            InstructionClass::Branch => self.branches_not_taken += 1,
+            InstructionClass::Load => self.loads += 1,
+            InstructionClass::Store => self.stores += 1,
+            _ => (),
+        }
+    }
+}
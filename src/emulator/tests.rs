@@ -1,7 +1,8 @@
 #![allow(non_snake_case)]
 
-use crate::isa::{Operands, ISA};
+use crate::isa::{Operands, CSR, ISA};
 
+use super::config::RegionTiming;
 use super::*;
 
 impl ISA {
@@ -184,8 +185,7 @@ fn test_JAL_neg_offset() {
 }
 
 #[test]
-#[should_panic(expected = "JAL instruction immediate it not on a 4-byte boundary")]
-fn test_JAL_panic() {
+fn test_JAL_misaligned() {
     let mut emulator_state = EmulatorState::default();
 
     // JAL ( x1 := PC + 4, jump to PC + 0x122)
@@ -198,8 +198,11 @@ fn test_JAL_panic() {
     // Instruction fetch
     emulator_state = clock(&emulator_state, &mut program);
 
-    // Should panic because the immediate is not on a 4-byte boundary
-    clock(&emulator_state, &mut program);
+    // Traps because the target is not on a 4-byte boundary
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 0);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 0x122);
+    assert_eq!(emulator_state.x[1], 0);
 }
 
 #[test]
@@ -1004,10 +1007,13 @@ fn test_SW() {
     emulator_state = clock(&emulator_state, &mut program);
 
     // SW (x1 := 10) -> Write x1 to address 100 + x2
-    clock(&emulator_state, &mut program);
+    // the access crosses a word boundary, so it is split into two transactions
+    emulator_state = clock(&emulator_state, &mut program);
     assert_eq!(program.data_memory.get(&105), Some(&0xFB)); // x1 = 0xFEFDFCFB (100 + x2)
     assert_eq!(program.data_memory.get(&106), Some(&0xFC));
     assert_eq!(program.data_memory.get(&107), Some(&0xFD));
+    assert_eq!(program.data_memory.get(&108), None);
+    clock(&emulator_state, &mut program);
     assert_eq!(program.data_memory.get(&108), Some(&0xFE));
 }
 
@@ -1956,8 +1962,8 @@ fn test_AND() {
 fn test_CSRRW() {
     let mut emulator_state = EmulatorState::default();
 
-    let csr1 = 5;
-    let csr2 = 6;
+    let csr1 = CSR::MSCRATCH.definition().address as i32;
+    let csr2 = CSR::MTVAL.definition().address as i32;
 
     let mut program = populate(&[
             // set x1 := 42
@@ -2016,7 +2022,7 @@ fn test_CSRRW() {
 #[test]
 fn test_CSRRS() {
     let mut emulator_state = EmulatorState::default();
-    let csr1 = 5;
+    let csr1 = CSR::MSCRATCH.definition().address as i32;
 
     let mut program = populate(&[
             // set x1 := 42
@@ -2075,7 +2081,7 @@ fn test_CSRRS() {
 #[test]
 fn test_CSRRC() {
     let mut emulator_state = EmulatorState::default();
-    let csr1 = 5;
+    let csr1 = CSR::MSCRATCH.definition().address as i32;
 
     let mut program = populate(&[
             // set x1 := 42
@@ -2135,7 +2141,7 @@ fn test_CSRRC() {
 fn test_CSRRWI() {
     let mut emulator_state = EmulatorState::default();
 
-    let csr1 = 5;
+    let csr1 = CSR::MSCRATCH.definition().address as i32;
 
     let mut program = populate(&[
             // CSRRC x1, csr1, x1
@@ -2173,7 +2179,7 @@ fn test_CSRRWI() {
 fn test_CSRRSI() {
     let mut emulator_state = EmulatorState::default();
 
-    let csr1 = 5;
+    let csr1 = CSR::MSCRATCH.definition().address as i32;
 
     let mut program = populate(&[
             // CSRRSI x1, csr1, x1
@@ -2210,7 +2216,7 @@ fn test_CSRRSI() {
 #[test]
 fn test_CSRRCI() {
     let mut emulator_state = EmulatorState::default();
-    let csr1 = 5;
+    let csr1 = CSR::MSCRATCH.definition().address as i32;
 
     let mut program = populate(&[
             // CSRRCI x1, csr1, x1
@@ -2243,3 +2249,1645 @@ fn test_CSRRCI() {
     assert_eq!(emulator_state.csr[&(csr1 as u32)], 0);
     assert_eq!(emulator_state.x[1], 0);
 }
+
+// CSR addresses above 0x7FF have to be passed as a sign-extended immediate
+fn csr_imm(csr: CSR) -> i32 {
+    ((csr.definition().address << 20) as i32) >> 20
+}
+
+#[test]
+fn test_CSRRW_x0_dest() {
+    let mut emulator_state = EmulatorState::default();
+
+    let mut program = populate(&[
+        // set x1 := 42
+        ISA::ADDI.build(Operands {
+            rd: 1,
+            rs1: 0,
+            imm: 42,
+            ..Default::default()
+        }),
+        // CSRRW x0, mscratch, x1
+        ISA::CSRRW.build(Operands {
+            rd: 0,
+            rs1: 1,
+            imm: csr_imm(CSR::MSCRATCH),
+            ..Default::default()
+        }),
+    ]);
+
+    // Instruction fetch
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // Set x1 := 42
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // CSRRW with rd = x0 still writes the CSR
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.read_csr(CSR::MSCRATCH), 42);
+    assert_eq!(emulator_state.x[0], 0);
+}
+
+#[test]
+fn test_CSR_write_masks() {
+    let mut emulator_state = EmulatorState::default();
+
+    let mut program = populate(&[
+        // set x1 := 0xFFFFFFFF
+        ISA::ADDI.build(Operands {
+            rd: 1,
+            rs1: 0,
+            imm: -1,
+            ..Default::default()
+        }),
+        // CSRRW x0, mstatus, x1
+        ISA::CSRRW.build(Operands {
+            rd: 0,
+            rs1: 1,
+            imm: csr_imm(CSR::MSTATUS),
+            ..Default::default()
+        }),
+        // CSRRW x0, mtvec, x1
+        ISA::CSRRW.build(Operands {
+            rd: 0,
+            rs1: 1,
+            imm: csr_imm(CSR::MTVEC),
+            ..Default::default()
+        }),
+        // CSRRW x2, misa, x1
+        ISA::CSRRW.build(Operands {
+            rd: 2,
+            rs1: 1,
+            imm: csr_imm(CSR::MISA),
+            ..Default::default()
+        }),
+    ]);
+
+    // Instruction fetch
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // Set x1 := 0xFFFFFFFF
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // Only MIE and MPIE are writable, MPP stays machine mode
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.read_csr(CSR::MSTATUS), 0x1888);
+
+    // mtvec keeps its alignment and vectored mode
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.read_csr(CSR::MTVEC), 0xFFFFFF01);
+
+    // Writes to misa are ignored
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.x[2], 0x40000100);
+    assert_eq!(emulator_state.read_csr(CSR::MISA), 0x40000100);
+}
+
+#[test]
+fn test_CSR_read_only() {
+    let mut emulator_state = EmulatorState::default();
+
+    let mut program = populate(&[
+        // CSRRS x1, mhartid, x0 (read without writing is allowed)
+        ISA::CSRRS.build(Operands {
+            rd: 1,
+            rs1: 0,
+            imm: csr_imm(CSR::MHARTID),
+            ..Default::default()
+        }),
+        // CSRRW x1, mhartid, x0 (write is illegal)
+        ISA::CSRRW.build(Operands {
+            rd: 1,
+            rs1: 0,
+            imm: csr_imm(CSR::MHARTID),
+            ..Default::default()
+        }),
+    ]);
+    let illegal = ISA::CSRRW
+        .build(Operands {
+            rd: 1,
+            rs1: 0,
+            imm: csr_imm(CSR::MHARTID),
+            ..Default::default()
+        })
+        .raw();
+
+    // Instruction fetch
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // CSRRS x1, mhartid, x0
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.exception, None);
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 0);
+
+    // CSRRW x1, mhartid, x0 -> illegal instruction
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 2);
+    assert_eq!(emulator_state.read_csr(CSR::MEPC), 4);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), illegal);
+    assert_eq!(emulator_state.pipeline.datapath.instr_addr_o, 0);
+    assert_eq!(emulator_state.pipeline.ID, 0);
+}
+
+#[test]
+fn test_CSR_nonexistent() {
+    let mut emulator_state = EmulatorState::default();
+
+    let mut program = populate(&[
+        // set x1 := 0x100
+        ISA::ADDI.build(Operands {
+            rd: 1,
+            rs1: 0,
+            imm: 0x100,
+            ..Default::default()
+        }),
+        // CSRRW x0, mtvec, x1
+        ISA::CSRRW.build(Operands {
+            rd: 0,
+            rs1: 1,
+            imm: csr_imm(CSR::MTVEC),
+            ..Default::default()
+        }),
+        // CSRRS x2, 0x7C0, x0 (no such CSR)
+        ISA::CSRRS.build(Operands {
+            rd: 2,
+            rs1: 0,
+            imm: 0x7C0,
+            ..Default::default()
+        }),
+        // skipped by the trap
+        ISA::ADDI.build(Operands {
+            rd: 3,
+            rs1: 0,
+            imm: 1,
+            ..Default::default()
+        }),
+    ]);
+    write(
+        &mut program.instruction_memory,
+        0x100,
+        &ISA::ADDI
+            .build(Operands {
+                rd: 4,
+                rs1: 0,
+                imm: 7,
+                ..Default::default()
+            })
+            .raw()
+            .to_le_bytes(),
+    );
+
+    // Instruction fetch
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // Set x1 := 0x100
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // CSRRW x0, mtvec, x1
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // CSRRS x2, 0x7C0, x0 -> illegal instruction
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 2);
+    assert_eq!(emulator_state.read_csr(CSR::MEPC), 8);
+    assert_eq!(emulator_state.pipeline.datapath.instr_addr_o, 0x100);
+
+    // Fetch the handler, then run it
+    emulator_state = clock(&emulator_state, &mut program);
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.x[2], 0);
+    assert_eq!(emulator_state.x[3], 0);
+    assert_eq!(emulator_state.x[4], 7);
+}
+
+#[test]
+fn test_MRET() {
+    let mut emulator_state = EmulatorState::default();
+
+    let mut program = populate(&[
+        // set x1 := 0xC
+        ISA::ADDI.build(Operands {
+            rd: 1,
+            rs1: 0,
+            imm: 0xC,
+            ..Default::default()
+        }),
+        // CSRRW x0, mepc, x1
+        ISA::CSRRW.build(Operands {
+            rd: 0,
+            rs1: 1,
+            imm: csr_imm(CSR::MEPC),
+            ..Default::default()
+        }),
+        ISA::MRET.build(Operands {
+            imm: 0x302,
+            ..Default::default()
+        }),
+        // ADDI x5, x0, 1
+        ISA::ADDI.build(Operands {
+            rd: 5,
+            rs1: 0,
+            imm: 1,
+            ..Default::default()
+        }),
+    ]);
+    emulator_state.write_csr(CSR::MSTATUS, 1 << 7);
+
+    // Instruction fetch
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // Set x1 := 0xC
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // CSRRW x0, mepc, x1
+    emulator_state = clock(&emulator_state, &mut program);
+
+    // MRET jumps to mepc and restores MIE from MPIE
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.pipeline.datapath.instr_addr_o, 0xC);
+    assert_eq!(emulator_state.read_csr(CSR::MSTATUS) & 0x88, 0x88);
+
+    emulator_state = clock(&emulator_state, &mut program);
+    emulator_state = clock(&emulator_state, &mut program);
+    assert_eq!(emulator_state.x[5], 1);
+}
+
+// clocks an assembled program until the given number of instructions retired
+fn run_until_retired(source: &str, retired: u64) -> EmulatorState {
+    let mut program =
+        crate::assembler::assemble(source).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let mut emulator_state = EmulatorState::default();
+    for _ in 0..1000 {
+        if emulator_state.statistics.retired == retired {
+            return emulator_state;
+        }
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    panic!("only {} instructions retired", emulator_state.statistics.retired);
+}
+
+#[test]
+fn test_performance_counters() {
+    let emulator_state = run_until_retired(
+        ".text
+        addi x1, x0, 2
+        loop: addi x1, x1, -1
+        bne x1, x0, loop
+        lw x2, 0(x0)
+        sw x2, 4(x0)
+        csrr x3, mcycle
+        rdinstret x4",
+        9,
+    );
+
+    // 1 fetch, 5 single cycle, taken branch, load and store take 2
+    assert_eq!(emulator_state.x[3], 11);
+    assert_eq!(emulator_state.x[4], 8);
+    assert_eq!(emulator_state.read_counter(CSR::MCYCLE, CSR::MCYCLEH), 13);
+    assert_eq!(emulator_state.read_counter(CSR::MINSTRET, CSR::MINSTRETH), 9);
+    assert_eq!(emulator_state.read_csr(CSR::CYCLE), 13);
+    assert_eq!(emulator_state.read_csr(CSR::INSTRET), 9);
+
+    let statistics = &emulator_state.statistics;
+    assert_eq!(statistics.cycles, 13);
+    assert_eq!(statistics.cpi(), Some(13.0 / 9.0));
+    assert_eq!(statistics.branches_taken, 1);
+    assert_eq!(statistics.branches_not_taken, 1);
+    assert_eq!(statistics.loads, 1);
+    assert_eq!(statistics.stores, 1);
+    assert_eq!(statistics.instructions["ADDI"], 3);
+    assert_eq!(statistics.formats[&crate::isa::InstructionFormat::I], 6);
+    assert_eq!(statistics.classes[&InstructionClass::System], 2);
+}
+
+#[test]
+fn test_performance_counters_inhibit_and_write() {
+    let emulator_state = run_until_retired(
+        ".text
+        csrwi mcountinhibit, 5
+        addi x1, x0, 1
+        csrwi mcountinhibit, 0
+        csrw mcycle, x0
+        csrw minstret, x1
+        csrr x2, mcycle",
+        6,
+    );
+
+    // counting stopped for the instructions between the two mcountinhibit writes,
+    // then explicit writes took precedence over the increment
+    assert_eq!(emulator_state.x[2], 1);
+    assert_eq!(emulator_state.read_counter(CSR::MINSTRET, CSR::MINSTRETH), 2);
+    assert_eq!(emulator_state.statistics.retired, 6);
+}
+
+// clocks an assembled program for a fixed number of cycles
+fn run_for_cycles(source: &str, cycles: usize, setup: impl Fn(&mut EmulatorState)) -> EmulatorState {
+    let mut program =
+        crate::assembler::assemble(source).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let mut emulator_state = EmulatorState::default();
+    setup(&mut emulator_state);
+    for _ in 0..cycles {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    emulator_state
+}
+
+// enables the interrupts in x7, then counts in x10 until interrupted, the
+// handlers for cause 3, 7, 16 and 31 record mcause/mepc and disable all interrupts
+const INTERRUPT_PROGRAM: &str = ".text
+    addi x1, x0, 0x100
+    csrw mtvec, x1
+    csrw mie, x7
+    csrsi mstatus, 8
+    loop: addi x10, x10, 1
+    jal x0, loop
+
+    .text 0x10C
+    jal x0, handler
+    .text 0x11C
+    jal x0, handler
+    .text 0x140
+    jal x0, handler
+    .text 0x17C
+    jal x0, handler
+    .text 0x180
+    handler: addi x11, x11, 1
+    csrr x12, mcause
+    csrr x13, mepc
+    csrw mie, x0
+    sw x0, 0(x5)
+    mret";
+
+#[test]
+fn test_timer_interrupt() {
+    let emulator_state = run_for_cycles(INTERRUPT_PROGRAM, 100, |state| {
+        state.x[7] = 1 << 7;
+        state.clint.mtimecmp = 40;
+    });
+
+    assert_eq!(emulator_state.x[11], 1);
+    assert_eq!(emulator_state.x[12], 1 << 31 | 7);
+    assert!(emulator_state.x[13] == 0x10 || emulator_state.x[13] == 0x14);
+    assert!(emulator_state.x[10] > 0);
+    // MRET restored the global interrupt enable
+    assert_eq!(emulator_state.read_csr(CSR::MSTATUS) & 0x88, 0x88);
+}
+
+#[test]
+fn test_software_interrupt() {
+    let emulator_state = run_for_cycles(INTERRUPT_PROGRAM, 100, |state| {
+        state.x[5] = crate::emulator::clint::CLINT_BASE;
+        state.x[7] = 1 << 3;
+        state.clint.msip = true;
+    });
+
+    // the handler cleared msip through the CLINT
+    assert_eq!(emulator_state.x[11], 1);
+    assert_eq!(emulator_state.x[12], 1 << 31 | 3);
+    assert!(!emulator_state.clint.msip);
+}
+
+#[test]
+fn test_fast_interrupt() {
+    let emulator_state = run_for_cycles(INTERRUPT_PROGRAM, 100, |state| {
+        state.x[7] = 1 << 16 | 1 << 7;
+        state.pipeline.datapath.irq_fast_i = 1;
+        state.clint.mtimecmp = 0;
+    });
+
+    // fast interrupts take priority over the timer
+    assert_eq!(emulator_state.x[11], 1);
+    assert_eq!(emulator_state.x[12], 1 << 31 | 16);
+}
+
+#[test]
+fn test_interrupts_masked() {
+    // mie is never enabled for the pending timer interrupt
+    let emulator_state = run_for_cycles(INTERRUPT_PROGRAM, 100, |state| {
+        state.x[7] = 1 << 3;
+        state.clint.mtimecmp = 0;
+    });
+    assert_eq!(emulator_state.x[11], 0);
+    assert_eq!(emulator_state.read_csr(CSR::MIP), 1 << 7);
+
+    // mstatus.MIE is clear while the program sets up mtvec
+    let emulator_state = run_for_cycles(INTERRUPT_PROGRAM, 6, |state| {
+        state.x[7] = 1 << 7;
+        state.clint.mtimecmp = 0;
+    });
+    assert_eq!(emulator_state.x[11], 0);
+    assert_eq!(emulator_state.pipeline.ID_pc, 0x10);
+}
+
+#[test]
+fn test_clint_registers() {
+    let emulator_state = run_for_cycles(
+        ".text
+        lui x5, 0x2004
+        addi x6, x0, -1
+        sw x6, 4(x5)
+        sb x0, 0(x5)
+        lui x5, 0x200C
+        lw x7, -8(x5)",
+        20,
+        |_| (),
+    );
+
+    assert_eq!(emulator_state.clint.mtimecmp, 0xFFFF_FFFF_FFFF_FF00);
+    // mtime read by the load's memory access in cycle 9
+    assert_eq!(emulator_state.x[7], 9);
+}
+
+#[test]
+fn test_nonmaskable_interrupt() {
+    // taken right away, even though mstatus.MIE is still clear
+    let emulator_state = run_for_cycles(INTERRUPT_PROGRAM, 8, |state| {
+        state.write_csr(CSR::MTVEC, 0x100);
+        state.pipeline.datapath.irq_nm_i = true;
+    });
+
+    assert_eq!(emulator_state.x[11], 1);
+    assert_eq!(emulator_state.x[12], 1 << 31 | 31);
+    assert_eq!(emulator_state.x[13], 0);
+    assert!(emulator_state.nmi_mode);
+}
+
+#[test]
+fn test_uart_hello() {
+    let emulator_state = run_for_cycles(crate::include_test_file!("uart-hello.s"), 1000, |state| {
+        state.uart.rx.extend(b"ok");
+    });
+
+    assert_eq!(emulator_state.uart.tx, b"Hello from the UART!\nok");
+    assert!(emulator_state.uart.rx.is_empty());
+}
+
+#[test]
+fn test_uart_registers() {
+    let emulator_state = run_for_cycles(
+        ".text
+        lui x5, 0x10000
+        lw x6, 4(x5)
+        lw x7, 0(x5)
+        lw x8, 4(x5)
+        sw x7, 0(x5)
+        sh x7, 1(x5)",
+        20,
+        |state| state.uart.rx.extend(b"ab"),
+    );
+
+    // a word read of DATA only pops a single byte
+    assert_eq!(emulator_state.x[6], 0b11);
+    assert_eq!(emulator_state.x[7], b'a' as u32);
+    assert_eq!(emulator_state.x[8], 0b11);
+    assert_eq!(emulator_state.uart.rx, [b'b']);
+    // writes that skip the low byte don't transmit
+    assert_eq!(emulator_state.uart.tx, b"a");
+}
+
+#[test]
+fn test_gpio() {
+    let emulator_state = run_for_cycles(
+        ".text
+        lui x5, 0x10001
+        lw x6, 8(x5)
+        sw x6, 0(x5)
+        lw x7, 12(x5)
+        addi x8, x0, 0x3F
+        sb x8, 5(x5)
+        sw x0, 8(x5)",
+        20,
+        |state| {
+            state.gpio.switches = 0xA5A5;
+            state.gpio.buttons = 0b0100;
+        },
+    );
+
+    // switches mirrored onto the LEDs
+    assert_eq!(emulator_state.x[6], 0xA5A5);
+    assert_eq!(emulator_state.gpio.leds, 0xA5A5);
+    assert_eq!(emulator_state.x[7], 0b0100);
+    // a "0" on digit 1
+    assert_eq!(emulator_state.gpio.seven_segment, [0, 0x3F, 0, 0]);
+    // inputs can't be written by the program
+    assert_eq!(emulator_state.gpio.switches, 0xA5A5);
+}
+
+#[test]
+fn test_framebuffer() {
+    let emulator_state = run_for_cycles(
+        ".text
+        lui x5, 0x20000
+        lui x6, 0xFF0
+        addi x6, x6, 0x0FF
+        sw x6, 0(x5)
+        addi x7, x0, -1
+        sw x7, 132(x5)
+        lw x8, 0(x5)",
+        20,
+        |_| (),
+    );
+
+    assert_eq!(emulator_state.framebuffer.pixels[0], 0xFF00FF);
+    // pixel (1, 1), the unused top byte always reads as 0
+    assert_eq!(emulator_state.framebuffer.pixels[33], 0xFFFFFF);
+    assert_eq!(emulator_state.x[8], 0xFF00FF);
+}
+
+// like run_for_cycles, but with the program's memory map enforced
+fn run_mapped(source: &str, cycles: usize) -> EmulatorState {
+    let mut program =
+        crate::assembler::assemble(source).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.memory_map = emulator_state.program_memory_map(&program);
+    for _ in 0..cycles {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    emulator_state
+}
+
+#[test]
+fn test_memory_map_regions() {
+    let program = crate::assembler::assemble(".data 0x100\n.word 1\n.text\naddi x1, x0, 1").unwrap();
+    let memory_map = EmulatorState::default().program_memory_map(&program);
+
+    let find = |kind| memory_map.regions.iter().find(|r| r.kind == kind).unwrap();
+    assert_eq!((find(RegionKind::Text).base, find(RegionKind::Text).size), (0, 4));
+    assert_eq!((find(RegionKind::Data).base, find(RegionKind::Data).size), (0x100, 4));
+    assert_eq!(find(RegionKind::Heap).base, 0x110);
+    assert_eq!(
+        find(RegionKind::Stack).base + find(RegionKind::Stack).size,
+        memory_map::STACK_TOP
+    );
+    assert!(memory_map.check(0x100, [true; 4], Access::Write).is_ok());
+    assert!(memory_map.check(0x102, [true, true, false, false], Access::Write).is_ok());
+    // runs past the end of .data
+    assert!(memory_map.check(0x102, [true; 4], Access::Write).is_err());
+    assert!(memory_map.check(0, [true; 4], Access::Write).is_err());
+    assert!(memory_map.check(0, [true; 4], Access::Read).is_ok());
+    assert!(memory_map.check(0x100, [true; 4], Access::Execute).is_err());
+    assert!(memory_map.check(0x1000_0000, [true; 4], Access::Write).is_ok());
+}
+
+#[test]
+fn test_load_access_fault() {
+    let emulator_state = run_mapped(
+        ".text
+        addi x1, x0, 5
+        lui x2, 0x40000
+        lw x1, 0(x2)
+        addi x3, x0, 1",
+        4,
+    );
+
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 5);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 0x4000_0000);
+    assert_eq!(emulator_state.read_csr(CSR::MEPC), 8);
+    assert!(emulator_state.pipeline.datapath.data_err_i);
+    // the load never wrote its destination
+    assert_eq!(emulator_state.x[1], 5);
+    assert_eq!(emulator_state.pipeline.datapath.instr_addr_o, 0);
+}
+
+#[test]
+fn test_moved_uart_is_mapped() {
+    let mut program = crate::assembler::assemble(
+        ".text
+        lui x5, 0x30000
+        addi x6, x0, 0x41
+        sw x6, 0(x5)
+        lui x5, 0x10000
+        sw x6, 0(x5)",
+    )
+    .unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.load_program(&program);
+    assert!(emulator_state.set_uart_base(0x3000_0002).is_err());
+    assert_eq!(emulator_state.set_uart_base(0x3000_0000), Ok(()));
+    for _ in 0..8 {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+
+    assert_eq!(emulator_state.uart.base, 0x3000_0000);
+    assert_eq!(emulator_state.uart.tx, b"A");
+    // the old base is no longer mapped
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 7);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 0x1000_0000);
+}
+
+#[test]
+fn test_uart_base_overlap() {
+    let program = crate::assembler::assemble(".data 0x1000\n.word 1\n.text\naddi x1, x0, 1").unwrap();
+    let mut emulator_state = EmulatorState::default();
+    // the devices are checked without a memory map too
+    assert!(emulator_state.set_uart_base(gpio::GPIO_BASE + 4).is_err());
+    emulator_state.load_program(&program);
+
+    for base in [
+        clint::CLINT_BASE,
+        gpio::GPIO_BASE - 8,
+        framebuffer::FRAMEBUFFER_BASE + 0x10,
+        0x1000,
+        0x7FFF_FFF8,
+        0xFFFF_FFF8,
+    ] {
+        let error = emulator_state.set_uart_base(base).unwrap_err();
+        assert!(error.contains(&format!("{base:#010x}")), "{error}");
+        assert_eq!(emulator_state.uart.base, uart::UART_BASE);
+    }
+    let uart = emulator_state.memory_map.regions.iter().find(|r| r.name == "UART");
+    assert_eq!(uart.unwrap().base, uart::UART_BASE);
+}
+
+#[test]
+fn test_store_to_text_faults() {
+    let emulator_state = run_mapped(
+        ".data 0x100
+        .word 0
+        .text
+        sw x0, 0x100(x0)
+        sw x0, 4(x0)",
+        5,
+    );
+
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 7);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 4);
+    assert_eq!(emulator_state.read_csr(CSR::MEPC), 4);
+}
+
+#[test]
+fn test_load_from_text() {
+    let emulator_state = run_mapped(
+        ".text
+        lw x1, 0(x0)",
+        3,
+    );
+
+    // loads from text read the instructions
+    assert_eq!(emulator_state.x[1], 0x00002083);
+}
+
+#[test]
+fn test_instruction_access_fault() {
+    let emulator_state = run_mapped(
+        ".text
+        addi x1, x0, 1
+        jal x0, 0x100",
+        6,
+    );
+
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 1);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 0x104);
+    assert_eq!(emulator_state.read_csr(CSR::MEPC), 0x104);
+}
+
+#[test]
+fn test_ecall_traps() {
+    let emulator_state = run_for_cycles(
+        ".text
+        addi x1, x0, 0x100
+        csrw mtvec, x1
+        ecall
+        addi x2, x0, 1",
+        8,
+        |_| (),
+    );
+
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 11);
+    assert_eq!(emulator_state.read_csr(CSR::MEPC), 8);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 0);
+    assert_eq!(emulator_state.x[2], 0);
+}
+
+#[test]
+fn test_misaligned_jump_traps() {
+    let emulator_state = run_for_cycles(
+        ".text
+        addi x1, x0, 0x100
+        csrw mtvec, x1
+        jal x5, 6",
+        8,
+        |_| (),
+    );
+
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 0);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 14);
+    assert_eq!(emulator_state.read_csr(CSR::MEPC), 8);
+    // the jump didn't link
+    assert_eq!(emulator_state.x[5], 0);
+}
+
+#[test]
+fn test_jump_targets_wrap() {
+    // jumping past the top of the address space wraps around to 0
+    let emulator_state = run_for_cycles(
+        ".text 0xFFFFFFF8
+        jal x1, 12",
+        2,
+        |state| state.pipeline.datapath.instr_addr_o = 0xFFFF_FFF8,
+    );
+
+    assert_eq!(emulator_state.x[1], 0xFFFF_FFFC);
+    assert_eq!(emulator_state.pipeline.datapath.instr_addr_o, 4);
+}
+
+// x1 := 0x89ABCDEF
+const WORD_PATTERN: &str = ".text
+    lui x1, 0x89ABD
+    addi x1, x1, -529
+";
+
+#[test]
+fn test_sub_word_lanes() {
+    let emulator_state = run_until_retired(
+        &format!(
+            "{WORD_PATTERN}
+            sw x1, 0x100(x0)
+            lb x2, 0x101(x0)
+            lbu x3, 0x103(x0)
+            lh x4, 0x102(x0)
+            lhu x5, 0x101(x0)
+            sh x1, 0x106(x0)
+            lhu x6, 0x106(x0)
+            sb x1, 0x10A(x0)
+            lw x7, 0x108(x0)"
+        ),
+        11,
+    );
+
+    assert_eq!(emulator_state.x[2], 0xFFFF_FFCD);
+    assert_eq!(emulator_state.x[3], 0x89);
+    assert_eq!(emulator_state.x[4], 0xFFFF_89AB);
+    assert_eq!(emulator_state.x[5], 0xABCD);
+    assert_eq!(emulator_state.x[6], 0xCDEF);
+    assert_eq!(emulator_state.x[7], 0x00EF_0000);
+}
+
+#[test]
+fn test_misaligned_access_split() {
+    let emulator_state = run_until_retired(
+        &format!(
+            "{WORD_PATTERN}
+            sw x1, 0x103(x0)
+            lw x2, 0x101(x0)
+            lh x3, 0x103(x0)
+            lw x4, 0x103(x0)"
+        ),
+        6,
+    );
+
+    assert_eq!(emulator_state.x[2], 0xCDEF_0000);
+    assert_eq!(emulator_state.x[3], 0xFFFF_CDEF);
+    assert_eq!(emulator_state.x[4], 0x89AB_CDEF);
+
+    // the second bus transaction costs one extra cycle
+    let aligned = run_until_retired(".text\nlw x1, 0x100(x0)", 1);
+    let split = run_until_retired(".text\nlw x1, 0x102(x0)", 1);
+    assert_eq!(split.statistics.cycles, aligned.statistics.cycles + 1);
+}
+
+#[test]
+fn test_misaligned_access_trap() {
+    let trap = |emulator_state: &mut EmulatorState| {
+        emulator_state.config.misaligned_access = MisalignedAccess::Trap;
+    };
+
+    let emulator_state = run_for_cycles(".text\naddi x1, x0, 5\nlw x1, 0x102(x0)", 4, trap);
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 4);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 0x102);
+    assert_eq!(emulator_state.read_csr(CSR::MEPC), 4);
+    assert_eq!(emulator_state.x[1], 5);
+
+    // a halfword within one word is still misaligned
+    let emulator_state = run_for_cycles(".text\nsh x1, 0x101(x0)", 3, trap);
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 6);
+    assert_eq!(emulator_state.read_csr(CSR::MTVAL), 0x101);
+
+    // byte accesses are never misaligned
+    let emulator_state = run_for_cycles(
+        ".text\naddi x1, x0, -1\nsb x1, 0x103(x0)\nlb x2, 0x103(x0)",
+        10,
+        trap,
+    );
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 0);
+    assert_eq!(emulator_state.x[2], 0xFFFF_FFFF);
+}
+
+#[test]
+fn test_loader_entry_point() {
+    let program = crate::assembler::assemble(".text\naddi x1, x0, 1\nmain: addi x1, x0, 2").unwrap();
+    assert_eq!(loader::entry_point(&program), 4);
+
+    let program =
+        crate::assembler::assemble(".text\nmain: addi x1, x0, 1\n_start: addi x1, x0, 2").unwrap();
+    assert_eq!(loader::entry_point(&program), 4);
+
+    let program = crate::assembler::assemble(".text 0x40\naddi x1, x0, 1").unwrap();
+    assert_eq!(loader::entry_point(&program), 0x40);
+}
+
+#[test]
+fn test_loader_registers() {
+    let program = crate::assembler::assemble(".data 0x1000\n.word 1\n.text\naddi x1, x0, 1").unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.config.loader.stack_top = 0x4000_0008;
+    emulator_state.load_program(&program);
+
+    assert_eq!(emulator_state.x[1], loader::HALT_ADDRESS);
+    assert_eq!(emulator_state.x[2], 0x4000_0000);
+    assert_eq!(emulator_state.x[3], 0x1800);
+    let stack = emulator_state.memory_map.regions.iter().find(|r| r.kind == RegionKind::Stack);
+    assert_eq!(stack.unwrap().base, 0x4000_0008 - memory_map::STACK_SIZE);
+}
+
+#[test]
+fn test_loader_layout() {
+    let program = crate::assembler::assemble(".data 0x1000\n.word 1\n.text\naddi x1, x0, 1").unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.load_program(&program);
+    assert_eq!(emulator_state.memory_map.check_loader_regions(), Ok(()));
+    let loader = emulator_state.config.loader.clone();
+
+    // the map follows a layout that fits
+    let moved = LoaderConfig { stack_top: 0x4000_0000, heap_size: 0x100, ..loader.clone() };
+    assert_eq!(emulator_state.set_loader(moved.clone()), Ok(()));
+    assert_eq!(emulator_state.config.loader, moved);
+    let region = |state: &EmulatorState, kind| *state.memory_map.regions.iter().find(|r| r.kind == kind).unwrap();
+    assert_eq!(region(&emulator_state, RegionKind::Stack).base, 0x4000_0000 - loader.stack_size);
+    assert_eq!(region(&emulator_state, RegionKind::Heap).size, 0x100);
+
+    // and is left alone by one that doesn't
+    let rejected = [
+        LoaderConfig { stack_top: Uart::default().base + 0x10, ..moved.clone() },
+        LoaderConfig { stack_top: 0x2000, stack_size: 0x1800, ..moved.clone() },
+        LoaderConfig { heap_size: 0x8000_0000, ..moved.clone() },
+        LoaderConfig { stack_size: 0x4000_0004, ..moved.clone() },
+        LoaderConfig { halt_address: 0xFFFF_FFF2, ..moved.clone() },
+    ];
+    for loader in rejected {
+        let before = emulator_state.memory_map.clone();
+        assert!(emulator_state.set_loader(loader).is_err());
+        assert_eq!(emulator_state.memory_map, before);
+        assert_eq!(emulator_state.config.loader, moved);
+    }
+}
+
+#[test]
+fn test_loader_halts_on_return() {
+    let mut program = crate::assembler::assemble(
+        ".text
+        double: add x10, x10, x10
+        jalr x0, x1, 0
+        main: addi x2, x2, -16
+        sw x1, 12(x2)
+        addi x10, x0, 21
+        jal x1, double
+        lw x1, 12(x2)
+        addi x2, x2, 16
+        jalr x0, x1, 0",
+    )
+    .unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.load_program(&program);
+    assert_eq!(emulator_state.pipeline.datapath.instr_addr_o, 8);
+
+    for _ in 0..100 {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    assert!(emulator_state.halted());
+    assert_eq!(emulator_state.x[10], 42);
+    assert_eq!(emulator_state.x[2], memory_map::STACK_TOP);
+    assert_eq!(emulator_state.statistics.retired, 9);
+    // the return address was saved on the stack
+    assert_eq!(program.data_memory.get(&(memory_map::STACK_TOP - 4)), Some(&0xF0));
+    assert_eq!(emulator_state.read_csr(CSR::MCAUSE), 0);
+}
+
+// sums 1..=4 recursively, each call saves ra and s0 in a 16 byte frame
+const RECURSIVE_SUM: &str = ".text
+    main: addi x2, x2, -16
+    sw x1, 12(x2)
+    addi x10, x0, 4
+    jal x1, sum
+    lw x1, 12(x2)
+    addi x2, x2, 16
+    jalr x0, x1, 0
+    sum: addi x2, x2, -16
+    sw x1, 12(x2)
+    sw x8, 8(x2)
+    addi x8, x10, 0
+    addi x6, x0, 1
+    blt x6, x10, recurse
+    jal x0, done
+    recurse: addi x10, x10, -1
+    jal x1, sum
+    add x10, x10, x8
+    done: lw x8, 8(x2)
+    lw x1, 12(x2)
+    addi x2, x2, 16
+    jalr x0, x1, 0";
+
+#[test]
+fn test_call_stack() {
+    let mut program = crate::assembler::assemble(RECURSIVE_SUM).unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.load_program(&program);
+    assert_eq!(emulator_state.call_stack.frames.len(), 1);
+
+    let mut deepest = emulator_state.clone();
+    while !emulator_state.halted() {
+        emulator_state = clock(&emulator_state, &mut program);
+        if emulator_state.call_stack.frames.len() > deepest.call_stack.frames.len() {
+            deepest = emulator_state.clone();
+        }
+        assert!(emulator_state.statistics.cycles < 500);
+    }
+    assert_eq!(emulator_state.x[10], 10);
+    // main returned to the halt address
+    assert!(emulator_state.call_stack.frames.is_empty());
+
+    // main and sum(4) down to sum(1)
+    let frames = &deepest.call_stack.frames;
+    assert_eq!(frames.len(), 5);
+    let sum = program.labels["sum"];
+    assert!(frames[1..].iter().all(|frame| frame.target == sum));
+    assert_eq!(frames[1].call_site, Some(12));
+    assert_eq!(frames[2].call_site, Some(program.labels["recurse"] + 4));
+    assert_eq!(frames[2].sp, memory_map::STACK_TOP - 32);
+
+    // each frame holds its caller's ra and s0
+    let sp = frames[2].sp;
+    assert_eq!(frames[2].saved.get(&(sp - 4)), Some(&1));
+    assert_eq!(frames[2].saved.get(&(sp - 8)), Some(&8));
+
+    let regions = deepest.call_stack.regions(memory_map::STACK_TOP - 64);
+    assert_eq!(regions[0], (memory_map::STACK_TOP - 16, memory_map::STACK_TOP));
+    assert_eq!(regions[4], (memory_map::STACK_TOP - 64, memory_map::STACK_TOP - 64));
+}
+
+#[test]
+fn test_call_stack_link_hints() {
+    let mut emulator_state = EmulatorState::default();
+    let mut jump = |rd, rs1, pc, target| {
+        emulator_state.pipeline.ID_pc = pc;
+        emulator_state.track_jump(rd, rs1, target);
+        emulator_state.call_stack.frames.clone()
+    };
+    jump(1, None, 0x10, 0x100);
+    assert_eq!(jump(5, None, 0x104, 0x200).len(), 2);
+
+    // returning through ra unwinds the t0 call above it
+    assert!(jump(0, Some(1), 0x110, 0x14).is_empty());
+
+    // an indirect jump through another register is neither
+    assert!(jump(0, Some(6), 0x10, 0x100).is_empty());
+
+    // ra and t0 swapped is a return followed by a call
+    jump(1, None, 0x10, 0x100);
+    let frames = jump(5, Some(1), 0x104, 0x14);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].return_address, 0x108);
+}
+
+fn check_calling_convention(source: &str, cycles: usize) -> Vec<(u32, String)> {
+    let mut program = crate::assembler::assemble(source).unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.config.check_calling_convention = true;
+    emulator_state.load_program(&program);
+    for _ in 0..cycles {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    emulator_state
+        .checker
+        .violations
+        .iter()
+        .map(|violation| (violation.pc, violation.kind.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_calling_convention_clean() {
+    assert_eq!(check_calling_convention(RECURSIVE_SUM, 200), vec![]);
+}
+
+#[test]
+fn test_calling_convention_violations() {
+    let violations = check_calling_convention(
+        ".text
+        main: addi x5, x0, 7
+        jal x1, bad
+        add x10, x10, x5
+        addi x2, x2, -4
+        jal x1, bad
+        end: jal x0, end
+        bad: addi x8, x0, 1
+        addi x2, x2, -16
+        jalr x0, x1, 0",
+        40,
+    );
+
+    let sp = memory_map::STACK_TOP - 20;
+    let expected = [
+        (4, "call overwrites ra, which was not saved on the stack".to_string()),
+        (32, "sp was not restored before returning".to_string()),
+        (32, "callee-saved register s0 was not restored before returning".to_string()),
+        (8, "caller-saved register t0 is read after a call without being set, the callee may have changed it".to_string()),
+        (16, "call overwrites ra, which was not saved on the stack".to_string()),
+        (16, format!("sp ({sp:#010x}) is not 16 byte aligned at a call")),
+    ];
+    assert_eq!(violations, expected);
+}
+
+#[test]
+fn test_edit_history() {
+    let mut program = crate::assembler::assemble(".data 0x100\n.word 7\n.text\nlw x1, 0x100(x0)").unwrap();
+    let mut emulator_state = clock(&EmulatorState::default(), &mut program);
+
+    emulator_state.edit_register(10, -5i32 as u32);
+    emulator_state.edit_register(0, 1);
+    emulator_state.edit_memory(&mut program, 0x102, 0xCAFE_F00D);
+    assert_eq!(emulator_state.x[10], 0xFFFF_FFFB);
+    assert_eq!(emulator_state.x[0], 0);
+    assert_eq!(program.data_memory.get(&0x100), Some(&0x0D));
+    assert_eq!(
+        emulator_state.edits,
+        vec![
+            Edit { cycle: 1, target: EditTarget::Register(10), old: 0, new: 0xFFFF_FFFB },
+            Edit { cycle: 1, target: EditTarget::Memory(0x100), old: 7, new: 0xCAFE_F00D },
+        ]
+    );
+
+    // the program sees the edited memory
+    for _ in 0..3 {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    assert_eq!(emulator_state.x[1], 0xCAFE_F00D);
+
+    emulator_state.undo_edit(&mut program);
+    emulator_state.undo_edit(&mut program);
+    assert_eq!(emulator_state.x[10], 0);
+    assert_eq!(program.data_memory.get(&0x100), Some(&7));
+    assert_eq!(emulator_state.undo_edit(&mut program), None);
+}
+
+#[test]
+fn test_written_bytes() {
+    let mut program = crate::assembler::assemble(".text\naddi x1, x0, 1\nsh x1, 0x103(x0)").unwrap();
+    let mut emulator_state = EmulatorState::default();
+    let mut written = Vec::new();
+    for _ in 0..6 {
+        emulator_state = clock(&emulator_state, &mut program);
+        written.push(emulator_state.written_bytes());
+    }
+
+    // the halfword is split over two words, each written by its own clock
+    assert_eq!(written[2], vec![0x103]);
+    assert_eq!(written[3], vec![0x104]);
+    assert!(written.iter().enumerate().all(|(i, w)| i == 2 || i == 3 || w.is_empty()));
+}
+
+#[test]
+fn test_changed_registers() {
+    let mut program = crate::assembler::assemble(".text\naddi x1, x0, 1\naddi x1, x0, 1\naddi x2, x0, 2").unwrap();
+    let mut changed = Vec::new();
+    let mut emulator_state = EmulatorState::default();
+    for _ in 0..4 {
+        emulator_state = clock(&emulator_state, &mut program);
+        changed.push(emulator_state.changed_registers());
+    }
+
+    // writing the value a register already holds doesn't change it
+    assert_eq!(changed, vec![vec![], vec![1], vec![], vec![2]]);
+}
+
+#[test]
+fn test_pipeline_timeline() {
+    let emulator_state = run_for_cycles(
+        ".text
+        addi x1, x0, 1
+        beq x0, x0, target
+        addi x2, x0, 2
+        target: addi x3, x0, 3",
+        5,
+        |_| (),
+    );
+    let rows = emulator_state.timeline.rows(TIMELINE_LENGTH);
+    let cells = |pc: u32| {
+        rows.iter()
+            .filter(|row| row.pc == pc)
+            .map(|row| row.cells.clone())
+            .collect::<Vec<_>>()
+    };
+    use StageActivity::*;
+
+    assert_eq!(cells(0x0), vec![vec![(0, Fetch), (1, Execute)]]);
+    // the taken branch spends a second cycle in ID while the target is fetched
+    assert_eq!(cells(0x4), vec![vec![(1, Fetch), (2, Execute), (3, Stall)]]);
+    assert_eq!(cells(0x8), vec![vec![(2, Flush)]]);
+    assert_eq!(cells(0xC), vec![vec![(3, Fetch), (4, Execute)]]);
+    assert_eq!(rows.len(), 5);
+
+    let text = emulator_state.timeline.to_text(3);
+    assert_eq!(text.lines().count(), 5);
+    assert!(text.contains("00000004 BEQ"));
+
+    // only the most recent cycles are kept
+    let emulator_state = run_for_cycles(".text\nloop: jal x0, loop", 100, |_| ());
+    assert_eq!(emulator_state.timeline.records.len(), TIMELINE_LENGTH);
+    assert_eq!(emulator_state.timeline.records.back().unwrap().cycle, 99);
+}
+
+#[test]
+fn test_waveform_vcd() {
+    let emulator_state = run_for_cycles(".text\naddi x1, x0, 5\nsw x1, 0x100(x0)", 4, |_| ());
+    let waveform = &emulator_state.waveform;
+    assert_eq!(waveform.samples.len(), 4);
+
+    let data_req = SIGNALS.iter().position(|s| s.name == "data_req_o").unwrap();
+    let data_addr = SIGNALS.iter().position(|s| s.name == "data_addr_o").unwrap();
+    let requests: Vec<_> = waveform.values(&SIGNALS[data_req]).collect();
+    assert_eq!(requests, vec![(0, 0), (1, 0), (2, 1), (3, 0)]);
+
+    let vcd = waveform.to_vcd(&[data_req, data_addr]);
+    let id = |i: usize| char::from(b'"' + i as u8);
+    assert!(vcd.starts_with("$version EmuGator $end\n$timescale 1ns $end\n"));
+    assert!(vcd.contains("$var wire 1 ! clk_i $end"));
+    assert!(vcd.contains(&format!("$var wire 1 {} data_req_o $end", id(data_req))));
+    assert!(vcd.contains(&format!("$var wire 32 {} data_addr_o [31:0] $end", id(data_addr))));
+    // only changes are dumped after the initial values
+    assert!(vcd.contains(&format!("#0\n$dumpvars\n1!\n0{}\nb0 {}\n$end\n#5\n0!\n#10\n1!\n#15", id(data_req), id(data_addr))));
+    assert!(vcd.contains(&format!("#20\n1!\n1{}\nb100000000 {}\n", id(data_req), id(data_addr))));
+    assert!(vcd.ends_with("#40\n"));
+}
+
+// cycles until `retired` instructions retired with the program's memory map,
+// data memory answering after `data_latency` cycles
+fn timed_cycles(source: &str, retired: u64, timing: TimingModel, data_latency: u32) -> u64 {
+    let mut program =
+        crate::assembler::assemble(source).unwrap_or_else(|e| panic!("Assembly Error: {}", e));
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.memory_map = emulator_state.program_memory_map(&program);
+    emulator_state.config.timing = timing;
+    emulator_state.config.bus.regions.insert(
+        RegionKind::Data,
+        RegionTiming {
+            wait_states: 0,
+            latency: data_latency,
+        },
+    );
+    for _ in 0..1000 {
+        if emulator_state.statistics.retired == retired {
+            return emulator_state.statistics.cycles;
+        }
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    panic!("only {} instructions retired", emulator_state.statistics.retired);
+}
+
+fn accurate_cycles(source: &str, retired: u64) -> u64 {
+    timed_cycles(source, retired, TimingModel::CycleAccurate, 1)
+}
+
+// every count includes the cycle fetching the first instruction
+#[test]
+fn test_cycle_accurate_alu() {
+    let source = ".text\naddi x1, x0, 1\naddi x2, x1, 1\nadd x3, x1, x2\nsub x4, x3, x1";
+    assert_eq!(accurate_cycles(source, 4), 5);
+    assert_eq!(run_until_retired(source, 4).statistics.cycles, 5);
+}
+
+#[test]
+fn test_cycle_accurate_branches() {
+    let not_taken = ".text\naddi x1, x0, 1\nbeq x1, x0, skip\naddi x2, x0, 2\nskip: addi x3, x0, 3";
+    assert_eq!(accurate_cycles(not_taken, 3), 4);
+
+    // the taken branch computes its target in a third cycle
+    let taken = ".text\nbeq x0, x0, target\naddi x1, x0, 1\ntarget: addi x2, x0, 2";
+    assert_eq!(accurate_cycles(taken, 2), 5);
+    assert_eq!(run_until_retired(taken, 2).statistics.cycles, 4);
+
+    let jump = ".text\njal x0, target\naddi x1, x0, 1\ntarget: addi x2, x0, 2";
+    assert_eq!(accurate_cycles(jump, 2), 4);
+}
+
+#[test]
+fn test_memory_latency() {
+    let load = ".data 0x100\n.word 41, 0\n.text\nlw x1, 0x100(x0)\naddi x2, x1, 1";
+    assert_eq!(accurate_cycles(load, 2), 4);
+    assert_eq!(timed_cycles(load, 2, TimingModel::CycleAccurate, 3), 6);
+    // loads and stores wait for the bus response in either timing model
+    assert_eq!(timed_cycles(load, 2, TimingModel::Approximate, 3), 6);
+
+    // a misaligned word is two transactions, each waiting for the memory
+    let split = ".data 0x100\n.word 41, 0\n.text\nlw x1, 0x102(x0)\naddi x2, x1, 1";
+    assert_eq!(timed_cycles(split, 2, TimingModel::CycleAccurate, 3), 9);
+
+    let store = ".data 0x100\n.word 41, 0\n.text\nsw x0, 0x100(x0)\naddi x2, x0, 1";
+    assert_eq!(timed_cycles(store, 2, TimingModel::CycleAccurate, 2), 5);
+
+    // the loaded value doesn't depend on the timing
+    let mut program = crate::assembler::assemble(load).unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.memory_map = emulator_state.program_memory_map(&program);
+    emulator_state.config.timing = TimingModel::CycleAccurate;
+    emulator_state.config.bus.regions.insert(RegionKind::Data, RegionTiming { wait_states: 2, latency: 4 });
+    while emulator_state.statistics.retired < 2 {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    assert_eq!(emulator_state.x[2], 42);
+    assert_eq!(emulator_state.statistics.loads, 1);
+}
+
+#[test]
+fn test_cycle_accurate_csrs() {
+    // reading a CSR takes one cycle, writing mie flushes the pipeline
+    let source = ".text\ncsrr x1, mstatus\ncsrw mie, x0\naddi x2, x0, 1";
+    assert_eq!(accurate_cycles(source, 3), 5);
+    assert_eq!(run_until_retired(source, 3).statistics.cycles, 4);
+
+    let mret = ".text\naddi x1, x0, 0x10\ncsrw mepc, x1\nmret\naddi x2, x0, 2\naddi x3, x0, 3";
+    assert_eq!(accurate_cycles(mret, 4), run_until_retired(mret, 4).statistics.cycles);
+}
+
+#[test]
+fn test_data_bus_handshake() {
+    let mut program = crate::assembler::assemble(".data 0x100\n.word 7\n.text\nlw x1, 0x100(x0)\naddi x2, x1, 1").unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.memory_map = emulator_state.program_memory_map(&program);
+    emulator_state.config.bus.regions.insert(RegionKind::Data, RegionTiming { wait_states: 2, latency: 2 });
+
+    let mut signals = Vec::new();
+    for _ in 0..6 {
+        emulator_state = clock(&emulator_state, &mut program);
+        let datapath = emulator_state.pipeline.datapath;
+        signals.push((datapath.data_req_o, datapath.data_gnt_i, datapath.data_rvalid_i));
+    }
+
+    // the request waits two cycles for its grant, is taken back once granted
+    // and answered two cycles after the grant
+    assert_eq!(
+        signals[1..6],
+        [
+            (true, false, false),
+            (true, false, false),
+            (true, true, false),
+            (false, false, true),
+            (false, false, false),
+        ]
+    );
+    assert_eq!(emulator_state.x[1], 7);
+    assert_eq!(emulator_state.statistics.retired, 1);
+}
+
+#[test]
+fn test_instruction_bus_wait_states() {
+    let mut program = crate::assembler::assemble(".text\naddi x1, x0, 1\naddi x2, x0, 2\naddi x3, x0, 3").unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.memory_map = emulator_state.program_memory_map(&program);
+    emulator_state.config.bus.regions.insert(RegionKind::Text, RegionTiming { wait_states: 1, latency: 1 });
+    while emulator_state.statistics.retired < 3 {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+
+    // every fetch takes two cycles, ID gets a bubble while it waits
+    assert_eq!(emulator_state.statistics.cycles, 7);
+    assert_eq!((emulator_state.x[1], emulator_state.x[2], emulator_state.x[3]), (1, 2, 3));
+}
+
+#[test]
+fn test_random_bus_stalls() {
+    let source = ".data 0x100\n.word 1\n.text
+        addi x2, x0, 8
+        loop: lw x1, 0x100(x0)
+        addi x1, x1, 1
+        sw x1, 0x100(x0)
+        addi x2, x2, -1
+        bne x2, x0, loop";
+    let run = |stall_percent: u32| {
+        let mut program = crate::assembler::assemble(source).unwrap();
+        let mut emulator_state = EmulatorState::default();
+        emulator_state.memory_map = emulator_state.program_memory_map(&program);
+        emulator_state.config.bus.instr_stall_percent = stall_percent;
+        emulator_state.config.bus.data_stall_percent = stall_percent;
+        while emulator_state.statistics.retired < 41 {
+            emulator_state = clock(&emulator_state, &mut program);
+        }
+        (emulator_state.statistics.cycles, program.data_memory[&0x100])
+    };
+
+    let (cycles, value) = run(0);
+    let (stalled_cycles, stalled_value) = run(50);
+    assert_eq!(value, 9);
+    assert_eq!(stalled_value, 9);
+    assert!(stalled_cycles > cycles);
+    // the stalls are pseudo-random, a run can be repeated
+    assert_eq!(run(50).0, stalled_cycles);
+}
+
+#[test]
+fn test_jumps_under_fetch_stalls() {
+    // the jump and the taken branch each skip an addi, which must never run
+    // however long the fetch of their target is stalled
+    let source = ".text
+        addi x2, x0, 8
+        loop: jal x0, skip
+        addi x3, x3, 1
+        skip: beq x0, x0, next
+        addi x4, x4, 1
+        next: addi x2, x2, -1
+        bne x2, x0, loop";
+    for stall_percent in [0, 25, 50, 75] {
+        let mut program = crate::assembler::assemble(source).unwrap();
+        let mut emulator_state = EmulatorState::default();
+        emulator_state.memory_map = emulator_state.program_memory_map(&program);
+        emulator_state.config.bus.instr_stall_percent = stall_percent;
+        while emulator_state.statistics.retired < 33 {
+            emulator_state = clock(&emulator_state, &mut program);
+        }
+        assert_eq!(
+            (emulator_state.x[2], emulator_state.x[3], emulator_state.x[4]),
+            (0, 0, 0),
+            "{stall_percent}% fetch stalls"
+        );
+    }
+}
+
+#[test]
+fn test_cache_shapes() {
+    let shape = |size, associativity, block_size| CacheConfig { size, associativity, block_size, ..Default::default() };
+    assert!(CacheConfig::default().is_valid());
+    assert!(shape(64, 2, 32).is_valid());
+    assert_eq!(shape(64, 2, 32).sets(), 1);
+    // 8 ways of 32 B blocks don't fit in 64 B
+    assert!(!shape(64, 8, 32).is_valid());
+    assert!(!shape(96, 1, 16).is_valid());
+    assert!(!shape(64, 0, 16).is_valid());
+    assert!(!shape(64, 1, 2).is_valid());
+
+    let mut config = EmulatorConfig { dcache: Some(shape(64, 2, 32)), ..Default::default() };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(serde_json::from_str::<EmulatorConfig>(&json).unwrap(), config);
+    config.dcache = Some(shape(64, 8, 32));
+    let json = serde_json::to_string(&config).unwrap();
+    let error = serde_json::from_str::<EmulatorConfig>(&json).unwrap_err().to_string();
+    assert!(error.contains("impossible cache"), "{error}");
+}
+
+#[test]
+fn test_cache_replacement() {
+    // two ways of one set, blocks 0x00, 0x10 and 0x20 all map to it
+    let config = CacheConfig { size: 32, associativity: 2, block_size: 16, ..Default::default() };
+    let mut cache = Cache::new(config);
+    assert!(!cache.access(0x00, false).hit);
+    assert!(cache.access(0x04, false).hit);
+    assert!(!cache.access(0x10, false).hit);
+    assert!(cache.access(0x00, false).hit);
+    // LRU evicts 0x10, used longest ago
+    assert!(!cache.access(0x20, false).hit);
+    assert!(cache.access(0x00, false).hit);
+    assert!(!cache.access(0x10, false).hit);
+    assert_eq!((cache.statistics.hits, cache.statistics.misses), (3, 4));
+
+    // FIFO evicts 0x00, filled first
+    let mut cache = Cache::new(CacheConfig { replacement: Replacement::Fifo, ..config });
+    for address in [0x00, 0x10, 0x00, 0x20] {
+        cache.access(address, false);
+    }
+    assert!(cache.access(0x10, false).hit);
+    assert!(!cache.access(0x00, false).hit);
+
+    // a direct-mapped cache has 0x00 and 0x20 conflict in its two sets
+    let mut cache = Cache::new(CacheConfig { associativity: 1, ..config });
+    assert_eq!(cache.locate(0x24), (0, 1));
+    assert!(!cache.access(0x00, false).hit);
+    assert!(!cache.access(0x10, false).hit);
+    assert!(!cache.access(0x20, false).hit);
+    assert!(cache.access(0x10, false).hit);
+    assert!(!cache.access(0x00, false).hit);
+}
+
+#[test]
+fn test_cache_write_policies() {
+    let config = CacheConfig { size: 16, associativity: 1, block_size: 16, ..Default::default() };
+    let memory = RegionTiming { wait_states: 1, latency: 2 };
+
+    // write-back: a store marks its line dirty, evicting it writes it back
+    let mut cache = Cache::new(config);
+    assert_eq!(cache.timing(0x00, true, memory), RegionTiming { wait_states: 0, latency: 6 });
+    assert!(cache.sets[0][0].dirty);
+    assert_eq!(cache.timing(0x08, false, memory), RegionTiming { wait_states: 0, latency: 1 });
+    let eviction = cache.access(0x40, false);
+    assert!(eviction.writeback);
+    assert_eq!(cache.statistics.writebacks, 1);
+    assert!(!cache.sets[0][0].dirty);
+
+    // write-through: stores go to memory and a store miss doesn't allocate
+    let mut cache = Cache::new(CacheConfig { write_policy: WritePolicy::WriteThrough, ..config });
+    assert_eq!(cache.timing(0x00, true, memory), memory);
+    assert!(!cache.sets[0][0].valid);
+    cache.access(0x00, false);
+    assert_eq!(cache.timing(0x04, true, memory), memory);
+    assert!(!cache.sets[0][0].dirty);
+    assert_eq!(cache.statistics.writebacks, 0);
+}
+
+#[test]
+fn test_cached_loop() {
+    let source = ".data 0x100\n.word 0\n.text
+        addi x2, x0, 8
+        loop: lw x1, 0x100(x0)
+        addi x1, x1, 1
+        sw x1, 0x100(x0)
+        addi x2, x2, -1
+        bne x2, x0, loop";
+    let run = |cached: bool| {
+        let mut program = crate::assembler::assemble(source).unwrap();
+        let mut emulator_state = EmulatorState::default();
+        emulator_state.memory_map = emulator_state.program_memory_map(&program);
+        let slow = RegionTiming { wait_states: 2, latency: 4 };
+        emulator_state.config.bus.regions.insert(RegionKind::Text, slow);
+        emulator_state.config.bus.regions.insert(RegionKind::Data, slow);
+        if cached {
+            emulator_state.config.icache = Some(CacheConfig::default());
+            emulator_state.config.dcache = Some(CacheConfig::default());
+        }
+        while emulator_state.statistics.retired < 41 {
+            emulator_state = clock(&emulator_state, &mut program);
+        }
+        (emulator_state, program.data_memory[&0x100])
+    };
+
+    let (uncached, result) = run(false);
+    assert_eq!(result, 8);
+    assert!(uncached.icache.is_none());
+    let (cached, result) = run(true);
+    assert_eq!(result, 8);
+    assert!(cached.statistics.cycles < uncached.statistics.cycles);
+
+    // the loop body spans two blocks, the load and store share one line
+    let icache = cached.icache.unwrap();
+    assert_eq!(icache.statistics.misses, 2);
+    let dcache = cached.dcache.unwrap();
+    assert_eq!(dcache.statistics.misses, 1);
+    assert_eq!(dcache.statistics.hits, 15);
+    assert!(dcache.sets[0].iter().any(|line| line.valid && line.dirty));
+}
+
+fn predicted_run(source: &str, retired: u64, config: PredictorConfig) -> EmulatorState {
+    let mut program = crate::assembler::assemble(source).unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.config.predictor = Some(config);
+    while emulator_state.statistics.retired < retired {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+    emulator_state
+}
+
+#[test]
+fn test_branch_predictors() {
+    // an inner loop of four iterations, run twice
+    let source = ".text
+        addi x4, x0, 2
+        outer: addi x2, x0, 4
+        inner: addi x1, x1, 1
+        addi x2, x2, -1
+        bne x2, x0, inner
+        addi x4, x4, -1
+        bne x4, x0, outer
+        addi x3, x0, 3";
+    let correct = |kind: PredictorKind| {
+        let state = predicted_run(source, 32, PredictorConfig { kind, ..Default::default() });
+        assert_eq!((state.x[1], state.x[3]), (8, 3));
+        let statistics = state.predictor.unwrap().statistics;
+        assert_eq!(statistics.branches, 10);
+        statistics.branches_correct
+    };
+
+    // the inner branch is taken 6 of 8 times, the outer one once of twice
+    assert_eq!(correct(PredictorKind::NotTaken), 3);
+    assert_eq!(correct(PredictorKind::Btfn), 7);
+    // 1-bit mispredicts the first and last inner branch of both loops, 2-bit
+    // still predicts taken when the second loop starts
+    assert_eq!(correct(PredictorKind::OneBit), 4);
+    assert_eq!(correct(PredictorKind::TwoBit), 5);
+    assert_eq!(correct(PredictorKind::Btb), 4);
+
+    // predicting doesn't change the timing unless asked to
+    let cycles = run_until_retired(source, 32).statistics.cycles;
+    let state = predicted_run(source, 32, PredictorConfig { kind: PredictorKind::TwoBit, ..Default::default() });
+    assert_eq!(state.statistics.cycles, cycles);
+    assert_eq!(state.statistics.branches_taken, 7);
+}
+
+#[test]
+fn test_return_address_stack() {
+    let source = ".text
+        jal x1, function
+        jal x1, function
+        addi x3, x0, 3
+        jal x0, end
+        function: addi x2, x2, 1
+        jalr x0, x1, 0
+        end: addi x4, x0, 4";
+    let config = PredictorConfig { kind: PredictorKind::Btfn, ..Default::default() };
+
+    let state = predicted_run(source, 9, config);
+    let statistics = state.predictor.unwrap().statistics;
+    assert_eq!((statistics.jumps, statistics.returns), (5, 2));
+    assert_eq!((statistics.jumps_correct, statistics.returns_correct), (3, 0));
+
+    let state = predicted_run(source, 9, PredictorConfig { return_stack_depth: 4, ..config });
+    assert_eq!((state.x[2], state.x[3], state.x[4]), (2, 3, 4));
+    let statistics = state.predictor.unwrap().statistics;
+    assert_eq!((statistics.jumps_correct, statistics.returns_correct), (5, 2));
+}
+
+#[test]
+fn test_mispredict_penalty() {
+    let source = ".text
+        addi x2, x0, 8
+        loop: addi x1, x1, 1
+        addi x2, x2, -1
+        bne x2, x0, loop
+        addi x3, x0, 3";
+    // every taken branch pays the redirect
+    assert_eq!(run_until_retired(source, 26).statistics.cycles, 34);
+
+    // BTFN gets all but the final branch right, which pays the penalty
+    let config = PredictorConfig { kind: PredictorKind::Btfn, apply_penalty: true, ..Default::default() };
+    let state = predicted_run(source, 26, config);
+    assert_eq!(state.statistics.cycles, 29);
+    assert_eq!((state.x[1], state.x[3]), (8, 3));
+    assert_eq!((state.statistics.branches_taken, state.statistics.branches_not_taken), (7, 1));
+
+    // never predicting taken costs the penalty on every taken branch
+    let config = PredictorConfig { kind: PredictorKind::NotTaken, mispredict_penalty: 3, ..config };
+    assert_eq!(predicted_run(source, 26, config).statistics.cycles, 1 + 26 + 7 * 3);
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let source = crate::include_test_file!("uart-hello.s");
+    let mut program = crate::assembler::assemble(source).unwrap();
+    let mut emulator_state = EmulatorState::default();
+    emulator_state.config.dcache = Some(CacheConfig::default());
+    emulator_state.config.predictor = Some(PredictorConfig { kind: PredictorKind::TwoBit, ..Default::default() });
+    emulator_state.load_program(&program);
+    emulator_state.uart.rx.extend(b"ok");
+    for _ in 0..300 {
+        emulator_state = clock(&emulator_state, &mut program);
+    }
+
+    // the headless core restores a snapshot without the interface
+    let json = Snapshot::new(source, Some(&program), &emulator_state).to_json();
+    let snapshot = Snapshot::from_json(&json).unwrap();
+    assert_eq!(snapshot.source, source);
+    let mut restored_program = snapshot.program.unwrap();
+    let mut restored = snapshot.state;
+    assert_eq!(restored_program.data_memory, program.data_memory);
+    assert_eq!(restored_program.source_map, program.source_map);
+    assert_eq!(restored.statistics, emulator_state.statistics);
+    assert_eq!(restored.memory_map, emulator_state.memory_map);
+
+    // and continues exactly like the original
+    for _ in 0..700 {
+        emulator_state = clock(&emulator_state, &mut program);
+        restored = clock(&restored, &mut restored_program);
+    }
+    assert_eq!(restored.uart.tx, b"Hello from the UART!\nok");
+    assert_eq!(serde_json::to_string(&restored).unwrap(), serde_json::to_string(&emulator_state).unwrap());
+}
+
+#[test]
+fn test_snapshot_errors() {
+    assert!(Snapshot::from_json("not json").unwrap_err().starts_with("Not a snapshot"));
+
+    let json = Snapshot::new("", None, &EmulatorState::default()).to_json();
+    let newer = json.replacen("\"version\":1", "\"version\":99", 1);
+    assert!(Snapshot::from_json(&newer).unwrap_err().contains("version 99"));
+    assert!(Snapshot::from_json(&json).unwrap().program.is_none());
+}
+
+#[test]
+fn test_malformed_snapshots() {
+    let mut state = EmulatorState::default();
+    state.config.icache = Some(CacheConfig::default());
+    state.config.predictor = Some(PredictorConfig { kind: PredictorKind::OneBit, ..Default::default() });
+    state.sync_caches();
+    state.sync_predictor();
+    assert!(Snapshot::from_json(&Snapshot::new("", None, &state).to_json()).is_ok());
+
+    // each of these parses, but would panic once the emulator indexes it
+    let malformed: [fn(&mut EmulatorState); 4] = [
+        |state| state.framebuffer.pixels.truncate(10),
+        |state| state.predictor.as_mut().unwrap().history.clear(),
+        |state| state.icache.as_mut().unwrap().sets.truncate(1),
+        |state| state.icache.as_mut().unwrap().config.block_size = 0,
+    ];
+    for malform in malformed {
+        let mut malformed = state.clone();
+        malform(&mut malformed);
+        let json = Snapshot::new("", None, &malformed).to_json();
+        assert!(Snapshot::from_json(&json).unwrap_err().starts_with("Invalid snapshot"));
+    }
+}
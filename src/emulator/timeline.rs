@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::EmulatorState;
+use crate::isa::{Instruction, ISA};
+
+/// Number of clock cycles the timeline keeps
+pub const TIMELINE_LENGTH: usize = 64;
+
+/// What a pipeline stage did with an instruction during one cycle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StageActivity {
+    /// Fetched into IF and passed on to ID
+    Fetch,
+    /// Decoded and executed in ID/EX
+    Execute,
+    /// Held in its stage for another cycle
+    Stall,
+    /// Discarded by a jump, taken branch or trap
+    Flush,
+}
+
+impl StageActivity {
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            StageActivity::Fetch => "IF",
+            StageActivity::Execute => "ID",
+            StageActivity::Stall => "st",
+            StageActivity::Flush => "fl",
+        }
+    }
+}
+
+/// An instruction in a pipeline stage during one cycle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageSlot {
+    pub pc: u32,
+    pub instr: u32,
+    pub activity: StageActivity,
+}
+
+/// Pipeline occupancy during one clock cycle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleRecord {
+    pub cycle: u64,
+    pub fetch: Option<StageSlot>,
+    pub execute: Option<StageSlot>,
+}
+
+impl CycleRecord {
+    /// Compares the pipeline before and after a clock
+    pub fn new(before: &EmulatorState, after: &EmulatorState) -> CycleRecord {
+        let pipeline = &after.pipeline;
+        let trapped = pipeline.flushed;
+
+        let fetch = pipeline.fetched.then_some(StageSlot {
+            pc: pipeline.IF_pc,
+            instr: pipeline.IF,
+            activity: if trapped {
+                StageActivity::Flush
+            } else if !pipeline.IF_valid {
+                // passed on to ID
+                StageActivity::Fetch
+            } else if pipeline.datapath.instr_addr_o != pipeline.IF_pc {
+                StageActivity::Flush
+            } else {
+                StageActivity::Stall
+            },
+        });
+        let execute = (before.pipeline.ID != 0).then_some(StageSlot {
+            pc: before.pipeline.ID_pc,
+            instr: before.pipeline.ID,
+            activity: if trapped {
+                StageActivity::Flush
+            } else if before.pipeline.datapath.id_multicycle != 0 {
+                StageActivity::Stall
+            } else {
+                StageActivity::Execute
+            },
+        });
+
+        CycleRecord {
+            cycle: before.statistics.cycles,
+            fetch,
+            execute,
+        }
+    }
+}
+
+/// One dynamic instruction and what the pipeline did with it each cycle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimelineRow {
+    pub pc: u32,
+    pub instr: u32,
+    pub cells: Vec<(u64, StageActivity)>,
+}
+
+impl TimelineRow {
+    pub fn mnemonic(&self) -> String {
+        ISA::instr_to_isa(Instruction::from_raw(self.instr)).map_or("???".to_string(), |isa| format!("{isa:?}"))
+    }
+}
+
+/// Pipeline occupancy over the most recent cycles, oldest first
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PipelineTimeline {
+    pub records: VecDeque<CycleRecord>,
+}
+
+impl PipelineTimeline {
+    pub fn push(&mut self, record: CycleRecord) {
+        if self.records.len() == TIMELINE_LENGTH {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /// Groups the last `cycles` records by instruction, in program order
+    pub fn rows(&self, cycles: usize) -> Vec<TimelineRow> {
+        let mut rows: Vec<TimelineRow> = Vec::new();
+        let mut fetch_row: Option<usize> = None;
+        let mut execute_row: Option<usize> = None;
+        let mut previous: Option<&CycleRecord> = None;
+        let new_row = |rows: &mut Vec<TimelineRow>, slot: &StageSlot| {
+            rows.push(TimelineRow {
+                pc: slot.pc,
+                instr: slot.instr,
+                cells: Vec::new(),
+            });
+            rows.len() - 1
+        };
+
+        let skip = self.records.len().saturating_sub(cycles);
+        for record in self.records.iter().skip(skip) {
+            let previous_fetch = previous.and_then(|p| p.fetch);
+            let previous_execute = previous.and_then(|p| p.execute);
+
+            execute_row = record.execute.map(|slot| {
+                let same = |other: Option<StageSlot>| other.is_some_and(|o| o.pc == slot.pc && o.instr == slot.instr);
+                let row = match (execute_row, fetch_row) {
+                    (Some(row), _) if slot.activity != StageActivity::Execute && same(previous_execute) => row,
+                    (_, Some(row)) if previous_fetch.is_some_and(|f| f.activity == StageActivity::Fetch) && same(previous_fetch) => row,
+                    _ => new_row(&mut rows, &slot),
+                };
+                rows[row].cells.push((record.cycle, slot.activity));
+                row
+            });
+            fetch_row = record.fetch.map(|slot| {
+                let row = match fetch_row {
+                    Some(row)
+                        if previous_fetch.is_some_and(|f| f.activity == StageActivity::Stall && f.pc == slot.pc) =>
+                    {
+                        row
+                    }
+                    _ => new_row(&mut rows, &slot),
+                };
+                rows[row].cells.push((record.cycle, slot.activity));
+                row
+            });
+            previous = Some(record);
+        }
+        rows
+    }
+
+    /// Plain text table of the last `cycles` cycles, one row per instruction
+    pub fn to_text(&self, cycles: usize) -> String {
+        let skip = self.records.len().saturating_sub(cycles);
+        let columns: Vec<u64> = self.records.iter().skip(skip).map(|r| r.cycle).collect();
+        let mut text = format!("{:<20}", "instruction");
+        for cycle in &columns {
+            text += &format!(" {:>4}", cycle);
+        }
+        text.push('\n');
+        for row in self.rows(cycles) {
+            text += &format!("{:08x} {:<11}", row.pc, row.mnemonic());
+            for cycle in &columns {
+                let cell = row
+                    .cells
+                    .iter()
+                    .find(|(c, _)| c == cycle)
+                    .map_or(".", |(_, activity)| activity.abbreviation());
+                text += &format!(" {:>4}", cell);
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
@@ -0,0 +1,61 @@
+use super::{EmulatorState, InstructionClass};
+use crate::isa::{Instruction, InstructionDefinition, CSR};
+
+const MRET: u32 = 0x30200073;
+
+/// Writing these CSRs can enable a pending interrupt, so CVE2 flushes the
+/// pipeline after the write
+const FLUSHING_CSRS: [CSR; 2] = [CSR::MSTATUS, CSR::MIE];
+
+/// Whether a Zicsr instruction writes one of the CSRs that flush the pipeline
+fn flushes_pipeline(instr: Instruction, def: &InstructionDefinition) -> bool {
+    if def.opcode != 0b1110011 || instr.funct3() == 0 {
+        return false;
+    }
+    // CSRRS/CSRRC and their immediate forms only write with a non-zero rs1/zimm
+    let writes = instr.funct3() & 0b11 == 0b01 || instr.rs1() != 0;
+    writes
+        && FLUSHING_CSRS
+            .iter()
+            .any(|csr| csr.definition().address == instr.csr())
+}
+
+/// Cycles the handlers keep an instruction in ID/EX, not counting the cycles
+/// a load or store waits for the bus
+fn approximate_cycles(instr: Instruction, class: InstructionClass, taken: bool) -> u32 {
+    match class {
+        InstructionClass::Branch if taken => 2,
+        InstructionClass::Jump => 2,
+        // MRET redirects the fetch like a jump
+        InstructionClass::System if instr.raw() == MRET => 2,
+        _ => 1,
+    }
+}
+
+/// Cycles CVE2 keeps an instruction in ID/EX, from the pipeline details in its
+/// user manual. Without a branch target ALU a taken branch uses the ALU again
+/// in a third cycle to compute the target. Loads and stores wait for each bus
+/// response in either model, so their timing comes from the bus configuration.
+fn accurate_cycles(instr: Instruction, def: &InstructionDefinition, taken: bool) -> u32 {
+    match InstructionClass::from_definition(def) {
+        InstructionClass::Branch if taken => 3,
+        InstructionClass::Jump => 2,
+        InstructionClass::System if flushes_pipeline(instr, def) => 2,
+        InstructionClass::System if instr.raw() == MRET => 2,
+        _ => 1,
+    }
+}
+
+impl EmulatorState {
+    /// Cycles the cycle-accurate timing model holds the instruction in ID/EX
+    /// after its handler is done. `multicycle` is `id_multicycle` at the start
+    /// of the instruction's last cycle, which tells whether a branch was taken.
+    pub(super) fn extra_cycles(&self, instr: Instruction, multicycle: u32) -> u32 {
+        let Some(def) = InstructionDefinition::from_instr(instr) else {
+            return 0;
+        };
+        let class = InstructionClass::from_definition(&def);
+        let taken = multicycle != 0;
+        accurate_cycles(instr, &def, taken).saturating_sub(approximate_cycles(instr, class, taken))
+    }
+}
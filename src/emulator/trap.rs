@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+use super::EmulatorState;
+use crate::isa::CSR;
+use crate::{bitmask, bits};
+
+/// Synchronous exceptions raised while executing the instruction in ID
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Exception {
+    /// Holds the misaligned jump or branch target
+    InstructionAddressMisaligned(u32),
+    /// Holds the faulting fetch address
+    InstructionAccessFault(u32),
+    /// Holds the raw instruction bits, which are reported in mtval
+    IllegalInstruction(u32),
+    /// Holds the misaligned load address
+    LoadAddressMisaligned(u32),
+    /// Holds the faulting load address
+    LoadAccessFault(u32),
+    /// Holds the misaligned store address
+    StoreAddressMisaligned(u32),
+    /// Holds the faulting store address
+    StoreAccessFault(u32),
+    /// ECALL from machine mode, the only privilege level
+    EnvironmentCall,
+}
+
+impl Exception {
+    /// Exception code written to mcause
+    pub fn cause(&self) -> u32 {
+        match self {
+            Exception::InstructionAddressMisaligned(_) => 0,
+            Exception::InstructionAccessFault(_) => 1,
+            Exception::IllegalInstruction(_) => 2,
+            Exception::LoadAddressMisaligned(_) => 4,
+            Exception::LoadAccessFault(_) => 5,
+            Exception::StoreAddressMisaligned(_) => 6,
+            Exception::StoreAccessFault(_) => 7,
+            Exception::EnvironmentCall => 11,
+        }
+    }
+
+    /// Value written to mtval
+    pub fn tval(&self) -> u32 {
+        match self {
+            Exception::InstructionAddressMisaligned(tval)
+            | Exception::InstructionAccessFault(tval)
+            | Exception::IllegalInstruction(tval)
+            | Exception::LoadAddressMisaligned(tval)
+            | Exception::LoadAccessFault(tval)
+            | Exception::StoreAddressMisaligned(tval)
+            | Exception::StoreAccessFault(tval) => *tval,
+            Exception::EnvironmentCall => 0,
+        }
+    }
+}
+
+/// Asynchronous interrupts, in the order CVE2 prioritizes them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interrupt {
+    NonMaskable,
+    /// Fast local interrupt 0-15, the lowest index wins
+    Fast(u32),
+    External,
+    Software,
+    Timer,
+}
+
+impl Interrupt {
+    /// Interrupt code written to mcause (without the interrupt bit),
+    /// also selects the handler in vectored mode
+    pub fn code(&self) -> u32 {
+        match self {
+            Interrupt::NonMaskable => 31,
+            Interrupt::Fast(i) => 16 + i,
+            Interrupt::External => 11,
+            Interrupt::Software => 3,
+            Interrupt::Timer => 7,
+        }
+    }
+}
+
+/// Returns the interrupt to take before the instruction in ID, if any
+pub fn pending_interrupt(state: &EmulatorState) -> Option<Interrupt> {
+    let datapath = &state.pipeline.datapath;
+
+    // interrupts are only taken between instructions
+    if state.pipeline.ID == 0 || datapath.id_multicycle != 0 {
+        return None;
+    }
+
+    // the NMI ignores mstatus.MIE but doesn't nest
+    if datapath.irq_nm_i && !state.nmi_mode {
+        return Some(Interrupt::NonMaskable);
+    }
+
+    if bits!(state.read_csr(CSR::MSTATUS), 3) == 0 {
+        return None;
+    }
+
+    let pending = state.read_csr(CSR::MIP) & state.read_csr(CSR::MIE);
+    if bits!(pending, 31;16) != 0 {
+        Some(Interrupt::Fast(bits!(pending, 31;16).trailing_zeros()))
+    } else if bits!(pending, 11) != 0 {
+        Some(Interrupt::External)
+    } else if bits!(pending, 3) != 0 {
+        Some(Interrupt::Software)
+    } else if bits!(pending, 7) != 0 {
+        Some(Interrupt::Timer)
+    } else {
+        None
+    }
+}
+
+/// Saves the trap cause and return address, then redirects execution to `target`
+fn enter_trap(state: &mut EmulatorState, mcause: u32, mtval: u32, target: u32) {
+    state.write_csr(CSR::MEPC, state.pipeline.ID_pc);
+    state.write_csr(CSR::MCAUSE, mcause);
+    state.write_csr(CSR::MTVAL, mtval);
+
+    // stack MIE into MPIE and disable interrupts
+    let mstatus = state.read_csr(CSR::MSTATUS);
+    let mie = bits!(mstatus, 3);
+    state.write_csr(CSR::MSTATUS, mstatus & !(1 << 3 | 1 << 7) | mie << 7);
+
+    // squash the instruction in ID and the one fetched behind it
+    state.pipeline.ID = 0;
+    state.pipeline.ID_err = false;
+    state.pipeline.IF_valid = false;
+    state.pipeline.flushed = true;
+    state.pipeline.instr_bus.cancel();
+    state.pipeline.data_bus.cancel();
+    state.pipeline.datapath.instr_addr_o = target;
+    state.pipeline.datapath.fetch_enable_i = true;
+    state.pipeline.datapath.id_multicycle = 0;
+    state.pipeline.stall_cycles = 0;
+    state.pipeline.datapath.data_req_o = false;
+    state.pipeline.datapath.data_we_o = false;
+    state.pipeline.datapath.data_be_o = [false; 4];
+}
+
+/// Redirects execution to the trap handler for an exception raised by the instruction in ID
+pub fn take_exception(state: &mut EmulatorState, exception: Exception) {
+    // exceptions always go to the base address, even in vectored mode
+    let base = state.read_csr(CSR::MTVEC) & bitmask!(31;8);
+    enter_trap(state, exception.cause(), exception.tval(), base);
+}
+
+/// Redirects execution to the vectored handler for an interrupt, the
+/// instruction in ID hasn't executed yet and is resumed by MRET
+pub fn take_interrupt(state: &mut EmulatorState, interrupt: Interrupt) {
+    let base = state.read_csr(CSR::MTVEC) & bitmask!(31;8);
+    let code = interrupt.code();
+    enter_trap(state, 1 << 31 | code, 0, base + 4 * code);
+
+    if interrupt == Interrupt::NonMaskable {
+        state.nmi_mode = true;
+    }
+}
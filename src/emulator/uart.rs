@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::mmio::MemoryMapped;
+
+/// Default base address of the UART registers
+pub const UART_BASE: u32 = 0x1000_0000;
+const UART_SIZE: u32 = 0x10;
+
+/// Writes transmit the low byte, reads pop the oldest received byte (0 if empty)
+const DATA: u32 = 0x0;
+/// Bit 0 is set while received data is waiting, bit 1 while TX can accept data
+const STATUS: u32 = 0x4;
+
+const STATUS_RX_VALID: u32 = 1 << 0;
+const STATUS_TX_READY: u32 = 1 << 1;
+
+/// Polled UART console, the terminal panel shows `tx` and fills `rx`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Uart {
+    pub base: u32,
+    /// Every byte the program transmitted
+    pub tx: Vec<u8>,
+    /// Bytes typed into the terminal the program hasn't read yet
+    pub rx: VecDeque<u8>,
+}
+
+impl Default for Uart {
+    fn default() -> Self {
+        Self {
+            base: UART_BASE,
+            tx: Vec::new(),
+            rx: VecDeque::new(),
+        }
+    }
+}
+
+impl MemoryMapped for Uart {
+    const NAME: &'static str = "UART";
+
+    fn base(&self) -> u32 {
+        self.base
+    }
+
+    fn size(&self) -> u32 {
+        UART_SIZE
+    }
+
+    fn read(&mut self, address: u32) -> u32 {
+        match address - self.base {
+            DATA => self.rx.pop_front().unwrap_or_default() as u32,
+            STATUS => {
+                let rx_valid = if self.rx.is_empty() { 0 } else { STATUS_RX_VALID };
+                rx_valid | STATUS_TX_READY
+            }
+            _ => 0,
+        }
+    }
+
+    fn write(&mut self, address: u32, value: u32, mask: u32) {
+        // only a write covering the low byte transmits
+        if address - self.base == DATA && mask & 0xFF != 0 {
+            self.tx.push(value as u8);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::datapath::CVE2Datapath;
+
+/// Number of clock cycles the waveform keeps
+pub const WAVEFORM_LENGTH: usize = 256;
+
+/// Clock period in the VCD's 1ns timescale, signals change on the rising edge
+const CLOCK_PERIOD: u64 = 10;
+
+/// A `cve2_top` port that can be traced
+pub struct DatapathSignal {
+    pub name: &'static str,
+    pub width: u32,
+    pub value: fn(&CVE2Datapath) -> u32,
+}
+
+/// The traced ports, in the order of the `cve2_top` port list
+pub const SIGNALS: &[DatapathSignal] = &[
+    DatapathSignal { name: "instr_req_o", width: 1, value: |d| d.instr_req_o as u32 },
+    DatapathSignal { name: "instr_gnt_i", width: 1, value: |d| d.instr_gnt_i as u32 },
+    DatapathSignal { name: "instr_rvalid_i", width: 1, value: |d| d.instr_rvalid_i as u32 },
+    DatapathSignal { name: "instr_addr_o", width: 32, value: |d| d.instr_addr_o },
+    DatapathSignal { name: "instr_rdata_i", width: 32, value: |d| d.instr_rdata_i },
+    DatapathSignal { name: "instr_err_i", width: 1, value: |d| d.instr_err_i as u32 },
+    DatapathSignal { name: "data_req_o", width: 1, value: |d| d.data_req_o as u32 },
+    DatapathSignal { name: "data_gnt_i", width: 1, value: |d| d.data_gnt_i as u32 },
+    DatapathSignal { name: "data_rvalid_i", width: 1, value: |d| d.data_rvalid_i as u32 },
+    DatapathSignal { name: "data_we_o", width: 1, value: |d| d.data_we_o as u32 },
+    DatapathSignal {
+        name: "data_be_o",
+        width: 4,
+        value: |d| d.data_be_o.iter().rev().fold(0, |be, &lane| be << 1 | lane as u32),
+    },
+    DatapathSignal { name: "data_addr_o", width: 32, value: |d| d.data_addr_o },
+    DatapathSignal { name: "data_wdata_o", width: 32, value: |d| d.data_wdata_o },
+    DatapathSignal { name: "data_rdata_i", width: 32, value: |d| d.data_rdata_i },
+    DatapathSignal { name: "data_err_i", width: 1, value: |d| d.data_err_i as u32 },
+    DatapathSignal { name: "irq_software_i", width: 1, value: |d| d.irq_software_i as u32 },
+    DatapathSignal { name: "irq_timer_i", width: 1, value: |d| d.irq_timer_i as u32 },
+    DatapathSignal { name: "irq_external_i", width: 1, value: |d| d.irq_external_i as u32 },
+    DatapathSignal { name: "irq_fast_i", width: 16, value: |d| d.irq_fast_i as u32 },
+    DatapathSignal { name: "irq_nm_i", width: 1, value: |d| d.irq_nm_i as u32 },
+    DatapathSignal { name: "debug_req_i", width: 1, value: |d| d.debug_req_i as u32 },
+    DatapathSignal { name: "fetch_enable_i", width: 1, value: |d| d.fetch_enable_i as u32 },
+    DatapathSignal { name: "core_sleep_o", width: 1, value: |d| d.core_sleep_o as u32 },
+];
+
+/// The datapath ports after each of the most recent clocks, oldest first
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Waveform {
+    pub samples: VecDeque<(u64, CVE2Datapath)>,
+}
+
+impl Waveform {
+    pub fn push(&mut self, cycle: u64, datapath: CVE2Datapath) {
+        if self.samples.len() == WAVEFORM_LENGTH {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((cycle, datapath));
+    }
+
+    /// Values of a signal over the recorded cycles
+    pub fn values<'a>(&'a self, signal: &'a DatapathSignal) -> impl Iterator<Item = (u64, u32)> + 'a {
+        self.samples.iter().map(|(cycle, datapath)| (*cycle, (signal.value)(datapath)))
+    }
+
+    /// Value Change Dump of the selected signals (indices into [`SIGNALS`])
+    /// plus a generated `clk_i`, for viewers like GTKWave
+    pub fn to_vcd(&self, selected: &[usize]) -> String {
+        // identifiers are printable ASCII characters, `!` is the clock
+        let id = |i: usize| char::from(b'"' + i as u8);
+        let format_value = |signal: &DatapathSignal, value: u32, i: usize| {
+            if signal.width == 1 {
+                format!("{value}{}\n", id(i))
+            } else {
+                format!("b{value:b} {}\n", id(i))
+            }
+        };
+
+        let mut vcd = String::from("$version EmuGator $end\n$timescale 1ns $end\n$scope module cve2_top $end\n");
+        vcd += "$var wire 1 ! clk_i $end\n";
+        for &i in selected {
+            let signal = &SIGNALS[i];
+            if signal.width == 1 {
+                vcd += &format!("$var wire 1 {} {} $end\n", id(i), signal.name);
+            } else {
+                vcd += &format!("$var wire {} {} {} [{}:0] $end\n", signal.width, id(i), signal.name, signal.width - 1);
+            }
+        }
+        vcd += "$upscope $end\n$enddefinitions $end\n";
+
+        let Some((first_cycle, _)) = self.samples.front() else {
+            return vcd;
+        };
+        let mut previous: Option<&CVE2Datapath> = None;
+        for (cycle, datapath) in &self.samples {
+            let time = (cycle - first_cycle) * CLOCK_PERIOD;
+            vcd += &format!("#{time}\n");
+            if previous.is_none() {
+                vcd += "$dumpvars\n";
+            }
+            vcd += "1!\n";
+            for &i in selected {
+                let signal = &SIGNALS[i];
+                let value = (signal.value)(datapath);
+                if previous.is_none_or(|previous| (signal.value)(previous) != value) {
+                    vcd += &format_value(signal, value, i);
+                }
+            }
+            if previous.is_none() {
+                vcd += "$end\n";
+            }
+            vcd += &format!("#{}\n0!\n", time + CLOCK_PERIOD / 2);
+            previous = Some(datapath);
+        }
+        let (last_cycle, _) = self.samples.back().unwrap();
+        vcd += &format!("#{}\n", (last_cycle - first_cycle + 1) * CLOCK_PERIOD);
+        vcd
+    }
+}
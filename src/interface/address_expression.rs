@@ -0,0 +1,85 @@
+use super::value_format::parse_value;
+use crate::assembler::AssembledProgram;
+use crate::emulator::{EmulatorState, ABI_NAMES};
+
+/// Value of a register by its `x` or ABI name, or `pc` for the PC in ID
+fn register(name: &str, state: &EmulatorState) -> Option<u32> {
+    let name = name.to_lowercase();
+    let index = match name.as_str() {
+        "pc" => return Some(state.pipeline.ID_pc),
+        "fp" => 8,
+        _ => match name.strip_prefix('x').and_then(|n| n.parse::<usize>().ok()) {
+            Some(index) if index < 32 => index,
+            _ => ABI_NAMES.iter().position(|&abi| abi == name)?,
+        },
+    };
+    Some(state.x[index])
+}
+
+fn term(text: &str, state: &EmulatorState, program: Option<&AssembledProgram>) -> Result<u32, String> {
+    if text.starts_with(|c: char| c.is_ascii_digit()) {
+        return parse_value(text);
+    }
+    register(text, state)
+        .or_else(|| {
+            let program = program?;
+            program.labels.get(text).or(program.data_labels.get(text)).copied()
+        })
+        .ok_or_else(|| format!("Unknown register or label: {text}"))
+}
+
+/// Evaluates an address like `sp+16`, `buffer + 0x4` or `0x100`: numbers,
+/// registers and labels added or subtracted with wrapping arithmetic
+pub fn eval_address(
+    text: &str,
+    state: &EmulatorState,
+    program: Option<&AssembledProgram>,
+) -> Result<u32, String> {
+    let mut terms = Vec::new();
+    let mut negative = false;
+    let mut current = String::new();
+    for c in text.chars() {
+        match c {
+            '+' | '-' if !current.trim().is_empty() => {
+                terms.push((negative, current.trim().to_string()));
+                current.clear();
+                negative = c == '-';
+            }
+            '+' => (),
+            '-' => negative = !negative,
+            _ => current.push(c),
+        }
+    }
+    if current.trim().is_empty() {
+        return Err("Expected an address".to_string());
+    }
+    terms.push((negative, current.trim().to_string()));
+
+    terms.iter().try_fold(0u32, |address, (negative, text)| {
+        let value = term(text, state, program)?;
+        Ok(if *negative {
+            address.wrapping_sub(value)
+        } else {
+            address.wrapping_add(value)
+        })
+    })
+}
+
+#[test]
+fn test_eval_address() {
+    let program = crate::assembler::assemble(".data 0x100\nbuffer: .word 1\n.text\nmain: addi x1, x0, 1").unwrap();
+    let mut state = EmulatorState::default();
+    state.x[2] = 0x7FFF_FFF0;
+    state.x[10] = 8;
+
+    assert_eq!(eval_address("0x100", &state, None), Ok(0x100));
+    assert_eq!(eval_address("sp+16", &state, None), Ok(0x8000_0000));
+    assert_eq!(eval_address("sp - 0x10", &state, None), Ok(0x7FFF_FFE0));
+    assert_eq!(eval_address("x2+a0", &state, None), Ok(0x7FFF_FFF8));
+    assert_eq!(eval_address("buffer+4", &state, Some(&program)), Ok(0x104));
+    assert_eq!(eval_address("main", &state, Some(&program)), Ok(0));
+    assert_eq!(eval_address("-4", &state, None), Ok(0xFFFF_FFFC));
+
+    assert!(eval_address("sp+", &state, None).is_err());
+    assert!(eval_address("nowhere", &state, Some(&program)).is_err());
+}
@@ -0,0 +1,115 @@
+use dioxus::prelude::*;
+
+use crate::emulator::{
+    EmulatorState, BUTTON_COUNT, DIGIT_COUNT, FRAMEBUFFER_HEIGHT, FRAMEBUFFER_WIDTH, LED_COUNT,
+    SWITCH_COUNT,
+};
+
+// (x, y, width, height) of segments a-g in a 30x44 digit cell
+const SEGMENTS: [(u32, u32, u32, u32); 7] = [
+    (5, 2, 16, 3),
+    (21, 5, 3, 16),
+    (21, 23, 3, 16),
+    (5, 39, 16, 3),
+    (2, 23, 3, 16),
+    (2, 5, 3, 16),
+    (5, 21, 16, 3),
+];
+
+#[component]
+#[allow(non_snake_case)]
+pub fn BoardView(emulator_state: Signal<EmulatorState>) -> Element {
+    let gpio = emulator_state.read().gpio;
+    let pixels = emulator_state.read().framebuffer.pixels.clone();
+
+    rsx! {
+        div { class: "h-full overflow-auto pr-2 font-mono text-sm flex gap-4",
+            div { class: "flex flex-col gap-3",
+                // LEDs, the rightmost is LED 0
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "text-gray-500 text-xs mb-1", "LEDs" }
+                    div { class: "flex gap-1",
+                        for i in (0..LED_COUNT).rev() {
+                            div {
+                                class: if gpio.leds & (1 << i) != 0 { "w-4 h-4 rounded-full bg-red-500 shadow" } else { "w-4 h-4 rounded-full bg-red-950" },
+                                title: "LED {i}",
+                            }
+                        }
+                    }
+                }
+                // seven-segment displays, the rightmost is digit 0
+                div { class: "bg-gray-900 rounded shadow-sm p-2 w-fit",
+                    svg {
+                        width: "{DIGIT_COUNT * 30}",
+                        height: "44",
+                        for digit in 0..DIGIT_COUNT {
+                            {
+                                let segments = gpio.seven_segment[DIGIT_COUNT - 1 - digit];
+                                let x_offset = digit * 30;
+                                rsx! {
+                                    for (bit, (x, y, width, height)) in SEGMENTS.iter().enumerate() {
+                                        rect {
+                                            x: "{x_offset as u32 + x}",
+                                            y: "{y}",
+                                            width: "{width}",
+                                            height: "{height}",
+                                            fill: if segments & (1 << bit) != 0 { "#ef4444" } else { "#3f1d1d" },
+                                        }
+                                    }
+                                    circle {
+                                        cx: "{x_offset + 27}",
+                                        cy: "41",
+                                        r: "2",
+                                        fill: if segments & 0x80 != 0 { "#ef4444" } else { "#3f1d1d" },
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                // switches and buttons are inputs, clicking toggles them
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "text-gray-500 text-xs mb-1", "Switches" }
+                    div { class: "flex gap-1",
+                        for i in (0..SWITCH_COUNT).rev() {
+                            button {
+                                class: if gpio.switches & (1 << i) != 0 { "w-4 h-6 rounded-sm bg-blue-600 border-t-4 border-white" } else { "w-4 h-6 rounded-sm bg-gray-400 border-b-4 border-white" },
+                                title: "Switch {i}",
+                                onclick: move |_| emulator_state.write().gpio.switches ^= 1 << i,
+                            }
+                        }
+                    }
+                }
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "text-gray-500 text-xs mb-1", "Buttons" }
+                    div { class: "flex gap-2",
+                        for i in (0..BUTTON_COUNT).rev() {
+                            button {
+                                class: if gpio.buttons & (1 << i) != 0 { "w-8 h-8 rounded-full bg-gray-700 text-white text-xs shadow-inner" } else { "w-8 h-8 rounded-full bg-gray-300 text-xs shadow" },
+                                onclick: move |_| emulator_state.write().gpio.buttons ^= 1 << i,
+                                "{i}"
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "bg-white rounded shadow-sm p-2 h-fit",
+                div { class: "text-gray-500 text-xs mb-1", "Framebuffer" }
+                svg {
+                    width: "{FRAMEBUFFER_WIDTH * 4}",
+                    height: "{FRAMEBUFFER_HEIGHT * 4}",
+                    "shape-rendering": "crispEdges",
+                    for (i, pixel) in pixels.iter().enumerate() {
+                        rect {
+                            x: "{(i % FRAMEBUFFER_WIDTH) * 4}",
+                            y: "{(i / FRAMEBUFFER_WIDTH) * 4}",
+                            width: "4",
+                            height: "4",
+                            fill: "#{pixel:06x}",
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
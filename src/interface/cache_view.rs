@@ -0,0 +1,212 @@
+use dioxus::prelude::*;
+
+use crate::emulator::{CacheConfig, EmulatorState, Replacement, WritePolicy};
+
+const SIZES: [u32; 7] = [64, 128, 256, 512, 1024, 2048, 4096];
+const ASSOCIATIVITIES: [u32; 4] = [1, 2, 4, 8];
+const BLOCK_SIZES: [u32; 4] = [4, 8, 16, 32];
+
+#[component]
+#[allow(non_snake_case)]
+pub fn CacheView(emulator_state: Signal<EmulatorState>) -> Element {
+    rsx! {
+        div { class: "h-full flex gap-4 overflow-auto font-mono text-sm",
+            CachePanel { emulator_state, data: false }
+            CachePanel { emulator_state, data: true }
+        }
+    }
+}
+
+/// Configuration, statistics and set contents of the instruction or data cache.
+/// Shapes too small for a single set can't be selected.
+#[component]
+#[allow(non_snake_case)]
+fn CachePanel(mut emulator_state: Signal<EmulatorState>, data: bool) -> Element {
+    let mut configure = move |config: Option<CacheConfig>| {
+        let mut state = emulator_state.write();
+        if data {
+            state.config.dcache = config;
+        } else {
+            state.config.icache = config;
+        }
+        state.sync_caches();
+    };
+
+    let state = emulator_state.read();
+    let (title, config, cache) = if data {
+        ("Data cache", state.config.dcache, state.dcache.as_ref())
+    } else {
+        ("Instruction cache", state.config.icache, state.icache.as_ref())
+    };
+
+    rsx! {
+        div { class: "flex-1 flex flex-col gap-2 min-w-0",
+            label { class: "flex items-center gap-1 font-bold",
+                input {
+                    r#type: "checkbox",
+                    checked: config.is_some(),
+                    onchange: move |evt| configure(evt.checked().then(CacheConfig::default)),
+                }
+                "{title}"
+            }
+            if let Some(config) = config {
+                div { class: "flex flex-wrap gap-2 items-center text-xs",
+                    select {
+                        class: "bg-white rounded px-1 shadow-sm",
+                        title: "Capacity",
+                        onchange: move |evt| {
+                            if let Ok(size) = evt.value().parse() {
+                                configure(Some(CacheConfig { size, ..config }));
+                            }
+                        },
+                        for size in SIZES {
+                            option {
+                                value: "{size}",
+                                selected: size == config.size,
+                                disabled: !CacheConfig { size, ..config }.is_valid(),
+                                "{size} B"
+                            }
+                        }
+                    }
+                    select {
+                        class: "bg-white rounded px-1 shadow-sm",
+                        title: "Associativity",
+                        onchange: move |evt| {
+                            if let Ok(associativity) = evt.value().parse() {
+                                configure(Some(CacheConfig { associativity, ..config }));
+                            }
+                        },
+                        for associativity in ASSOCIATIVITIES {
+                            option {
+                                value: "{associativity}",
+                                selected: associativity == config.associativity,
+                                disabled: !CacheConfig { associativity, ..config }.is_valid(),
+                                if associativity == 1 { "direct-mapped" } else { "{associativity}-way" }
+                            }
+                        }
+                    }
+                    select {
+                        class: "bg-white rounded px-1 shadow-sm",
+                        title: "Block size",
+                        onchange: move |evt| {
+                            if let Ok(block_size) = evt.value().parse() {
+                                configure(Some(CacheConfig { block_size, ..config }));
+                            }
+                        },
+                        for block_size in BLOCK_SIZES {
+                            option {
+                                value: "{block_size}",
+                                selected: block_size == config.block_size,
+                                disabled: !CacheConfig { block_size, ..config }.is_valid(),
+                                "{block_size} B blocks"
+                            }
+                        }
+                    }
+                    select {
+                        class: "bg-white rounded px-1 shadow-sm",
+                        title: "Replacement policy",
+                        onchange: move |evt| {
+                            let replacement = match evt.value().as_str() {
+                                "fifo" => Replacement::Fifo,
+                                "random" => Replacement::Random,
+                                _ => Replacement::Lru,
+                            };
+                            configure(Some(CacheConfig { replacement, ..config }));
+                        },
+                        option { value: "lru", selected: config.replacement == Replacement::Lru, "LRU" }
+                        option { value: "fifo", selected: config.replacement == Replacement::Fifo, "FIFO" }
+                        option { value: "random", selected: config.replacement == Replacement::Random, "Random" }
+                    }
+                    if data {
+                        select {
+                            class: "bg-white rounded px-1 shadow-sm",
+                            title: "Write policy",
+                            onchange: move |evt| {
+                                let write_policy = if evt.value() == "through" { WritePolicy::WriteThrough } else { WritePolicy::WriteBack };
+                                configure(Some(CacheConfig { write_policy, ..config }));
+                            },
+                            option { value: "back", selected: config.write_policy == WritePolicy::WriteBack, "write-back" }
+                            option { value: "through", selected: config.write_policy == WritePolicy::WriteThrough, "write-through" }
+                        }
+                    }
+                    label { class: "flex items-center gap-1",
+                        "Hit latency"
+                        input {
+                            class: "bg-white rounded px-1 w-12",
+                            r#type: "number",
+                            min: "1",
+                            value: "{config.hit_latency}",
+                            onchange: move |evt| {
+                                if let Ok(hit_latency) = evt.value().parse::<u32>() {
+                                    configure(Some(CacheConfig { hit_latency: hit_latency.max(1), ..config }));
+                                }
+                            },
+                        }
+                    }
+                }
+                if let Some(cache) = cache {
+                    {
+                        let statistics = cache.statistics;
+                        let hit_rate = statistics
+                            .hit_rate()
+                            .map_or("-".to_string(), |rate| format!("{:.1}%", rate * 100.0));
+                        let last = cache.last_access;
+                        rsx! {
+                            div { class: "flex gap-4 text-xs",
+                                span { "hits {statistics.hits}" }
+                                span { "misses {statistics.misses}" }
+                                span { "hit rate {hit_rate}" }
+                                if data {
+                                    span { "writebacks {statistics.writebacks}" }
+                                }
+                            }
+                            div { class: "overflow-auto",
+                                table { class: "bg-white rounded shadow-sm text-xs",
+                                    thead {
+                                        tr { class: "text-left text-gray-500 border-b border-gray-200",
+                                            th { class: "p-1", "Set" }
+                                            for way in 0..config.associativity {
+                                                th { class: "p-1", "Way {way}" }
+                                            }
+                                        }
+                                    }
+                                    tbody {
+                                        for (set, lines) in cache.sets.iter().enumerate() {
+                                            tr { class: "border-b border-gray-100",
+                                                td { class: "p-1 text-gray-500", "{set}" }
+                                                for (way, line) in lines.iter().enumerate() {
+                                                    {
+                                                        let highlight = match last {
+                                                            Some(access) if access.set == set && access.way == Some(way) => {
+                                                                if access.hit { "bg-green-200" } else { "bg-red-200" }
+                                                            }
+                                                            _ => "",
+                                                        };
+                                                        // the block address, rebuilt from the tag and set
+                                                        let block = (line.tag * config.sets() + set as u32) * config.block_size;
+                                                        rsx! {
+                                                            td { class: "p-1 {highlight}",
+                                                                if line.valid {
+                                                                    span { title: "tag {line.tag:#x}", "{block:#010x}" }
+                                                                    if line.dirty {
+                                                                        span { class: "text-red-600 font-bold", title: "dirty", " D" }
+                                                                    }
+                                                                } else {
+                                                                    span { class: "text-gray-400", "-" }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
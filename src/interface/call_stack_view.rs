@@ -0,0 +1,65 @@
+use dioxus::prelude::*;
+
+use crate::assembler::AssembledProgram;
+use crate::emulator::{EmulatorState, ABI_NAMES};
+
+#[component]
+#[allow(non_snake_case)]
+pub fn CallStackView(
+    emulator_state: Signal<EmulatorState>,
+    assembled_program: Signal<Option<AssembledProgram>>,
+) -> Element {
+    let state = emulator_state.read();
+    let program = assembled_program.read();
+    let Some(program) = program.as_ref() else {
+        return rsx! {
+            div { class: "flex justify-center items-center h-full",
+                span { class: "text-gray-500 font-mono", "No program loaded" }
+            }
+        };
+    };
+
+    let name = |address: u32| program.symbolize(address).unwrap_or(format!("{address:#010x}"));
+    let word = |address: u32| {
+        let bytes: [u8; 4] = std::array::from_fn(|i| {
+            program.data_memory.get(&(address + i as u32)).copied().unwrap_or_default()
+        });
+        u32::from_le_bytes(bytes)
+    };
+
+    // innermost frame first, like a debugger's backtrace
+    let regions = state.call_stack.regions(state.x[2]);
+    let frames: Vec<_> = state.call_stack.frames.iter().zip(regions).enumerate().rev().collect();
+    let depth = frames.len();
+
+    rsx! {
+        div { class: "h-full overflow-auto pr-2 flex flex-col gap-2",
+            if frames.is_empty() {
+                span { class: "text-gray-500 font-mono", "No calls tracked" }
+            }
+            for (i, (frame, (low, high))) in frames {
+                div { class: "bg-white rounded shadow-sm p-2 font-mono text-sm",
+                    div { class: "flex justify-between",
+                        span { class: "font-bold", "#{depth - 1 - i} {name(frame.target)}" }
+                        span { class: "text-gray-500",
+                            match frame.call_site {
+                                Some(call_site) => format!("called from {}", name(call_site)),
+                                None => "entry point".to_string(),
+                            }
+                        }
+                    }
+                    div { class: "text-xs text-gray-500",
+                        "stack {low:#010x}..{high:#010x} ({high - low} bytes), returns to {name(frame.return_address)}"
+                    }
+                    for (address, register) in frame.saved.iter() {
+                        div { class: "text-xs pl-4",
+                            span { class: "text-gray-500", "{address:#010x} " }
+                            span { class: "font-bold", "{ABI_NAMES[*register]}" }
+                            " = {word(*address):#010x}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
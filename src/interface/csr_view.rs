@@ -0,0 +1,49 @@
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::emulator::EmulatorState;
+use crate::isa::CSR;
+
+#[component]
+#[allow(non_snake_case)]
+pub fn CsrView(emulator_state: Signal<EmulatorState>) -> Element {
+    let state = emulator_state.read();
+
+    rsx! {
+        div { class: "h-full overflow-auto pr-2",
+            table { class: "w-full bg-white rounded shadow-sm font-mono text-sm",
+                thead {
+                    tr { class: "text-left text-gray-500 text-xs border-b border-gray-200",
+                        th { class: "p-1", "CSR" }
+                        th { class: "p-1", "Address" }
+                        th { class: "p-1", "Value" }
+                        th { class: "p-1", "Access" }
+                    }
+                }
+                tbody {
+                    for csr in CSR::iter() {
+                        {
+                            let def = csr.definition();
+                            let value = state.read_csr(csr);
+                            // only CSRs written since reset are stored, the rest read their reset value
+                            let written = state.csr.contains_key(&csr.alias_of().unwrap_or(csr).definition().address);
+                            let access = match csr.alias_of() {
+                                Some(alias) => format!("RO, shadows {}", alias.definition().name),
+                                None if csr.is_read_only() => "RO".to_string(),
+                                None => "RW".to_string(),
+                            };
+                            rsx! {
+                                tr { class: "border-b border-gray-100",
+                                    td { class: "p-1 font-bold", "{def.name}" }
+                                    td { class: "p-1 text-gray-500", "{def.address:#05x}" }
+                                    td { class: if written { "p-1" } else { "p-1 text-gray-400" }, "{value:#010x}" }
+                                    td { class: "p-1 text-gray-500", "{access}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
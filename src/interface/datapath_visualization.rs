@@ -1,13 +1,562 @@
-use crate::emulator::EmulatorState;
+use crate::emulator::{EmulatorState, InstructionClass};
+use crate::isa::{Instruction, CSR, InstructionDefinition, InstructionFormat, ISA};
 use dioxus::prelude::*;
 
+/// A functional unit of the CVE2 core drawn as a box
+struct Unit {
+    name: &'static str,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    /// Detail shown under the name, like the PC of the stage
+    detail: String,
+    active: bool,
+}
+
+/// A signal between two units, `points` is the SVG polyline
+struct Wire {
+    name: &'static str,
+    points: &'static str,
+    value: String,
+    active: bool,
+}
+
+/// What the instruction in ID/EX uses of the datapath
+#[derive(Default)]
+struct Usage {
+    decoded: bool,
+    reads_rs1: bool,
+    reads_rs2: bool,
+    immediate: bool,
+    pc_operand: bool,
+    alu: bool,
+    lsu: bool,
+    store: bool,
+    csr: bool,
+    redirect: bool,
+    writeback: bool,
+}
+
+impl Usage {
+    fn of(state: &EmulatorState) -> Usage {
+        let instr = Instruction::from_raw(state.pipeline.ID);
+        let Some(def) = InstructionDefinition::from_instr(instr) else {
+            return Usage::default();
+        };
+        let class = InstructionClass::from_definition(&def);
+        let csr_op = def.opcode == 0b1110011 && instr.funct3() != 0;
+        let format = def.format;
+        Usage {
+            decoded: true,
+            // the CSRR*I instructions carry an immediate in the rs1 field
+            reads_rs1: matches!(format, InstructionFormat::R | InstructionFormat::S | InstructionFormat::B)
+                || (format == InstructionFormat::I && def.opcode != 0b1110011)
+                || (csr_op && instr.funct3() < 0b100),
+            reads_rs2: matches!(format, InstructionFormat::R | InstructionFormat::S | InstructionFormat::B),
+            immediate: format != InstructionFormat::R && def.opcode != 0b1110011,
+            pc_operand: matches!(def.opcode, 0b0010111 | 0b1101111) || class == InstructionClass::Branch,
+            alu: class != InstructionClass::System,
+            lsu: matches!(class, InstructionClass::Load | InstructionClass::Store),
+            store: class == InstructionClass::Store,
+            csr: csr_op,
+            // a taken branch stalls the fetch for its second cycle
+            redirect: class == InstructionClass::Jump
+                || (class == InstructionClass::Branch && state.pipeline.datapath.id_multicycle != 0),
+            writeback: instr.rd() != 0
+                && !matches!(format, InstructionFormat::S | InstructionFormat::B)
+                && (class != InstructionClass::System || csr_op),
+        }
+    }
+}
+
+fn units(state: &EmulatorState, usage: &Usage) -> Vec<Unit> {
+    let pipeline = &state.pipeline;
+    let datapath = &pipeline.datapath;
+    let instr = Instruction::from_raw(pipeline.ID);
+    let mnemonic = ISA::instr_to_isa(instr).map_or("-".to_string(), |isa| format!("{isa:?}"));
+    let unit = |name, (x, y, width, height), detail: String, active| Unit {
+        name,
+        x,
+        y,
+        width,
+        height,
+        detail,
+        active,
+    };
+    vec![
+        unit("Instr Mem", (10, 100, 80, 90), String::new(), datapath.instr_req_o),
+        unit("IF", (120, 100, 90, 90), format!("{:08x}", pipeline.IF_pc), datapath.instr_req_o),
+        unit("Decoder", (290, 30, 100, 50), mnemonic, usage.decoded),
+        unit(
+            "Register File",
+            (290, 110, 100, 100),
+            if usage.writeback { format!("rd = x{}", instr.rd()) } else { String::new() },
+            usage.reads_rs1 || usage.reads_rs2 || usage.writeback,
+        ),
+        unit(
+            "CSR",
+            (290, 240, 100, 45),
+            if usage.csr { format!("{:#05x}", instr.csr()) } else { String::new() },
+            usage.csr,
+        ),
+        unit("ALU", (450, 100, 70, 90), String::new(), usage.alu),
+        unit("LSU", (570, 100, 70, 90), String::new(), usage.lsu),
+        unit("Data Mem", (680, 100, 70, 90), String::new(), datapath.data_req_o),
+    ]
+}
+
+fn wires(state: &EmulatorState, usage: &Usage) -> Vec<Wire> {
+    let pipeline = &state.pipeline;
+    let datapath = &pipeline.datapath;
+    let instr = Instruction::from_raw(pipeline.ID);
+    let immediate = if usage.decoded { instr.immediate().ok() } else { None };
+    let rs1 = state.x[instr.rs1() as usize];
+    let target = |base: u32| immediate.map(|imm| base.wrapping_add_signed(imm));
+    let hex = |value: Option<u32>| value.map_or("-".to_string(), |value| format!("{value:#010x}"));
+    // a load has its data once the memory responded to its request
+    let responded = datapath.id_multicycle != 0 && datapath.data_rvalid_i;
+    let load_data = responded.then_some(datapath.data_rdata_i);
+    // the values belong to the instruction in ID, not the previous clock
+    let result = if usage.decoded { writeback_value(state, instr, immediate) } else { None };
+    let flag = |value: bool| value as u8;
+    let byte_enable: String = datapath.data_be_o.iter().rev().map(|&b| if b { '1' } else { '0' }).collect();
+    let irqs = [
+        ("software", datapath.irq_software_i),
+        ("timer", datapath.irq_timer_i),
+        ("external", datapath.irq_external_i),
+        ("nm", datapath.irq_nm_i),
+    ];
+    let wire = |name, points, value: String, active| Wire {
+        name,
+        points,
+        value,
+        active,
+    };
+    vec![
+        wire(
+            "instr_addr_o",
+            "120,115 90,115",
+            format!("{:#010x}", datapath.instr_addr_o),
+            datapath.instr_req_o,
+        ),
+        wire(
+            "instr bus handshake",
+            "120,145 90,145",
+            format!(
+                "req={} gnt={} rvalid={} err={}",
+                flag(datapath.instr_req_o),
+                flag(datapath.instr_gnt_i),
+                flag(datapath.instr_rvalid_i),
+                flag(datapath.instr_err_i)
+            ),
+            datapath.instr_req_o,
+        ),
+        wire(
+            "instr_rdata_i",
+            "90,175 120,175",
+            format!("{:#010x}", datapath.instr_rdata_i),
+            datapath.instr_rvalid_i,
+        ),
+        wire(
+            "IF",
+            "210,145 240,145",
+            format!("{:#010x} @ {:#010x}", pipeline.IF, pipeline.IF_pc),
+            datapath.fetch_enable_i,
+        ),
+        wire(
+            "ID",
+            "256,55 290,55",
+            format!("{:#010x} @ {:#010x}", pipeline.ID, pipeline.ID_pc),
+            usage.decoded,
+        ),
+        wire(
+            "register indices",
+            "340,80 340,110",
+            format!("rs1=x{} rs2=x{} rd=x{}", instr.rs1(), instr.rs2(), instr.rd()),
+            usage.reads_rs1 || usage.reads_rs2 || usage.writeback,
+        ),
+        wire(
+            "immediate",
+            "390,45 485,45 485,100",
+            immediate.map_or("-".to_string(), |imm| format!("{imm} ({:#x})", imm as u32)),
+            usage.immediate,
+        ),
+        wire(
+            "ID_pc",
+            "256,95 430,95 430,115 450,115",
+            format!("{:#010x}", pipeline.ID_pc),
+            usage.pc_operand,
+        ),
+        wire(
+            "rs1 data",
+            "390,135 450,135",
+            format!("{rs1:#010x}"),
+            usage.reads_rs1 && !usage.csr,
+        ),
+        wire(
+            "rs2 data",
+            "390,170 450,170",
+            format!("{:#010x}", state.x[instr.rs2() as usize]),
+            usage.reads_rs2,
+        ),
+        wire(
+            "store data",
+            "420,170 420,205 605,205 605,190",
+            format!("{:#010x}", state.x[instr.rs2() as usize]),
+            usage.store,
+        ),
+        wire(
+            "address",
+            "520,145 570,145",
+            hex(target(rs1)),
+            usage.lsu,
+        ),
+        wire(
+            "jump target",
+            "520,120 545,120 545,15 165,15 165,100",
+            // JALR clears the lowest bit of its target
+            if instr.opcode() == 0b1100111 {
+                hex(target(rs1).map(|target| target & !1))
+            } else {
+                hex(target(pipeline.ID_pc))
+            },
+            usage.redirect,
+        ),
+        wire(
+            "data_addr_o",
+            "640,115 680,115",
+            format!("{:#010x}", datapath.data_addr_o),
+            datapath.data_req_o,
+        ),
+        wire(
+            "data_wdata_o",
+            "640,135 680,135",
+            format!("{:#010x}", datapath.data_wdata_o),
+            datapath.data_req_o && datapath.data_we_o,
+        ),
+        wire(
+            "data bus handshake",
+            "640,155 680,155",
+            format!(
+                "req={} we={} be={byte_enable} gnt={} rvalid={} err={}",
+                flag(datapath.data_req_o),
+                flag(datapath.data_we_o),
+                flag(datapath.data_gnt_i),
+                flag(datapath.data_rvalid_i),
+                flag(datapath.data_err_i)
+            ),
+            datapath.data_req_o,
+        ),
+        wire(
+            "data_rdata_i",
+            "680,175 640,175",
+            format!("{:#010x}", datapath.data_rdata_i),
+            datapath.data_rvalid_i && !datapath.data_we_o,
+        ),
+        wire(
+            "csr operand",
+            "340,210 340,240",
+            if instr.funct3() < 0b100 {
+                format!("{rs1:#010x}")
+            } else {
+                format!("zimm = {}", instr.rs1())
+            },
+            usage.csr,
+        ),
+        wire(
+            "interrupts",
+            "200,262 290,262",
+            irqs.iter()
+                .map(|(name, level)| format!("{name}={}", flag(*level)))
+                .chain([format!("fast={:#06x}", datapath.irq_fast_i)])
+                .collect::<Vec<_>>()
+                .join(" "),
+            irqs.iter().any(|(_, level)| *level) || datapath.irq_fast_i != 0,
+        ),
+        wire(
+            "ALU result",
+            "520,180 540,180 540,310 440,310",
+            hex(result),
+            usage.writeback && usage.alu && !usage.lsu,
+        ),
+        wire(
+            "load data",
+            "625,190 625,310 540,310",
+            hex(load_data),
+            usage.writeback && usage.lsu,
+        ),
+        wire(
+            "csr read data",
+            "390,262 440,262 440,310",
+            hex(result),
+            usage.writeback && usage.csr,
+        ),
+        wire(
+            "rd write data",
+            "440,310 270,310 270,190 290,190",
+            format!("x{} = {}", instr.rd(), hex(result)),
+            usage.writeback,
+        ),
+    ]
+}
+
+/// What the decoded instruction in ID writes to rd, worked out from its fields
+/// and the register file instead of running its handler, which would trap or
+/// redirect the fetch. `None` while a load waits for the last of its data.
+fn writeback_value(state: &EmulatorState, instr: Instruction, immediate: Option<i32>) -> Option<u32> {
+    let pipeline = &state.pipeline;
+    let datapath = &pipeline.datapath;
+    let rs1 = state.x[instr.rs1() as usize];
+    let rs2 = state.x[instr.rs2() as usize];
+    // a stalled instruction already wrote its result
+    if pipeline.stall_cycles > 0 {
+        return Some(state.x[instr.rd() as usize]);
+    }
+    match instr.opcode() {
+        0b0110111 => immediate.map(|imm| imm as u32),
+        0b0010111 => immediate.map(|imm| pipeline.ID_pc.wrapping_add_signed(imm)),
+        0b1101111 | 0b1100111 => Some(pipeline.ID_pc.wrapping_add(4)),
+        // bit 30 only selects SRAI among the immediate operations
+        0b0010011 => immediate.map(|imm| {
+            let alternate = instr.funct3() == 0b101 && instr.raw() & (1 << 30) != 0;
+            alu(instr.funct3(), alternate, rs1, imm as u32)
+        }),
+        0b0110011 => Some(alu(instr.funct3(), instr.raw() & (1 << 30) != 0, rs1, rs2)),
+        0b0000011 => {
+            let (size, signed) = match instr.funct3() {
+                0b000 => (1, true),
+                0b001 => (2, true),
+                0b100 => (1, false),
+                0b101 => (2, false),
+                _ => (4, false),
+            };
+            let offset = rs1.wrapping_add_signed(immediate?) & 0b11;
+            let split = offset + size > 4;
+            let last = datapath.id_multicycle == 2 || (datapath.id_multicycle == 1 && !split);
+            if !(last && datapath.data_rvalid_i) {
+                return None;
+            }
+            // move the addressed lanes down to the low bytes
+            let rdata = if split {
+                pipeline.LSU_rdata >> (8 * offset) | datapath.data_rdata_i << (8 * (4 - offset))
+            } else {
+                datapath.data_rdata_i >> (8 * offset)
+            };
+            let unused = 32 - 8 * size;
+            Some(if signed { ((rdata << unused) as i32 >> unused) as u32 } else { rdata << unused >> unused })
+        }
+        0b1110011 => CSR::from_address(instr.csr()).map(|csr| state.read_csr(csr)),
+        _ => None,
+    }
+}
+
+/// Result of the ALU for the OP and OP-IMM `funct3`, `alternate` selects SUB
+/// and the arithmetic shift
+fn alu(funct3: u8, alternate: bool, a: u32, b: u32) -> u32 {
+    match funct3 {
+        0b000 if alternate => a.wrapping_sub(b),
+        0b000 => a.wrapping_add(b),
+        0b001 => a << (b & 0x1F),
+        0b010 => ((a as i32) < (b as i32)) as u32,
+        0b011 => (a < b) as u32,
+        0b100 => a ^ b,
+        0b101 if alternate => ((a as i32) >> (b & 0x1F)) as u32,
+        0b101 => a >> (b & 0x1F),
+        0b110 => a | b,
+        _ => a & b,
+    }
+}
+
 #[component]
 #[allow(non_snake_case)]
 pub fn DatapathVisualization(emulator_state: Signal<EmulatorState>) -> Element {
-    let pipeline = emulator_state.read().pipeline;
+    let mut hovered = use_signal(|| None::<(&'static str, String)>);
+    let state = emulator_state.read();
+    let usage = Usage::of(&state);
+    let units = units(&state, &usage);
+    let wires = wires(&state, &usage);
+
     rsx! {
-        div { class: "w-full h-full overflow-y-scroll",
-            div { class: "mb-4 font-mono text-sm whitespace-pre", "{pipeline:#?}" }
+        div { class: "w-full h-full overflow-y-auto flex flex-col gap-2",
+            div { class: "font-mono text-sm h-5",
+                match hovered() {
+                    Some((name, value)) => rsx! {
+                        span { class: "font-bold", "{name}: " }
+                        span { "{value}" }
+                    },
+                    None => rsx! {
+                        span { class: "text-gray-500", "Hover a wire to see its value" }
+                    },
+                }
+            }
+            svg { class: "w-full", view_box: "0 0 760 330",
+                // pipeline register between the stages
+                rect { x: "240", y: "25", width: "16", height: "270", fill: "#9ca3af" }
+                for unit in units {
+                    rect {
+                        x: "{unit.x}",
+                        y: "{unit.y}",
+                        width: "{unit.width}",
+                        height: "{unit.height}",
+                        rx: "4",
+                        fill: if unit.active { "#dbeafe" } else { "#f3f4f6" },
+                        stroke: if unit.active { "#2563eb" } else { "#9ca3af" },
+                    }
+                    text {
+                        x: "{unit.x + unit.width / 2}",
+                        y: "{unit.y + 20}",
+                        text_anchor: "middle",
+                        font_size: "12",
+                        font_weight: "bold",
+                        "{unit.name}"
+                    }
+                    text {
+                        x: "{unit.x + unit.width / 2}",
+                        y: "{unit.y + 36}",
+                        text_anchor: "middle",
+                        font_size: "10",
+                        font_family: "monospace",
+                        "{unit.detail}"
+                    }
+                }
+                for wire in wires {
+                    g {
+                        onmouseenter: {
+                            let name = wire.name;
+                            let value = wire.value.clone();
+                            move |_| hovered.set(Some((name, value.clone())))
+                        },
+                        onmouseleave: move |_| hovered.set(None),
+                        polyline {
+                            points: wire.points,
+                            fill: "none",
+                            stroke: if wire.active { "#2563eb" } else { "#d1d5db" },
+                            stroke_width: if wire.active { "3" } else { "2" },
+                        }
+                        // wider invisible stroke so thin wires are easy to hover
+                        polyline {
+                            points: wire.points,
+                            fill: "none",
+                            stroke: "transparent",
+                            stroke_width: "10",
+                        }
+                    }
+                }
+            }
         }
     }
 }
+
+#[test]
+fn test_active_paths() {
+    let wires = |raw: u32, setup: fn(&mut EmulatorState)| {
+        let mut state = EmulatorState::default();
+        state.pipeline.ID = raw;
+        setup(&mut state);
+        let usage = Usage::of(&state);
+        wires(&state, &usage)
+    };
+    let active = |wires: &[Wire]| {
+        wires.iter().filter(|wire| wire.active).map(|wire| wire.name).collect::<Vec<_>>()
+    };
+    let value = |wires: &[Wire], name| {
+        wires.iter().find(|wire| wire.name == name).unwrap().value.clone()
+    };
+
+    // add x1, x2, x3
+    let add = wires(0x003100B3, |state| {
+        state.x[1] = 1;
+        state.x[2] = 5;
+        state.x[3] = 7;
+    });
+    assert!(active(&add).contains(&"rs2 data"));
+    assert!(active(&add).contains(&"ALU result"));
+    assert!(!active(&add).contains(&"address"));
+    assert!(!active(&add).contains(&"immediate"));
+    // the result of this add, not the old x1
+    assert_eq!(value(&add, "ALU result"), "0x0000000c");
+    assert_eq!(value(&add, "rd write data"), "x1 = 0x0000000c");
+
+    // lw x5, 0(x10)
+    let load = wires(0x00052283, |state| state.x[10] = 0x100);
+    assert!(active(&load).contains(&"address"));
+    assert!(active(&load).contains(&"load data"));
+    assert!(!active(&load).contains(&"rs2 data"));
+    assert!(!active(&load).contains(&"ALU result"));
+    assert_eq!(value(&load, "address"), "0x00000100");
+    // the request goes out this cycle, the data comes back in a later one
+    assert_eq!(value(&load, "load data"), "-");
+    assert_eq!(value(&load, "rd write data"), "x5 = -");
+    let response = wires(0x00052283, |state| {
+        state.x[10] = 0x100;
+        state.pipeline.datapath.id_multicycle = 1;
+        state.pipeline.datapath.data_rvalid_i = true;
+        state.pipeline.datapath.data_rdata_i = 0x1234;
+    });
+    assert_eq!(value(&response, "load data"), "0x00001234");
+    assert_eq!(value(&response, "rd write data"), "x5 = 0x00001234");
+
+    // sw x5, 4(x10)
+    let store = wires(0x00552223, |state| state.x[10] = 0x100);
+    assert!(active(&store).contains(&"store data"));
+    assert!(!active(&store).contains(&"rd write data"));
+    assert_eq!(value(&store, "address"), "0x00000104");
+
+    // jal x1, 8
+    let jump = wires(0x008000EF, |state| state.pipeline.ID_pc = 0x40);
+    assert!(active(&jump).contains(&"jump target"));
+    assert_eq!(value(&jump, "jump target"), "0x00000048");
+    assert_eq!(value(&jump, "rd write data"), "x1 = 0x00000044");
+
+    // jalr x0, 5(x6)
+    let jump = wires(0x00530067, |state| state.x[6] = 0x100);
+    assert_eq!(value(&jump, "jump target"), "0x00000104");
+
+    // csrrw x1, mscratch, x2
+    let csr = wires(0x340110F3, |state| state.write_csr(crate::isa::CSR::MSCRATCH, 0x55));
+    assert!(active(&csr).contains(&"csr operand"));
+    assert!(active(&csr).contains(&"csr read data"));
+    assert!(!active(&csr).contains(&"rs1 data"));
+    assert_eq!(value(&csr, "csr read data"), "0x00000055");
+}
+
+#[test]
+fn test_undecoded_instructions() {
+    // after reset, on a bubble or a flush ID holds zeros; an illegal
+    // instruction stays in ID until it traps
+    for raw in [0, 0xFFFFFFFF] {
+        let mut state = EmulatorState::default();
+        state.pipeline.ID = raw;
+        let usage = Usage::of(&state);
+        assert!(!usage.decoded);
+        assert!(units(&state, &usage).iter().all(|unit| unit.name != "Decoder" || !unit.active));
+        let wires = wires(&state, &usage);
+        assert_eq!(wires.iter().find(|wire| wire.name == "immediate").unwrap().value, "-");
+    }
+}
+
+#[test]
+fn test_values_without_handlers() {
+    // ecall, ebreak, mret, pause, a jump and a taken branch to a
+    // misaligned target: their handlers would trap or redirect the fetch
+    for raw in [0x00000073, 0x00100073, 0x30200073, 0x0100000F, 0x006000EF, 0x00000363] {
+        for multicycle in [0, 1] {
+            let mut state = EmulatorState::default();
+            state.pipeline.ID = raw;
+            state.pipeline.datapath.id_multicycle = multicycle;
+            let usage = Usage::of(&state);
+            assert!(usage.decoded);
+            units(&state, &usage);
+            wires(&state, &usage);
+        }
+    }
+
+    // srai x1, x2, 4
+    let mut state = EmulatorState::default();
+    state.pipeline.ID = 0x40415093;
+    state.x[2] = 0xFFFFFF00;
+    let usage = Usage::of(&state);
+    let wires = wires(&state, &usage);
+    assert_eq!(wires.iter().find(|wire| wire.name == "ALU result").unwrap().value, "0xfffffff0");
+}
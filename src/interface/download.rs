@@ -0,0 +1,39 @@
+use dioxus::prelude::*;
+use dioxus_logger::tracing::error;
+
+/// Saves `contents` as a file through the browser's download prompt
+pub fn download(filename: &str, mime: &str, contents: &str) {
+    let eval = document::eval(
+        r#"
+        const [filename, mime, contents] = await dioxus.recv();
+        const url = URL.createObjectURL(new Blob([contents], { type: mime }));
+        const link = document.createElement("a");
+        link.href = url;
+        link.download = filename;
+        link.click();
+        URL.revokeObjectURL(url);
+        "#,
+    );
+    if let Err(e) = eval.send((filename, mime, contents)) {
+        error!("Failed to download {filename}: {e:?}");
+    }
+}
+
+/// Saves the rendered SVG element with the given id as an image
+pub fn download_svg(filename: &str, element_id: &str) {
+    let eval = document::eval(
+        r#"
+        const [filename, id] = await dioxus.recv();
+        const svg = new XMLSerializer().serializeToString(document.getElementById(id));
+        const url = URL.createObjectURL(new Blob([svg], { type: "image/svg+xml" }));
+        const link = document.createElement("a");
+        link.href = url;
+        link.download = filename;
+        link.click();
+        URL.revokeObjectURL(url);
+        "#,
+    );
+    if let Err(e) = eval.send((filename, element_id)) {
+        error!("Failed to download {filename}: {e:?}");
+    }
+}
@@ -0,0 +1,54 @@
+use dioxus::prelude::*;
+
+use super::value_format::parse_value;
+
+/// Shows `display`, clicking it opens an input for a new value which is
+/// passed to `onchange` when Enter is pressed. Escape cancels.
+#[component]
+#[allow(non_snake_case)]
+pub fn EditableValue(display: String, class: String, onchange: EventHandler<u32>) -> Element {
+    let mut editing = use_signal(|| false);
+    let mut text = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    if !editing() {
+        return rsx! {
+            div {
+                class: "{class} cursor-text hover:bg-gray-100 rounded",
+                title: "Click to edit (hex 0x.., decimal, -signed or 'ASCII')",
+                onclick: move |_| {
+                    text.set(display.clone());
+                    error.set(None);
+                    editing.set(true);
+                },
+                "{display}"
+            }
+        };
+    }
+
+    rsx! {
+        div {
+            input {
+                class: "{class} w-full bg-yellow-50 rounded px-1 outline outline-1 outline-blue-500",
+                value: "{text}",
+                autofocus: true,
+                oninput: move |evt| text.set(evt.value()),
+                onkeydown: move |evt| match evt.key() {
+                    Key::Enter => match parse_value(&text.read()) {
+                        Ok(value) => {
+                            onchange.call(value);
+                            editing.set(false);
+                        }
+                        Err(e) => error.set(Some(e)),
+                    },
+                    Key::Escape => editing.set(false),
+                    _ => (),
+                },
+                onfocusout: move |_| editing.set(false),
+            }
+            if let Some(e) = error() {
+                div { class: "text-xs text-red-600", "{e}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use dioxus::prelude::*;
+use dioxus_logger::tracing::error;
+
+use super::{download::download, permalink::ShareButton};
+use crate::emulator::EmulatorConfig;
+
+/// Asks with the browser's confirmation dialog, `false` without a window
+fn confirm(message: &str) -> bool {
+    web_sys::window().and_then(|window| window.confirm_with_message(message).ok()).unwrap_or(false)
+}
+
+/// Opens assembly files from disk, saves the source back to one and shares
+/// it as a link. Opening a file over modified source asks first.
+#[component]
+#[allow(non_snake_case)]
+pub fn FileMenu(
+    mut source: Signal<String>,
+    mut file_name: Signal<String>,
+    mut modified: Signal<bool>,
+    config: ReadOnlySignal<EmulatorConfig>,
+) -> Element {
+    // the source as last opened or saved, editing away from it modifies it
+    let mut unmodified = use_signal(|| source.peek().clone());
+    use_effect(move || {
+        if *source.read() != *unmodified.peek() {
+            modified.set(true);
+        }
+    });
+
+    rsx! {
+        div { class: "flex items-center gap-2 mb-2",
+            label {
+                class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded cursor-pointer",
+                title: "Open an assembly file, replacing the source",
+                "Open"
+                input {
+                    class: "hidden",
+                    r#type: "file",
+                    accept: ".s,.S,.asm,text/plain",
+                    onchange: move |evt| async move {
+                        let Some(files) = evt.files() else {
+                            return;
+                        };
+                        if let Some(file) = files.files().into_iter().next() {
+                            let discard = format!("Discard the changes to {file_name} and open {file}?");
+                            if modified() && !confirm(&discard) {
+                                return;
+                            }
+                            match files.read_file_to_string(&file).await {
+                                Some(contents) => {
+                                    unmodified.set(contents.clone());
+                                    source.set(contents);
+                                    file_name.set(file);
+                                    modified.set(false);
+                                }
+                                None => error!("Failed to read {file}"),
+                            }
+                        }
+                    },
+                }
+            }
+            button {
+                class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded",
+                title: "Download the source",
+                onclick: move |_| {
+                    download(&file_name.read(), "text/plain", &source.read());
+                    unmodified.set(source());
+                    modified.set(false);
+                },
+                "Save"
+            }
+            ShareButton { source, config }
+            span {
+                class: "text-gray-300 font-mono text-sm",
+                title: "Autosaved in this browser",
+                "{file_name}"
+                if modified() {
+                    " (modified)"
+                }
+            }
+        }
+    }
+}
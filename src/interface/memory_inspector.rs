@@ -0,0 +1,186 @@
+use dioxus::prelude::*;
+
+use super::address_expression::eval_address;
+use super::editable_value::EditableValue;
+use super::value_format::MemoryFormat;
+use crate::assembler::{AssembledProgram, Section};
+use crate::emulator::EmulatorState;
+
+const BYTES_PER_ROW: u32 = 16;
+const ROWS: u32 = 16;
+
+/// Reads a byte the way a load would, data memory before the instructions
+fn peek(program: &AssembledProgram, address: u32) -> Option<u8> {
+    program
+        .data_memory
+        .get(&address)
+        .or_else(|| program.instruction_memory.get(&address))
+        .copied()
+}
+
+#[component]
+#[allow(non_snake_case)]
+pub fn MemoryInspector(
+    mut assembled_program: Signal<Option<AssembledProgram>>,
+    mut emulator_state: Signal<EmulatorState>,
+) -> Element {
+    let mut base = use_signal(|| {
+        assembled_program
+            .peek()
+            .as_ref()
+            .map_or(0, |program| program.get_section_start(Section::Data))
+    });
+    let mut target = use_signal(|| None::<u32>);
+    let mut goto = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut format = use_signal(|| MemoryFormat::Words);
+    let mut follow_sp = use_signal(|| false);
+
+    let program = assembled_program.read();
+    let Some(program) = program.as_ref() else {
+        return rsx! {
+            div { class: "flex justify-center items-center h-full",
+                span { class: "text-gray-500 font-mono", "No program loaded" }
+            }
+        };
+    };
+
+    let state = emulator_state.read();
+    let sp = state.x[2];
+    let written = state.written_bytes();
+    let start = if follow_sp() { sp } else { base() } & !(BYTES_PER_ROW - 1);
+    let size = format().size();
+    let editable = size == 4 && format() != MemoryFormat::Float;
+
+    let row_labels = |row: u32| {
+        let mut labels: Vec<_> = program
+            .labels
+            .iter()
+            .chain(program.data_labels.iter())
+            .filter(|(_, &address)| address.wrapping_sub(row) < BYTES_PER_ROW)
+            .map(|(label, _)| label.as_str())
+            .collect();
+        labels.sort();
+        labels.join(", ")
+    };
+
+    rsx! {
+        div { class: "h-full flex flex-col font-mono text-sm",
+            div { class: "flex gap-2 items-center mb-2 flex-shrink-0",
+                input {
+                    class: "bg-white rounded px-2 w-40 shadow-sm",
+                    placeholder: "address, label, sp+16",
+                    value: "{goto}",
+                    oninput: move |evt| goto.set(evt.value()),
+                    onchange: move |_| {
+                        let result = eval_address(
+                            &goto.read(),
+                            &emulator_state.read(),
+                            assembled_program.read().as_ref(),
+                        );
+                        match result {
+                            Ok(address) => {
+                                base.set(address);
+                                target.set(Some(address));
+                                follow_sp.set(false);
+                                error.set(None);
+                            }
+                            Err(e) => error.set(Some(e)),
+                        }
+                    },
+                }
+                button {
+                    class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded",
+                    onclick: move |_| {
+                        base.set(start.wrapping_sub(BYTES_PER_ROW * ROWS));
+                        follow_sp.set(false);
+                    },
+                    "◀"
+                }
+                button {
+                    class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded",
+                    onclick: move |_| {
+                        base.set(start.wrapping_add(BYTES_PER_ROW * ROWS));
+                        follow_sp.set(false);
+                    },
+                    "▶"
+                }
+                select {
+                    class: "bg-white rounded px-1 shadow-sm",
+                    onchange: move |evt| {
+                        if let Some(selected) = MemoryFormat::ALL.iter().find(|f| f.name() == evt.value()) {
+                            format.set(*selected);
+                        }
+                    },
+                    for option_format in MemoryFormat::ALL {
+                        option {
+                            value: option_format.name(),
+                            selected: option_format == format(),
+                            "{option_format.name()}"
+                        }
+                    }
+                }
+                label { class: "flex items-center gap-1",
+                    input {
+                        r#type: "checkbox",
+                        checked: follow_sp(),
+                        onchange: move |evt| follow_sp.set(evt.checked()),
+                    }
+                    "Follow sp"
+                }
+                if let Some(e) = error() {
+                    span { class: "text-xs text-red-600", "{e}" }
+                }
+            }
+            div { class: "flex-grow overflow-auto pr-2",
+                table { class: "bg-white rounded shadow-sm w-full",
+                    tbody {
+                        for row in (0..ROWS).map(|i| start.wrapping_add(i * BYTES_PER_ROW)) {
+                            tr { class: "border-b border-gray-100",
+                                td { class: "px-1 text-gray-500 text-xs", "{row:08x}" }
+                                for address in (0..BYTES_PER_ROW).step_by(size as usize).map(|i| row.wrapping_add(i)) {
+                                    {
+                                        let bytes: Vec<Option<u8>> =
+                                            (0..size).map(|i| peek(program, address.wrapping_add(i))).collect();
+                                        let value = bytes
+                                            .iter()
+                                            .rev()
+                                            .fold(0u32, |value, byte| value << 8 | byte.unwrap_or_default() as u32);
+                                        let is_written = (0..size).any(|i| written.contains(&address.wrapping_add(i)));
+                                        let is_target = target().is_some_and(|t| t.wrapping_sub(address) < size);
+                                        let class = format!(
+                                            "px-1 text-right {} {} {}",
+                                            if bytes.iter().all(Option::is_none) { "text-gray-400" } else { "" },
+                                            if is_written { "bg-yellow-200" } else { "" },
+                                            if address == sp { "outline outline-1 outline-blue-500" } else if is_target { "outline outline-1 outline-gray-500" } else { "" },
+                                        );
+                                        let display = format().format(value);
+                                        rsx! {
+                                            td { class: "{class}",
+                                                title: if address == sp { "sp" } else { "" },
+                                                if editable {
+                                                    EditableValue {
+                                                        class: "",
+                                                        display,
+                                                        onchange: move |value| {
+                                                            if let Some(program) = assembled_program.write().as_mut() {
+                                                                emulator_state.write().edit_memory(program, address, value);
+                                                            }
+                                                        },
+                                                    }
+                                                } else {
+                                                    "{display}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                td { class: "px-1 text-xs text-blue-700 whitespace-nowrap", "{row_labels(row)}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
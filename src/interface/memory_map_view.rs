@@ -0,0 +1,160 @@
+use dioxus::prelude::*;
+
+use super::editable_value::EditableValue;
+use crate::emulator::{EmulatorState, LoaderConfig, RegionKind};
+
+/// A loader setting: its label, current value and how to change it
+type LoaderField = (&'static str, u32, fn(&mut LoaderConfig, u32));
+
+#[component]
+#[allow(non_snake_case)]
+pub fn MemoryMapView(mut emulator_state: Signal<EmulatorState>) -> Element {
+    let mut loader_error = use_signal(|| None::<String>);
+    let regions = emulator_state.read().memory_map.regions.clone();
+    let bus = emulator_state.read().config.bus.clone();
+    let loader = emulator_state.read().config.loader.clone();
+    // reassembling can grow the program into a layout that was fine before
+    let error = loader_error().or_else(|| emulator_state.read().memory_map.check_loader_regions().err());
+    let loader_fields: [LoaderField; 4] = [
+        ("Stack top", loader.stack_top, |loader, value| loader.stack_top = value),
+        ("Stack size", loader.stack_size, |loader, value| loader.stack_size = value),
+        ("Heap size", loader.heap_size, |loader, value| loader.heap_size = value),
+        ("Halt address", loader.halt_address, |loader, value| loader.halt_address = value),
+    ];
+
+    rsx! {
+        div { class: "h-full overflow-auto pr-2",
+            div { class: "flex gap-4 mb-2 font-mono text-xs",
+                label { class: "flex items-center gap-1",
+                    "Random fetch stalls %"
+                    input {
+                        class: "bg-white rounded px-1 w-12",
+                        r#type: "number",
+                        min: "0",
+                        max: "100",
+                        value: "{bus.instr_stall_percent}",
+                        onchange: move |evt| {
+                            if let Ok(percent) = evt.value().parse::<u32>() {
+                                emulator_state.write().config.bus.instr_stall_percent = percent.min(100);
+                            }
+                        },
+                    }
+                }
+                label { class: "flex items-center gap-1",
+                    "Random load/store stalls %"
+                    input {
+                        class: "bg-white rounded px-1 w-12",
+                        r#type: "number",
+                        min: "0",
+                        max: "100",
+                        value: "{bus.data_stall_percent}",
+                        onchange: move |evt| {
+                            if let Ok(percent) = evt.value().parse::<u32>() {
+                                emulator_state.write().config.bus.data_stall_percent = percent.min(100);
+                            }
+                        },
+                    }
+                }
+            }
+            // sp and ra start from these when the program is assembled
+            div { class: "flex gap-4 mb-2 font-mono text-xs",
+                for (name, value, set) in loader_fields {
+                    div { class: "flex items-center gap-1",
+                        "{name}"
+                        EditableValue {
+                            display: format!("{value:#010x}"),
+                            class: "bg-white px-1 w-24",
+                            onchange: move |value| {
+                                let mut loader = emulator_state.read().config.loader.clone();
+                                set(&mut loader, value);
+                                loader_error.set(emulator_state.write().set_loader(loader).err());
+                            },
+                        }
+                    }
+                }
+            }
+            if let Some(error) = error {
+                div { class: "mb-2 font-mono text-xs text-red-600", "{error}" }
+            }
+            if regions.is_empty() {
+                div { class: "flex justify-center items-center",
+                    span { class: "text-gray-500 font-mono", "No memory map, all accesses are allowed" }
+                }
+            } else {
+                table { class: "w-full bg-white rounded shadow-sm font-mono text-sm",
+                    thead {
+                        tr { class: "text-left text-gray-500 text-xs border-b border-gray-200",
+                            th { class: "p-1", "Region" }
+                            th { class: "p-1", "Kind" }
+                            th { class: "p-1", "Start" }
+                            th { class: "p-1", "End" }
+                            th { class: "p-1", "Size" }
+                            th { class: "p-1", "Perm" }
+                            th { class: "p-1", title: "Cycles a request to this kind of region waits for its grant", "Wait" }
+                            th { class: "p-1", title: "Cycles from the grant until the response", "Latency" }
+                        }
+                    }
+                    tbody {
+                        for region in regions {
+                            {
+                                let end = region.base.wrapping_add(region.size).wrapping_sub(1);
+                                let permissions = format!(
+                                    "{}{}{}",
+                                    if region.permissions.read { "r" } else { "-" },
+                                    if region.permissions.write { "w" } else { "-" },
+                                    if region.permissions.execute { "x" } else { "-" },
+                                );
+                                let kind = match region.kind {
+                                    RegionKind::Text => "text",
+                                    RegionKind::Data => "data",
+                                    RegionKind::Heap => "heap",
+                                    RegionKind::Stack => "stack",
+                                    RegionKind::Mmio => "mmio",
+                                };
+                                let timing = bus.timing(Some(region.kind));
+                                rsx! {
+                                    tr { class: "border-b border-gray-100",
+                                        td { class: "p-1 font-bold", "{region.name}" }
+                                        td { class: "p-1 text-gray-500", "{kind}" }
+                                        td { class: "p-1", "{region.base:#010x}" }
+                                        td { class: "p-1", "{end:#010x}" }
+                                        td { class: "p-1", "{region.size:#x}" }
+                                        td { class: "p-1", "{permissions}" }
+                                        td { class: "p-1",
+                                            input {
+                                                class: "bg-gray-100 rounded px-1 w-12",
+                                                r#type: "number",
+                                                min: "0",
+                                                value: "{timing.wait_states}",
+                                                onchange: move |evt| {
+                                                    if let Ok(wait_states) = evt.value().parse() {
+                                                        let regions = &mut emulator_state.write().config.bus.regions;
+                                                        regions.entry(region.kind).or_default().wait_states = wait_states;
+                                                    }
+                                                },
+                                            }
+                                        }
+                                        td { class: "p-1",
+                                            input {
+                                                class: "bg-gray-100 rounded px-1 w-12",
+                                                r#type: "number",
+                                                min: "1",
+                                                value: "{timing.latency}",
+                                                onchange: move |evt| {
+                                                    if let Ok(latency) = evt.value().parse::<u32>() {
+                                                        let regions = &mut emulator_state.write().config.bus.regions;
+                                                        regions.entry(region.kind).or_default().latency = latency.max(1);
+                                                    }
+                                                },
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
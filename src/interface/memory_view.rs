@@ -1,5 +1,6 @@
-use super::data_views::DataView;
 use super::instruction_views::InstructionView;
+use super::memory_inspector::MemoryInspector;
+use super::memory_map_view::MemoryMapView;
 use crate::assembler::AssembledProgram;
 use crate::emulator::EmulatorState;
 use dioxus::prelude::*;
@@ -8,6 +9,7 @@ use dioxus::prelude::*;
 pub enum MemoryViewType {
     Instruction,
     Data,
+    MemoryMap,
 }
 
 #[component]
@@ -33,7 +35,14 @@ pub fn MemoryView(
                     class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
                     style: if *view_type.read() == MemoryViewType::Data { "text-decoration: underline" } else { "" },
                     onclick: move |_| view_type.set(MemoryViewType::Data),
-                    "Data Memory"
+                    "Memory Inspector"
+                }
+                span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                button {
+                    class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                    style: if *view_type.read() == MemoryViewType::MemoryMap { "text-decoration: underline" } else { "" },
+                    onclick: move |_| view_type.set(MemoryViewType::MemoryMap),
+                    "Memory Map"
                 }
             }
 
@@ -46,8 +55,14 @@ pub fn MemoryView(
                         }
                     },
                     MemoryViewType::Data => rsx! {
-                        DataView {
-                            assembled_program: assembled_program
+                        MemoryInspector {
+                            assembled_program: assembled_program,
+                            emulator_state: emulator_state
+                        }
+                    },
+                    MemoryViewType::MemoryMap => rsx! {
+                        MemoryMapView {
+                            emulator_state: emulator_state
                         }
                     }
                 }
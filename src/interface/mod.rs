@@ -1,16 +1,38 @@
-mod data_views;
+mod address_expression;
+mod board_view;
+mod cache_view;
+mod call_stack_view;
+mod csr_view;
 mod datapath_visualization;
+mod download;
+mod editable_value;
+mod file_menu;
 mod instruction_views;
+mod memory_inspector;
+mod memory_map_view;
 mod memory_view;
+mod permalink;
+mod pipeline_view;
+mod predictor_view;
 mod register_view;
 mod run_buttons;
+mod snapshot_buttons;
+mod statistics_view;
+mod storage;
+mod terminal_view;
+mod value_format;
+mod waveform_view;
 
 use dioxus::prelude::*;
 use dioxus_logger::tracing::info;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use self::{
-    datapath_visualization::DatapathVisualization, memory_view::MemoryView,
-    register_view::RegisterView, run_buttons::RunButtons,
+    board_view::BoardView, cache_view::CacheView, call_stack_view::CallStackView, csr_view::CsrView,
+    datapath_visualization::DatapathVisualization, file_menu::FileMenu, memory_view::MemoryView,
+    permalink::Permalink, pipeline_view::PipelineView, register_view::RegisterView, run_buttons::RunButtons, statistics_view::StatisticsView,
+    storage::Settings, terminal_view::TerminalView, waveform_view::WaveformView,
 };
 use crate::{
     assembler::AssembledProgram,
@@ -19,12 +41,98 @@ use crate::{
     include_test_file,
 };
 
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum ProcessorViewType {
+    Datapath,
+    Pipeline,
+    Waveform,
+    Cache,
+    Statistics,
+    Terminal,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum StateViewType {
+    Registers,
+    Csrs,
+    Board,
+    CallStack,
+}
+
+#[derive(Routable, Clone, PartialEq)]
+pub enum Route {
+    /// The fragment holds a shared program, if any, so links open without a server
+    #[route("/#:permalink")]
+    App { permalink: Permalink },
+}
+
 #[component]
 #[allow(non_snake_case)]
-pub fn App() -> Element {
-    let source = use_signal(|| include_test_file!("prototype-demo.s").to_string());
+pub fn App(permalink: Permalink) -> Element {
+    // restore the last session's source and settings from local storage,
+    // unless the page was opened from a shared link
+    let settings = use_hook(Settings::load);
+    let shared = use_hook(|| (!permalink.is_empty()).then_some(permalink));
+    let source = use_signal(|| match &shared {
+        Some(shared) => shared.source.clone(),
+        None => storage::load_source().unwrap_or_else(|| include_test_file!("prototype-demo.s").to_string()),
+    });
+    let file_name = use_signal(|| match shared {
+        Some(_) => permalink::SHARED_FILE_NAME.to_string(),
+        None => settings.file_name.clone(),
+    });
+    let modified = use_signal(|| shared.is_none() && settings.modified);
     let assembled_program: Signal<Option<AssembledProgram>> = use_signal(|| None);
-    let emulator_state: Signal<EmulatorState> = use_signal(|| EmulatorState::default());
+    let emulator_state: Signal<EmulatorState> = use_signal(|| EmulatorState {
+        config: shared
+            .as_ref()
+            .and_then(|shared| shared.config.clone())
+            .unwrap_or_else(|| settings.config.clone()),
+        ..Default::default()
+    });
+    let mut processor_view = use_signal(|| settings.processor_view);
+    let mut state_view = use_signal(|| settings.state_view);
+
+    // edits to the shared program are autosaved, drop it from the address bar
+    // so reloading doesn't discard them
+    let opened_shared = shared.is_some();
+    use_effect(move || {
+        if opened_shared {
+            navigator().replace(Route::App {
+                permalink: Permalink::default(),
+            });
+        }
+    });
+
+    // a shared program replaces the autosaved one, its name and configuration
+    // only once it is edited, until then reloading restores the last session
+    let mut autosave = use_signal(|| shared.is_none());
+    let shared_source = shared.as_ref().map(|shared| shared.source.clone());
+    use_effect(move || {
+        let source = source.read();
+        if !*autosave.peek() && shared_source.as_ref() != Some(&*source) {
+            autosave.set(true);
+        }
+        if *autosave.peek() {
+            storage::save_source(&source);
+        }
+    });
+    let config = use_memo(move || emulator_state.read().config.clone());
+    use_effect(move || {
+        let (file_name, modified, config) = if autosave() {
+            (file_name(), modified(), config())
+        } else {
+            (settings.file_name.clone(), settings.modified, settings.config.clone())
+        };
+        Settings {
+            file_name,
+            modified,
+            processor_view: processor_view(),
+            state_view: state_view(),
+            config,
+        }
+        .save()
+    });
 
     use_effect(move || {
         info!("source changed");
@@ -50,6 +158,7 @@ pub fn App() -> Element {
             line_highlights.write().push(LineHighlight {
                 line,
                 css_class: "id-pc-decoration",
+                hover_message: None,
             });
         }
 
@@ -57,6 +166,22 @@ pub fn App() -> Element {
             line_highlights.write().push(LineHighlight {
                 line,
                 css_class: "if-pc-decoration",
+                hover_message: None,
+            });
+        }
+
+        // calling convention violations, one highlight per line listing all of them
+        let mut warnings = BTreeMap::<usize, Vec<String>>::new();
+        for violation in emulator_state.read().checker.violations.iter() {
+            if let Some(line) = get_pc_line(violation.pc, &assembled_program) {
+                warnings.entry(line).or_default().push(violation.kind.to_string());
+            }
+        }
+        for (line, messages) in warnings {
+            line_highlights.write().push(LineHighlight {
+                line,
+                css_class: "warning-decoration",
+                hover_message: Some(messages.join("\n\n")),
             });
         }
     });
@@ -66,17 +191,126 @@ pub fn App() -> Element {
 
         div { class: "flex h-screen w-full",
             div { class: "w-1/2 p-4 flex flex-col h-full bg-[#1E1E1E]",
+                FileMenu { source, file_name, modified, config }
                 RunButtons { source, assembled_program, emulator_state }
                 div { class: "flex-grow",
                     CodeEditor { source, line_highlights }
                 }
             }
             div { class: "w-1/2 flex flex-col",
-                div { class: "h-1/3 bg-gray-200 p-4",
-                    DatapathVisualization { emulator_state }
+                div { class: "h-1/3 bg-gray-200 p-4 flex flex-col overflow-hidden",
+                    div { class: "flex gap-4 mb-2 flex-shrink-0",
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *processor_view.read() == ProcessorViewType::Datapath { "text-decoration: underline" } else { "" },
+                            onclick: move |_| processor_view.set(ProcessorViewType::Datapath),
+                            "Datapath"
+                        }
+                        span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *processor_view.read() == ProcessorViewType::Pipeline { "text-decoration: underline" } else { "" },
+                            onclick: move |_| processor_view.set(ProcessorViewType::Pipeline),
+                            "Pipeline"
+                        }
+                        span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *processor_view.read() == ProcessorViewType::Waveform { "text-decoration: underline" } else { "" },
+                            onclick: move |_| processor_view.set(ProcessorViewType::Waveform),
+                            "Waveform"
+                        }
+                        span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *processor_view.read() == ProcessorViewType::Cache { "text-decoration: underline" } else { "" },
+                            onclick: move |_| processor_view.set(ProcessorViewType::Cache),
+                            "Cache"
+                        }
+                        span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *processor_view.read() == ProcessorViewType::Statistics { "text-decoration: underline" } else { "" },
+                            onclick: move |_| processor_view.set(ProcessorViewType::Statistics),
+                            "Statistics"
+                        }
+                        span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *processor_view.read() == ProcessorViewType::Terminal { "text-decoration: underline" } else { "" },
+                            onclick: move |_| processor_view.set(ProcessorViewType::Terminal),
+                            "Terminal"
+                        }
+                    }
+                    div { class: "flex-grow overflow-hidden",
+                        match *processor_view.read() {
+                            ProcessorViewType::Datapath => rsx! {
+                                DatapathVisualization { emulator_state }
+                            },
+                            ProcessorViewType::Pipeline => rsx! {
+                                PipelineView { emulator_state }
+                            },
+                            ProcessorViewType::Waveform => rsx! {
+                                WaveformView { emulator_state }
+                            },
+                            ProcessorViewType::Cache => rsx! {
+                                CacheView { emulator_state }
+                            },
+                            ProcessorViewType::Statistics => rsx! {
+                                StatisticsView { emulator_state }
+                            },
+                            ProcessorViewType::Terminal => rsx! {
+                                TerminalView { emulator_state }
+                            },
+                        }
+                    }
                 }
-                div { class: "h-1/3 bg-gray-300 p-4",
-                    RegisterView { emulator_state }
+                div { class: "h-1/3 bg-gray-300 p-4 flex flex-col overflow-hidden",
+                    div { class: "flex gap-4 mb-2 flex-shrink-0",
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *state_view.read() == StateViewType::Registers { "text-decoration: underline" } else { "" },
+                            onclick: move |_| state_view.set(StateViewType::Registers),
+                            "Registers"
+                        }
+                        span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *state_view.read() == StateViewType::Csrs { "text-decoration: underline" } else { "" },
+                            onclick: move |_| state_view.set(StateViewType::Csrs),
+                            "CSRs"
+                        }
+                        span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *state_view.read() == StateViewType::Board { "text-decoration: underline" } else { "" },
+                            onclick: move |_| state_view.set(StateViewType::Board),
+                            "Board"
+                        }
+                        span { class: "text-lg font-mono font-bold text-gray-900", "/" }
+                        button {
+                            class: "text-lg font-mono font-bold text-gray-900 hover:text-gray-700 transition-colors",
+                            style: if *state_view.read() == StateViewType::CallStack { "text-decoration: underline" } else { "" },
+                            onclick: move |_| state_view.set(StateViewType::CallStack),
+                            "Call Stack"
+                        }
+                    }
+                    div { class: "flex-grow overflow-hidden",
+                        match *state_view.read() {
+                            StateViewType::Registers => rsx! {
+                                RegisterView { emulator_state, assembled_program }
+                            },
+                            StateViewType::Csrs => rsx! {
+                                CsrView { emulator_state }
+                            },
+                            StateViewType::Board => rsx! {
+                                BoardView { emulator_state }
+                            },
+                            StateViewType::CallStack => rsx! {
+                                CallStackView { emulator_state, assembled_program }
+                            },
+                        }
+                    }
                 }
                 div { class: "h-1/3 bg-gray-400 p-4",
                     MemoryView { assembled_program, emulator_state }
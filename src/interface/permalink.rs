@@ -0,0 +1,146 @@
+use std::{fmt, str::FromStr};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use dioxus::prelude::*;
+use dioxus_logger::tracing::error;
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+use serde::{Deserialize, Serialize};
+
+use crate::emulator::EmulatorConfig;
+
+/// Prefix of the encoded fragment, bumped whenever the encoding changes
+const PERMALINK_VERSION: &str = "1";
+
+/// Largest source a permalink may inflate to, so a crafted link can't exhaust memory
+const MAX_SOURCE_SIZE: usize = 1 << 20;
+
+/// Name a shared program is saved under
+pub const SHARED_FILE_NAME: &str = "shared.s";
+
+/// A program shared as a link, encoded into the URL fragment so opening it
+/// needs no server. The default, an empty fragment, shares nothing.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Permalink {
+    pub source: String,
+    /// Run configuration to open the program with, the saved one if `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<EmulatorConfig>,
+}
+
+impl Permalink {
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+}
+
+/// `<version>.<base64url(deflate(json))>`, the empty string for an empty link
+impl fmt::Display for Permalink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let json = serde_json::to_vec(self).map_err(|_| fmt::Error)?;
+        let encoded = URL_SAFE_NO_PAD.encode(compress_to_vec(&json, 9));
+        write!(f, "{PERMALINK_VERSION}.{encoded}")
+    }
+}
+
+impl FromStr for Permalink {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Permalink::default());
+        }
+        let Some((version, encoded)) = s.split_once('.') else {
+            return Err("Not a permalink".to_string());
+        };
+        if version != PERMALINK_VERSION {
+            return Err(format!(
+                "Permalink version {version} isn't supported, expected version {PERMALINK_VERSION}"
+            ));
+        }
+        let compressed = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|e| format!("Invalid permalink: {e}"))?;
+        let json = decompress_to_vec_with_limit(&compressed, MAX_SOURCE_SIZE)
+            .map_err(|e| format!("Invalid permalink: {e}"))?;
+        serde_json::from_slice(&json).map_err(|e| format!("Invalid permalink: {e}"))
+    }
+}
+
+/// Copies a link to this page with the permalink as its fragment
+fn copy_link(permalink: &Permalink) {
+    let eval = document::eval(
+        r#"
+        const fragment = await dioxus.recv();
+        const url = new URL(window.location.href);
+        url.hash = fragment;
+        await navigator.clipboard.writeText(url.href);
+        "#,
+    );
+    if let Err(e) = eval.send(permalink.to_string()) {
+        error!("Failed to copy the link: {e:?}");
+    }
+}
+
+/// Copies a link that opens the source, and optionally the run configuration
+#[component]
+#[allow(non_snake_case)]
+pub fn ShareButton(source: Signal<String>, config: ReadOnlySignal<EmulatorConfig>) -> Element {
+    let mut with_config = use_signal(|| false);
+    rsx! {
+        button {
+            class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded",
+            title: "Copy a link that opens this program",
+            onclick: move |_| {
+                copy_link(&Permalink {
+                    source: source(),
+                    config: with_config().then(|| config.read().clone()),
+                })
+            },
+            "Share"
+        }
+        label {
+            class: "flex items-center gap-1 text-gray-300 text-xs",
+            title: "Include the run configuration in the link",
+            input {
+                r#type: "checkbox",
+                checked: with_config(),
+                onchange: move |evt| with_config.set(evt.checked()),
+            }
+            "with config"
+        }
+    }
+}
+
+#[test]
+fn test_permalink_round_trip() {
+    let mut permalink = Permalink {
+        source: "addi x1, x0, 5\n".repeat(100),
+        config: None,
+    };
+    let encoded = permalink.to_string();
+    assert!(encoded.starts_with("1."));
+    // repetitive sources compress well below their own length
+    assert!(encoded.len() < permalink.source.len() / 4);
+    assert_eq!(encoded.parse::<Permalink>().unwrap(), permalink);
+
+    permalink.config = Some(EmulatorConfig {
+        check_calling_convention: true,
+        ..Default::default()
+    });
+    assert_eq!(permalink.to_string().parse::<Permalink>().unwrap(), permalink);
+
+    assert_eq!(Permalink::default().to_string(), "");
+    assert_eq!("".parse::<Permalink>().unwrap(), Permalink::default());
+}
+
+#[test]
+fn test_permalink_errors() {
+    assert_eq!("garbage".parse::<Permalink>().unwrap_err(), "Not a permalink");
+    assert!("2.abc".parse::<Permalink>().unwrap_err().contains("version 2"));
+    assert!("1.!!!".parse::<Permalink>().unwrap_err().starts_with("Invalid permalink"));
+    let not_deflated = URL_SAFE_NO_PAD.encode(b"source");
+    assert!(format!("1.{not_deflated}").parse::<Permalink>().is_err());
+}
@@ -0,0 +1,120 @@
+use dioxus::prelude::*;
+
+use super::download::{download, download_svg};
+use crate::emulator::{EmulatorState, StageActivity, TIMELINE_LENGTH};
+
+const LABEL_WIDTH: u64 = 130;
+const CELL_WIDTH: u64 = 26;
+const ROW_HEIGHT: u64 = 18;
+const HEADER_HEIGHT: u64 = 18;
+const CHART_ID: &str = "pipeline-timeline";
+
+fn fill(activity: StageActivity) -> &'static str {
+    match activity {
+        StageActivity::Fetch => "#bbf7d0",
+        StageActivity::Execute => "#bfdbfe",
+        StageActivity::Stall => "#fde68a",
+        StageActivity::Flush => "#fecaca",
+    }
+}
+
+#[component]
+#[allow(non_snake_case)]
+pub fn PipelineView(emulator_state: Signal<EmulatorState>) -> Element {
+    let mut cycles = use_signal(|| 16usize);
+    let state = emulator_state.read();
+    let timeline = &state.timeline;
+    let rows = timeline.rows(cycles());
+    let first_cycle = timeline
+        .records
+        .get(timeline.records.len().saturating_sub(cycles()))
+        .map_or(0, |record| record.cycle);
+    let columns = timeline.records.len().min(cycles()) as u64;
+    let width = LABEL_WIDTH + columns * CELL_WIDTH;
+    let height = HEADER_HEIGHT + rows.len() as u64 * ROW_HEIGHT;
+
+    rsx! {
+        div { class: "h-full flex flex-col font-mono text-sm",
+            div { class: "flex gap-2 items-center mb-2 flex-shrink-0",
+                select {
+                    class: "bg-white rounded px-1 shadow-sm",
+                    onchange: move |evt| {
+                        if let Ok(selected) = evt.value().parse() {
+                            cycles.set(selected);
+                        }
+                    },
+                    for option_cycles in [8, 16, 32, TIMELINE_LENGTH] {
+                        option {
+                            value: "{option_cycles}",
+                            selected: option_cycles == cycles(),
+                            "last {option_cycles} cycles"
+                        }
+                    }
+                }
+                button {
+                    class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded",
+                    onclick: move |_| download("pipeline.txt", "text/plain", &emulator_state.read().timeline.to_text(cycles())),
+                    "Export text"
+                }
+                button {
+                    class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded",
+                    onclick: move |_| download_svg("pipeline.svg", CHART_ID),
+                    "Export image"
+                }
+                for activity in [StageActivity::Fetch, StageActivity::Execute, StageActivity::Stall, StageActivity::Flush] {
+                    span {
+                        class: "px-1 rounded text-xs",
+                        style: "background-color: {fill(activity)}",
+                        "{activity.abbreviation()} = {activity:?}"
+                    }
+                }
+            }
+            div { class: "flex-grow overflow-auto",
+                if rows.is_empty() {
+                    span { class: "text-gray-500", "Clock the processor to fill the timeline" }
+                } else {
+                    svg {
+                        id: CHART_ID,
+                        xmlns: "http://www.w3.org/2000/svg",
+                        width: "{width}",
+                        height: "{height}",
+                        font_family: "monospace",
+                        font_size: "11",
+                        rect { width: "{width}", height: "{height}", fill: "white" }
+                        for column in 0..columns {
+                            text {
+                                x: "{LABEL_WIDTH + column * CELL_WIDTH + CELL_WIDTH / 2}",
+                                y: "{HEADER_HEIGHT - 5}",
+                                text_anchor: "middle",
+                                fill: "#6b7280",
+                                "{first_cycle + column}"
+                            }
+                        }
+                        for (i, row) in rows.iter().enumerate() {
+                            text {
+                                x: "2",
+                                y: "{HEADER_HEIGHT + i as u64 * ROW_HEIGHT + ROW_HEIGHT - 5}",
+                                "{row.pc:08x} {row.mnemonic()}"
+                            }
+                            for &(cycle, activity) in row.cells.iter() {
+                                rect {
+                                    x: "{LABEL_WIDTH + (cycle - first_cycle) * CELL_WIDTH}",
+                                    y: "{HEADER_HEIGHT + i as u64 * ROW_HEIGHT + 1}",
+                                    width: "{CELL_WIDTH - 2}",
+                                    height: "{ROW_HEIGHT - 2}",
+                                    fill: fill(activity),
+                                }
+                                text {
+                                    x: "{LABEL_WIDTH + (cycle - first_cycle) * CELL_WIDTH + CELL_WIDTH / 2 - 1}",
+                                    y: "{HEADER_HEIGHT + i as u64 * ROW_HEIGHT + ROW_HEIGHT - 5}",
+                                    text_anchor: "middle",
+                                    "{activity.abbreviation()}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use dioxus::prelude::*;
+
+use crate::emulator::{EmulatorState, PredictorConfig, PredictorKind};
+
+const TABLE_SIZES: [u32; 6] = [4, 8, 16, 32, 64, 128];
+const RETURN_STACK_DEPTHS: [u32; 5] = [0, 2, 4, 8, 16];
+
+/// Branch predictor configuration and accuracy
+#[component]
+#[allow(non_snake_case)]
+pub fn PredictorView(mut emulator_state: Signal<EmulatorState>) -> Element {
+    let mut configure = move |config: Option<PredictorConfig>| {
+        let mut state = emulator_state.write();
+        state.config.predictor = config;
+        state.sync_predictor();
+    };
+
+    let state = emulator_state.read();
+    let config = state.config.predictor;
+    let statistics = state.predictor.as_ref().map(|predictor| predictor.statistics);
+    let rate = |correct: u64, total: u64| {
+        if total == 0 {
+            "-".to_string()
+        } else {
+            format!("{correct}/{total} ({:.1}%)", 100.0 * correct as f64 / total as f64)
+        }
+    };
+
+    rsx! {
+        div { class: "bg-white rounded shadow-sm p-2 mt-2",
+            label { class: "flex items-center gap-1 font-bold mb-1",
+                input {
+                    r#type: "checkbox",
+                    checked: config.is_some(),
+                    onchange: move |evt| configure(evt.checked().then(PredictorConfig::default)),
+                }
+                "Branch prediction"
+            }
+            if let Some(config) = config {
+                div { class: "flex flex-wrap gap-2 items-center text-xs mb-1",
+                    select {
+                        class: "bg-white rounded px-1 shadow-sm",
+                        onchange: move |evt| {
+                            if let Some(&kind) = PredictorKind::ALL.iter().find(|kind| kind.name() == evt.value()) {
+                                configure(Some(PredictorConfig { kind, ..config }));
+                            }
+                        },
+                        for kind in PredictorKind::ALL {
+                            option { value: "{kind.name()}", selected: kind == config.kind, "{kind.name()}" }
+                        }
+                    }
+                    if matches!(config.kind, PredictorKind::OneBit | PredictorKind::TwoBit | PredictorKind::Btb) {
+                        select {
+                            class: "bg-white rounded px-1 shadow-sm",
+                            title: "Table entries",
+                            onchange: move |evt| {
+                                if let Ok(table_size) = evt.value().parse() {
+                                    configure(Some(PredictorConfig { table_size, ..config }));
+                                }
+                            },
+                            for size in TABLE_SIZES {
+                                option { value: "{size}", selected: size == config.table_size, "{size} entries" }
+                            }
+                        }
+                    }
+                    select {
+                        class: "bg-white rounded px-1 shadow-sm",
+                        title: "Return-address stack",
+                        onchange: move |evt| {
+                            if let Ok(return_stack_depth) = evt.value().parse() {
+                                configure(Some(PredictorConfig { return_stack_depth, ..config }));
+                            }
+                        },
+                        for depth in RETURN_STACK_DEPTHS {
+                            option {
+                                value: "{depth}",
+                                selected: depth == config.return_stack_depth,
+                                if depth == 0 { "no RAS" } else { "{depth}-entry RAS" }
+                            }
+                        }
+                    }
+                    label { class: "flex items-center gap-1",
+                        input {
+                            r#type: "checkbox",
+                            checked: config.apply_penalty,
+                            onchange: move |evt| configure(Some(PredictorConfig { apply_penalty: evt.checked(), ..config })),
+                        }
+                        "Mispredict penalty"
+                    }
+                    if config.apply_penalty {
+                        input {
+                            class: "bg-gray-100 rounded px-1 w-12",
+                            r#type: "number",
+                            min: "0",
+                            value: "{config.mispredict_penalty}",
+                            onchange: move |evt| {
+                                if let Ok(mispredict_penalty) = evt.value().parse() {
+                                    configure(Some(PredictorConfig { mispredict_penalty, ..config }));
+                                }
+                            },
+                        }
+                        span { "cycles" }
+                    }
+                }
+                if let Some(statistics) = statistics {
+                    div { class: "flex justify-between border-b border-gray-100",
+                        span { class: "text-gray-500", "Accuracy" }
+                        span { class: "font-bold text-blue-600",
+                            {statistics.accuracy().map_or("-".to_string(), |accuracy| format!("{:.1}%", accuracy * 100.0))}
+                        }
+                    }
+                    div { class: "flex justify-between border-b border-gray-100",
+                        span { class: "text-gray-500", "Branches correct" }
+                        span { {rate(statistics.branches_correct, statistics.branches)} }
+                    }
+                    div { class: "flex justify-between border-b border-gray-100",
+                        span { class: "text-gray-500", "Jumps correct" }
+                        span { {rate(statistics.jumps_correct, statistics.jumps)} }
+                    }
+                    div { class: "flex justify-between border-b border-gray-100",
+                        span { class: "text-gray-500", "Returns correct" }
+                        span { {rate(statistics.returns_correct, statistics.returns)} }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,35 +1,83 @@
 use dioxus::prelude::*;
+use std::collections::BTreeMap;
 
+use super::editable_value::EditableValue;
+use super::value_format::RegisterFormat;
+use crate::assembler::AssembledProgram;
 use crate::emulator::EmulatorState;
 
+/// Label a register value points to, if it is the address of an instruction or data
+fn pointed_label(program: &AssembledProgram, value: u32) -> Option<String> {
+    program.symbolize_data(value).or_else(|| {
+        program
+            .instruction_memory
+            .contains_key(&value)
+            .then(|| program.symbolize(value))
+            .flatten()
+    })
+}
+
 #[component]
 #[allow(non_snake_case)]
-pub fn RegisterView(emulator_state: Signal<EmulatorState>) -> Element {
+pub fn RegisterView(
+    mut emulator_state: Signal<EmulatorState>,
+    assembled_program: Signal<Option<AssembledProgram>>,
+) -> Element {
     let abi_names = &[
         "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0/fp", "s1", "a0", "a1", "a2", "a3",
         "a4", "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11",
         "t3", "t4", "t5", "t6",
     ];
+    let mut global_format = use_signal(|| RegisterFormat::Hex);
+    // registers shown in a different format than the rest
+    let mut formats = use_signal(BTreeMap::<usize, RegisterFormat>::new);
+
     let register_vals = &emulator_state.read().x.x;
+    let changed = emulator_state.read().changed_registers();
     let pc = emulator_state.read().pipeline.ID_pc;
+    let program = assembled_program.read();
+    let format_of = move |i: usize| formats.read().get(&i).copied().unwrap_or(global_format());
+    let tooltip = |value: u32| {
+        program
+            .as_ref()
+            .and_then(|program| pointed_label(program, value))
+            .map(|label| format!("points to {label}"))
+            .unwrap_or_default()
+    };
+
     rsx! {
-        div { 
+        div {
             class: "flex flex-col h-full",
-            div { 
-                class: "flex justify-between items-center mb-2",
-                h1 { class: "text-lg font-mono font-bold text-gray-900", "Registers" }
+            div {
+                class: "flex justify-end items-center gap-2 mb-2",
+                select {
+                    class: "bg-white rounded px-1 shadow-sm font-mono",
+                    onchange: move |evt| {
+                        if let Some(selected) = RegisterFormat::ALL.iter().find(|f| f.name() == evt.value()) {
+                            global_format.set(*selected);
+                            formats.write().clear();
+                        }
+                    },
+                    for option_format in RegisterFormat::ALL {
+                        option {
+                            value: option_format.name(),
+                            selected: option_format == global_format(),
+                            "{option_format.name()}"
+                        }
+                    }
+                }
                 div { class: "bg-white rounded px-3 py-1 shadow-sm",
                     span { class: "font-mono font-bold text-gray-700", "PC (ID): " }
-                    span { class: "font-mono text-blue-600", "{pc:#010x}" }
+                    span { class: "font-mono text-blue-600", title: "{tooltip(pc)}", "{pc:#010x}" }
                 }
             }
-            div { 
+            div {
                 class: "grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-2 overflow-auto max-h-[calc(100vh-12rem)] pr-2",
                 for c in 0..4 {
                     div { class: "bg-white rounded shadow-sm p-2",
                         div { class: "grid gap-1",
                             for i in (8 * c)..(8 * (c + 1)) {
-                                div { 
+                                div {
                                     class: {
                                         if i < 8 * (c + 1) - 1 {
                                             "flex justify-between items-center border-b border-gray-100 py-1"
@@ -37,9 +85,32 @@ pub fn RegisterView(emulator_state: Signal<EmulatorState>) -> Element {
                                             "flex justify-between items-center py-1"
                                         }
                                     },
-                                    div { class: "flex-1",
-                                        div { class: "font-mono text-gray-500 text-xs", "x{i} ({abi_names[i]})" }
-                                        div { class: "font-mono font-bold", "{register_vals[i]:#010x}" }
+                                    div { class: "flex-1 min-w-0",
+                                        div { class: "flex justify-between font-mono text-gray-500 text-xs",
+                                            span { "x{i} ({abi_names[i]})" }
+                                            button {
+                                                class: "hover:text-gray-900",
+                                                title: "Change this register's format",
+                                                onclick: move |_| {
+                                                    let next = format_of(i).next();
+                                                    formats.write().insert(i, next);
+                                                },
+                                                "{format_of(i).name()}"
+                                            }
+                                        }
+                                        div {
+                                            class: if changed.contains(&i) { "bg-yellow-200 rounded" } else { "" },
+                                            title: "{tooltip(register_vals[i])}",
+                                            if i == 0 {
+                                                div { class: "font-mono font-bold break-all", "{format_of(i).format(register_vals[i])}" }
+                                            } else {
+                                                EditableValue {
+                                                    class: "font-mono font-bold break-all",
+                                                    display: format_of(i).format(register_vals[i]),
+                                                    onchange: move |value| emulator_state.write().edit_register(i, value),
+                                                }
+                                            }
+                                        }
                                     }
                                 }
                             }
@@ -49,4 +120,4 @@ pub fn RegisterView(emulator_state: Signal<EmulatorState>) -> Element {
             }
         }
     }
-}
\ No newline at end of file
+}
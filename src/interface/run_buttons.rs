@@ -1,10 +1,12 @@
-use crate::assembler::{self, AssembledProgram, Section};
-use crate::emulator::{self, EmulatorState};
+use crate::assembler::{self, AssembledProgram};
+use crate::emulator::{self, EditTarget, EmulatorState, MisalignedAccess, TimingModel};
 
 use dioxus::prelude::*;
 use dioxus_logger::tracing::info;
 use std::ops::Deref;
 
+use super::snapshot_buttons::SnapshotButtons;
+
 #[component]
 #[allow(non_snake_case)]
 pub fn RunButtons(
@@ -21,8 +23,12 @@ pub fn RunButtons(
                     match assembler::assemble(&source.read()) {
                         Ok(assembled) => {
                             let mut new_state = EmulatorState::default();
-                            let start_addr = assembled.get_section_start(Section::Text);
-                            new_state.pipeline.datapath.instr_addr_o = start_addr;
+                            // keep the device configuration across reassembly
+                            new_state.uart.base = emulator_state.read().uart.base;
+                            new_state.config = emulator_state.read().config.clone();
+                            new_state.sync_caches();
+                            new_state.sync_predictor();
+                            new_state.load_program(&assembled);
                             emulator_state.set(new_state);
                             assembled_program.set(Some(assembled));
                         }
@@ -33,7 +39,9 @@ pub fn RunButtons(
                 },
                 "Assemble"
             }
-            if assembled_program.read().is_some() {
+            if emulator_state.read().halted() {
+                span { class: "text-s font-bold py-1 px-2", "Halted" }
+            } else if assembled_program.read().is_some() {
                 button {
                     class: "bg-purple-500 hover:bg-purple-600 text-s text-white font-bold py-1 px-2 rounded",
                     onclick: move |_| {
@@ -48,6 +56,60 @@ pub fn RunButtons(
                     "Next Clock"
                 }
             }
+            if let Some(edit) = emulator_state.read().edits.last().copied() {
+                button {
+                    class: "bg-gray-500 hover:bg-gray-600 text-s text-white font-bold py-1 px-2 rounded",
+                    title: "{emulator_state.read().edits.len()} edit(s) in history",
+                    onclick: move |_| {
+                        if let Some(program) = assembled_program.write().as_mut() {
+                            emulator_state.write().undo_edit(program);
+                        }
+                    },
+                    match edit.target {
+                        EditTarget::Register(register) => format!("Undo x{register} = {:#010x}", edit.new),
+                        EditTarget::Memory(address) => format!("Undo [{address:#010x}] = {:#010x}", edit.new),
+                    }
+                }
+            }
+            label { class: "flex items-center gap-1 text-s",
+                "Misaligned access"
+                select {
+                    class: "bg-gray-100 rounded px-1",
+                    onchange: move |event| {
+                        emulator_state.write().config.misaligned_access = match event.value().as_str() {
+                            "trap" => MisalignedAccess::Trap,
+                            _ => MisalignedAccess::Split,
+                        };
+                    },
+                    option { value: "split", selected: emulator_state.read().config.misaligned_access == MisalignedAccess::Split, "Split" }
+                    option { value: "trap", selected: emulator_state.read().config.misaligned_access == MisalignedAccess::Trap, "Trap" }
+                }
+            }
+            label { class: "flex items-center gap-1 text-s",
+                "Timing"
+                select {
+                    class: "bg-gray-100 rounded px-1",
+                    onchange: move |event| {
+                        emulator_state.write().config.timing = match event.value().as_str() {
+                            "accurate" => TimingModel::CycleAccurate,
+                            _ => TimingModel::Approximate,
+                        };
+                    },
+                    option { value: "approximate", selected: emulator_state.read().config.timing == TimingModel::Approximate, "Approximate" }
+                    option { value: "accurate", selected: emulator_state.read().config.timing == TimingModel::CycleAccurate, "Cycle-accurate" }
+                }
+            }
+            label { class: "flex items-center gap-1 text-s",
+                input {
+                    r#type: "checkbox",
+                    checked: emulator_state.read().config.check_calling_convention,
+                    onchange: move |event| {
+                        emulator_state.write().config.check_calling_convention = event.checked();
+                    },
+                }
+                "Check calling convention"
+            }
+            SnapshotButtons { source, assembled_program, emulator_state }
         }
     }
 }
@@ -0,0 +1,56 @@
+use dioxus::prelude::*;
+use dioxus_logger::tracing::error;
+
+use super::download::download;
+use crate::assembler::AssembledProgram;
+use crate::emulator::{EmulatorState, Snapshot};
+
+/// Saves the whole session to a snapshot file and restores one
+#[component]
+#[allow(non_snake_case)]
+pub fn SnapshotButtons(
+    mut source: Signal<String>,
+    mut assembled_program: Signal<Option<AssembledProgram>>,
+    mut emulator_state: Signal<EmulatorState>,
+) -> Element {
+    rsx! {
+        button {
+            class: "bg-gray-500 hover:bg-gray-600 text-s text-white font-bold py-1 px-2 rounded",
+            title: "Download the source, program, memory and processor state",
+            onclick: move |_| {
+                let snapshot = Snapshot::new(&source.read(), assembled_program.read().as_ref(), &emulator_state.read());
+                download("emugator-snapshot.json", "application/json", &snapshot.to_json());
+            },
+            "Save Snapshot"
+        }
+        label {
+            class: "bg-gray-500 hover:bg-gray-600 text-s text-white font-bold py-1 px-2 rounded cursor-pointer",
+            title: "Restore a downloaded snapshot",
+            "Load Snapshot"
+            input {
+                class: "hidden",
+                r#type: "file",
+                accept: ".json,application/json",
+                onchange: move |evt| async move {
+                    let Some(files) = evt.files() else {
+                        return;
+                    };
+                    for file in files.files() {
+                        let Some(json) = files.read_file_to_string(&file).await else {
+                            error!("Failed to read {file}");
+                            continue;
+                        };
+                        match Snapshot::from_json(&json) {
+                            Ok(snapshot) => {
+                                source.set(snapshot.source);
+                                assembled_program.set(snapshot.program);
+                                emulator_state.set(snapshot.state);
+                            }
+                            Err(e) => error!("Failed to load {file}: {e}"),
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use dioxus::prelude::*;
+
+use super::predictor_view::PredictorView;
+use crate::emulator::{EmulatorState, InstructionClass};
+use crate::isa::{InstructionFormat, CSR};
+
+#[component]
+#[allow(non_snake_case)]
+pub fn StatisticsView(emulator_state: Signal<EmulatorState>) -> Element {
+    let state = emulator_state.read();
+    let statistics = &state.statistics;
+    let mcycle = state.read_counter(CSR::MCYCLE, CSR::MCYCLEH);
+    let minstret = state.read_counter(CSR::MINSTRET, CSR::MINSTRETH);
+
+    let cpi = match statistics.cpi() {
+        Some(cpi) => format!("{cpi:.3}"),
+        None => "-".to_string(),
+    };
+    let branches = statistics.branches_taken + statistics.branches_not_taken;
+    let percent = |count: u64, total: u64| {
+        if total == 0 {
+            0.0
+        } else {
+            100.0 * count as f64 / total as f64
+        }
+    };
+
+    let formats = [
+        InstructionFormat::R,
+        InstructionFormat::I,
+        InstructionFormat::S,
+        InstructionFormat::B,
+        InstructionFormat::U,
+        InstructionFormat::J,
+    ]
+    .map(|format| (format, statistics.formats.get(&format).copied().unwrap_or(0)));
+    let classes = [
+        InstructionClass::Arithmetic,
+        InstructionClass::Load,
+        InstructionClass::Store,
+        InstructionClass::Branch,
+        InstructionClass::Jump,
+        InstructionClass::System,
+    ]
+    .map(|class| (class, statistics.classes.get(&class).copied().unwrap_or(0)));
+
+    rsx! {
+        div { class: "h-full overflow-auto pr-2 font-mono text-sm",
+            div { class: "grid grid-cols-2 lg:grid-cols-4 gap-2 mb-2",
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "text-gray-500 text-xs", "Cycles" }
+                    div { class: "font-bold", "{statistics.cycles}" }
+                }
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "text-gray-500 text-xs", "Retired" }
+                    div { class: "font-bold", "{statistics.retired}" }
+                }
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "text-gray-500 text-xs", "CPI" }
+                    div { class: "font-bold text-blue-600", "{cpi}" }
+                }
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "text-gray-500 text-xs", "mcycle / minstret" }
+                    div { class: "font-bold", "{mcycle} / {minstret}" }
+                }
+            }
+            div { class: "grid grid-cols-1 lg:grid-cols-3 gap-2",
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "font-bold mb-1", "Format mix" }
+                    for (format, count) in formats {
+                        div { class: "flex justify-between border-b border-gray-100",
+                            span { class: "text-gray-500", "{format:?}-type" }
+                            span { "{count} ({percent(count, statistics.retired):.1}%)" }
+                        }
+                    }
+                }
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "font-bold mb-1", "Class mix" }
+                    for (class, count) in classes {
+                        div { class: "flex justify-between border-b border-gray-100",
+                            span { class: "text-gray-500", "{class:?}" }
+                            span { "{count} ({percent(count, statistics.retired):.1}%)" }
+                        }
+                    }
+                }
+                div { class: "bg-white rounded shadow-sm p-2",
+                    div { class: "font-bold mb-1", "Memory and control" }
+                    div { class: "flex justify-between border-b border-gray-100",
+                        span { class: "text-gray-500", "Loads" }
+                        span { "{statistics.loads}" }
+                    }
+                    div { class: "flex justify-between border-b border-gray-100",
+                        span { class: "text-gray-500", "Stores" }
+                        span { "{statistics.stores}" }
+                    }
+                    div { class: "flex justify-between border-b border-gray-100",
+                        span { class: "text-gray-500", "Branches taken" }
+                        span { "{statistics.branches_taken} ({percent(statistics.branches_taken, branches):.1}%)" }
+                    }
+                    div { class: "flex justify-between border-b border-gray-100",
+                        span { class: "text-gray-500", "Branches not taken" }
+                        span { "{statistics.branches_not_taken} ({percent(statistics.branches_not_taken, branches):.1}%)" }
+                    }
+                    for (name, count) in statistics.instructions.iter() {
+                        div { class: "flex justify-between border-b border-gray-100 text-xs",
+                            span { class: "text-gray-500", "{name}" }
+                            span { "{count}" }
+                        }
+                    }
+                }
+            }
+            PredictorView { emulator_state }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use dioxus_logger::tracing::warn;
+use serde::{Deserialize, Serialize};
+
+use super::{ProcessorViewType, StateViewType};
+use crate::emulator::EmulatorConfig;
+
+const SOURCE_KEY: &str = "emugator.source";
+const SETTINGS_KEY: &str = "emugator.settings";
+
+/// Name the bundled demo program is saved under
+pub const DEFAULT_FILE_NAME: &str = "prototype-demo.s";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+fn load(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok().flatten()
+}
+
+fn save(key: &str, value: &str) {
+    let saved = local_storage().map(|storage| storage.set_item(key, value));
+    if !matches!(saved, Some(Ok(()))) {
+        warn!("Failed to save {key} to local storage");
+    }
+}
+
+/// The source autosaved by the last session
+pub fn load_source() -> Option<String> {
+    load(SOURCE_KEY)
+}
+
+pub fn save_source(source: &str) {
+    save(SOURCE_KEY, source);
+}
+
+/// Interface settings autosaved alongside the source
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Name of the open file, used when saving it
+    pub file_name: String,
+    /// Whether the source changed since it was last opened or saved
+    pub modified: bool,
+    pub processor_view: ProcessorViewType,
+    pub state_view: StateViewType,
+    pub config: EmulatorConfig,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            file_name: DEFAULT_FILE_NAME.to_string(),
+            modified: false,
+            processor_view: ProcessorViewType::Datapath,
+            state_view: StateViewType::Registers,
+            config: EmulatorConfig::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Settings saved by an older version or by hand fall back to the defaults
+    fn parse(json: &str) -> Settings {
+        serde_json::from_str(json).unwrap_or_else(|e| {
+            warn!("Ignoring the saved settings: {e}");
+            Settings::default()
+        })
+    }
+
+    pub fn load() -> Settings {
+        load(SETTINGS_KEY).map_or_else(Settings::default, |json| Settings::parse(&json))
+    }
+
+    pub fn save(&self) {
+        match serde_json::to_string(self) {
+            Ok(json) => save(SETTINGS_KEY, &json),
+            Err(e) => warn!("Failed to serialize the settings: {e}"),
+        }
+    }
+}
+
+#[test]
+fn test_settings_parse() {
+    let mut settings = Settings {
+        file_name: "lab3.s".to_string(),
+        modified: true,
+        processor_view: ProcessorViewType::Waveform,
+        ..Default::default()
+    };
+    settings.config.check_calling_convention = true;
+    let json = serde_json::to_string(&settings).unwrap();
+    assert_eq!(Settings::parse(&json), settings);
+
+    // missing fields keep their defaults, garbage gives the defaults
+    let partial = Settings::parse(r#"{"file_name": "lab4.s"}"#);
+    assert_eq!(partial.file_name, "lab4.s");
+    assert_eq!(partial.processor_view, ProcessorViewType::Datapath);
+    assert_eq!(Settings::parse("{"), Settings::default());
+}
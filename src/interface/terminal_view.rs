@@ -0,0 +1,66 @@
+use dioxus::prelude::*;
+
+use crate::emulator::EmulatorState;
+
+#[component]
+#[allow(non_snake_case)]
+pub fn TerminalView(emulator_state: Signal<EmulatorState>) -> Element {
+    let uart = emulator_state.read().uart.clone();
+    let output = String::from_utf8_lossy(&uart.tx).to_string();
+    let queued = uart.rx.len();
+    let mut base_input = use_signal(|| format!("{:#010x}", uart.base));
+    let mut base_error = use_signal(|| None::<String>);
+
+    rsx! {
+        div { class: "h-full flex flex-col font-mono text-sm",
+            div { class: "flex gap-2 items-center mb-2 flex-shrink-0",
+                span { class: "text-gray-700", "UART base:" }
+                input {
+                    class: "bg-white rounded px-2 w-32 shadow-sm",
+                    value: "{base_input}",
+                    oninput: move |evt| base_input.set(evt.value()),
+                    onchange: move |evt| {
+                        let value = evt.value();
+                        let digits = value.trim().trim_start_matches("0x");
+                        let moved = match u32::from_str_radix(digits, 16) {
+                            Ok(base) => emulator_state.write().set_uart_base(base),
+                            Err(_) => Err(format!("Invalid hex address: {}", value.trim())),
+                        };
+                        base_error.set(moved.err());
+                        base_input.set(format!("{:#010x}", emulator_state.read().uart.base));
+                    },
+                }
+                span { class: "text-gray-500 text-xs", "DATA +0x0, STATUS +0x4" }
+                if let Some(error) = base_error() {
+                    span { class: "text-red-600 text-xs", "{error}" }
+                }
+                span { class: "flex-grow" }
+                span { class: "text-gray-500 text-xs", "{queued} byte(s) queued for RX" }
+                button {
+                    class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded",
+                    onclick: move |_| emulator_state.write().uart.tx.clear(),
+                    "Clear"
+                }
+            }
+            // typing into the terminal queues bytes for the program to receive
+            div {
+                class: "flex-grow overflow-auto bg-black text-green-400 rounded p-2 whitespace-pre-wrap focus:outline focus:outline-blue-500",
+                tabindex: 0,
+                onkeydown: move |evt| {
+                    let bytes = match evt.key() {
+                        Key::Character(c) => c.into_bytes(),
+                        Key::Enter => vec![b'\n'],
+                        Key::Backspace => vec![0x08],
+                        Key::Tab => vec![b'\t'],
+                        Key::Escape => vec![0x1B],
+                        _ => return,
+                    };
+                    evt.prevent_default();
+                    emulator_state.write().uart.rx.extend(bytes);
+                },
+                "{output}"
+                span { class: "animate-pulse", "_" }
+            }
+        }
+    }
+}
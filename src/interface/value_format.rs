@@ -0,0 +1,197 @@
+/// Parses a value typed into the debugger: `0x` hex, `0b` binary, a
+/// negative signed decimal, an unsigned decimal or up to four ASCII
+/// characters in quotes, the first character in the lowest byte
+pub fn parse_value(text: &str) -> Result<u32, String> {
+    let text = text.trim();
+
+    if let Some(binary) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        u32::from_str_radix(&binary.replace('_', ""), 2)
+            .map_err(|_| format!("Invalid binary value: {text}"))
+    } else if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u32::from_str_radix(&hex.replace('_', ""), 16)
+            .map_err(|_| format!("Invalid hex value: {text}"))
+    } else if let Some(quoted) = text
+        .strip_prefix('\'')
+        .and_then(|t| t.strip_suffix('\''))
+        .or_else(|| text.strip_prefix('"').and_then(|t| t.strip_suffix('"')))
+    {
+        if !quoted.is_ascii() || quoted.len() > 4 {
+            return Err(format!("Expected up to 4 ASCII characters: {text}"));
+        }
+        let mut bytes = [0; 4];
+        bytes[..quoted.len()].copy_from_slice(quoted.as_bytes());
+        Ok(u32::from_le_bytes(bytes))
+    } else if text.starts_with('-') {
+        text.parse::<i32>()
+            .map(|value| value as u32)
+            .map_err(|_| format!("Invalid signed value: {text}"))
+    } else {
+        text.parse::<u32>()
+            .map_err(|_| format!("Invalid value: {text}"))
+    }
+}
+
+#[test]
+fn test_parse_value() {
+    assert_eq!(parse_value("0xFF"), Ok(0xFF));
+    assert_eq!(parse_value(" 0xdead_beef "), Ok(0xDEAD_BEEF));
+    assert_eq!(parse_value("0b1010_0101"), Ok(0xA5));
+    assert_eq!(parse_value("42"), Ok(42));
+    assert_eq!(parse_value("4294967295"), Ok(u32::MAX));
+    assert_eq!(parse_value("-1"), Ok(u32::MAX));
+    assert_eq!(parse_value("-2147483648"), Ok(0x8000_0000));
+    assert_eq!(parse_value("'A'"), Ok(0x41));
+    assert_eq!(parse_value("\"abcd\""), Ok(0x6463_6261));
+
+    assert!(parse_value("0x1_0000_0000").is_err());
+    assert!(parse_value("4294967296").is_err());
+    assert!(parse_value("'abcde'").is_err());
+    assert!(parse_value("ten").is_err());
+}
+
+/// How the memory inspector groups and shows bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryFormat {
+    Bytes,
+    Halfwords,
+    Words,
+    Signed,
+    Unsigned,
+    Ascii,
+    Float,
+}
+
+impl MemoryFormat {
+    pub const ALL: [MemoryFormat; 7] = [
+        MemoryFormat::Bytes,
+        MemoryFormat::Halfwords,
+        MemoryFormat::Words,
+        MemoryFormat::Signed,
+        MemoryFormat::Unsigned,
+        MemoryFormat::Ascii,
+        MemoryFormat::Float,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MemoryFormat::Bytes => "Bytes",
+            MemoryFormat::Halfwords => "Halfwords",
+            MemoryFormat::Words => "Words",
+            MemoryFormat::Signed => "Signed",
+            MemoryFormat::Unsigned => "Unsigned",
+            MemoryFormat::Ascii => "ASCII",
+            MemoryFormat::Float => "Float",
+        }
+    }
+
+    /// Bytes shown per cell
+    pub fn size(&self) -> u32 {
+        match self {
+            MemoryFormat::Bytes | MemoryFormat::Ascii => 1,
+            MemoryFormat::Halfwords => 2,
+            _ => 4,
+        }
+    }
+
+    /// Formats the little endian value of a cell
+    pub fn format(&self, value: u32) -> String {
+        match self {
+            MemoryFormat::Bytes => format!("{value:02x}"),
+            MemoryFormat::Halfwords => format!("{value:04x}"),
+            MemoryFormat::Words => format!("{value:08x}"),
+            MemoryFormat::Signed => format!("{}", value as i32),
+            MemoryFormat::Unsigned => format!("{value}"),
+            MemoryFormat::Ascii => match value as u8 {
+                byte @ 0x20..=0x7E => (byte as char).to_string(),
+                _ => ".".to_string(),
+            },
+            MemoryFormat::Float => {
+                let float = f32::from_bits(value);
+                if float == 0.0 || (1e-4..1e7).contains(&float.abs()) {
+                    format!("{float}")
+                } else {
+                    format!("{float:e}")
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_memory_format() {
+    assert_eq!(MemoryFormat::Bytes.format(0xA), "0a");
+    assert_eq!(MemoryFormat::Halfwords.format(0xBEEF), "beef");
+    assert_eq!(MemoryFormat::Signed.format(0xFFFF_FFF0), "-16");
+    assert_eq!(MemoryFormat::Unsigned.format(0xFFFF_FFF0), "4294967280");
+    assert_eq!(MemoryFormat::Ascii.format(b'A' as u32), "A");
+    assert_eq!(MemoryFormat::Ascii.format(0), ".");
+    assert_eq!(MemoryFormat::Float.format(1.5f32.to_bits()), "1.5");
+    assert_eq!(MemoryFormat::Float.format(1e20f32.to_bits()), "1e20");
+}
+
+/// How the register view shows a register
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegisterFormat {
+    Hex,
+    Signed,
+    Unsigned,
+    Binary,
+    Ascii,
+}
+
+impl RegisterFormat {
+    pub const ALL: [RegisterFormat; 5] = [
+        RegisterFormat::Hex,
+        RegisterFormat::Signed,
+        RegisterFormat::Unsigned,
+        RegisterFormat::Binary,
+        RegisterFormat::Ascii,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RegisterFormat::Hex => "Hex",
+            RegisterFormat::Signed => "Signed",
+            RegisterFormat::Unsigned => "Unsigned",
+            RegisterFormat::Binary => "Binary",
+            RegisterFormat::Ascii => "ASCII",
+        }
+    }
+
+    /// The format after this one, for cycling through them
+    pub fn next(&self) -> RegisterFormat {
+        let index = RegisterFormat::ALL.iter().position(|f| f == self).unwrap();
+        RegisterFormat::ALL[(index + 1) % RegisterFormat::ALL.len()]
+    }
+
+    pub fn format(&self, value: u32) -> String {
+        match self {
+            RegisterFormat::Hex => format!("{value:#010x}"),
+            RegisterFormat::Signed => format!("{}", value as i32),
+            RegisterFormat::Unsigned => format!("{value}"),
+            RegisterFormat::Binary => {
+                let nibbles: Vec<String> =
+                    (0..8).rev().map(|i| format!("{:04b}", value >> (4 * i) & 0xF)).collect();
+                format!("0b{}", nibbles.join("_"))
+            }
+            // the bytes in memory order, as a store would write them
+            RegisterFormat::Ascii => {
+                let chars: String = value
+                    .to_le_bytes()
+                    .iter()
+                    .map(|&byte| MemoryFormat::Ascii.format(byte as u32))
+                    .collect();
+                format!("'{chars}'")
+            }
+        }
+    }
+}
+
+#[test]
+fn test_register_format() {
+    assert_eq!(RegisterFormat::Hex.format(255), "0x000000ff");
+    assert_eq!(RegisterFormat::Signed.format(u32::MAX), "-1");
+    assert_eq!(RegisterFormat::Binary.format(0x8000_000A), "0b1000_0000_0000_0000_0000_0000_0000_1010");
+    assert_eq!(RegisterFormat::Ascii.format(0x0A_63_62_61), "'abc.'");
+    assert_eq!(RegisterFormat::Ascii.next(), RegisterFormat::Hex);
+}
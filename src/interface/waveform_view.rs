@@ -0,0 +1,172 @@
+use dioxus::prelude::*;
+use std::collections::BTreeSet;
+
+use super::download::download;
+use crate::emulator::{EmulatorState, SIGNALS, WAVEFORM_LENGTH};
+
+const NAME_WIDTH: u64 = 200;
+const CYCLE_WIDTH: u64 = 24;
+const ROW_HEIGHT: u64 = 24;
+const HIGH: u64 = 4;
+const LOW: u64 = 18;
+
+/// Ports shown before any are picked, the bus handshakes
+const DEFAULT_SIGNALS: &[&str] = &[
+    "instr_req_o",
+    "instr_addr_o",
+    "instr_rdata_i",
+    "data_req_o",
+    "data_we_o",
+    "data_be_o",
+    "data_addr_o",
+    "fetch_enable_i",
+];
+
+#[component]
+#[allow(non_snake_case)]
+pub fn WaveformView(emulator_state: Signal<EmulatorState>) -> Element {
+    let mut selected = use_signal(|| {
+        SIGNALS
+            .iter()
+            .enumerate()
+            .filter(|(_, signal)| DEFAULT_SIGNALS.contains(&signal.name))
+            .map(|(i, _)| i)
+            .collect::<BTreeSet<usize>>()
+    });
+    let mut cycles = use_signal(|| 32usize);
+    let mut cursor = use_signal(|| None::<u64>);
+
+    let state = emulator_state.read();
+    let waveform = &state.waveform;
+    let skip = waveform.samples.len().saturating_sub(cycles());
+    let first_cycle = waveform.samples.get(skip).map_or(0, |(cycle, _)| *cycle);
+    let columns = (waveform.samples.len() - skip) as u64;
+    let width = NAME_WIDTH + columns * CYCLE_WIDTH;
+    let height = selected.read().len() as u64 * ROW_HEIGHT;
+    let x = |cycle: u64| NAME_WIDTH + (cycle - first_cycle) * CYCLE_WIDTH;
+
+    rsx! {
+        div { class: "h-full flex flex-col font-mono text-sm",
+            div { class: "flex gap-2 items-center mb-2 flex-shrink-0",
+                select {
+                    class: "bg-white rounded px-1 shadow-sm",
+                    onchange: move |evt| {
+                        if let Ok(selected) = evt.value().parse() {
+                            cycles.set(selected);
+                        }
+                    },
+                    for option_cycles in [16, 32, 64, 128, WAVEFORM_LENGTH] {
+                        option {
+                            value: "{option_cycles}",
+                            selected: option_cycles == cycles(),
+                            "last {option_cycles} cycles"
+                        }
+                    }
+                }
+                button {
+                    class: "bg-gray-500 hover:bg-gray-600 text-xs text-white font-bold py-1 px-2 rounded",
+                    onclick: move |_| {
+                        let selected: Vec<usize> = selected.read().iter().copied().collect();
+                        download("emugator.vcd", "text/plain", &emulator_state.read().waveform.to_vcd(&selected));
+                    },
+                    "Export VCD"
+                }
+            }
+            div { class: "flex gap-2 flex-grow overflow-hidden",
+                div { class: "flex flex-col overflow-y-auto flex-shrink-0 text-xs",
+                    for (i, signal) in SIGNALS.iter().enumerate() {
+                        label { class: "flex items-center gap-1",
+                            input {
+                                r#type: "checkbox",
+                                checked: selected.read().contains(&i),
+                                onchange: move |evt| {
+                                    if evt.checked() {
+                                        selected.write().insert(i);
+                                    } else {
+                                        selected.write().remove(&i);
+                                    }
+                                },
+                            }
+                            "{signal.name}"
+                        }
+                    }
+                }
+                div { class: "flex-grow overflow-auto",
+                    if columns == 0 {
+                        span { class: "text-gray-500", "Clock the processor to record the waveform" }
+                    } else {
+                        svg {
+                            width: "{width}",
+                            height: "{height}",
+                            font_size: "10",
+                            onmouseleave: move |_| cursor.set(None),
+                            rect { width: "{width}", height: "{height}", fill: "white" }
+                            for (row, &i) in selected.read().iter().enumerate() {
+                                {
+                                    let signal = &SIGNALS[i];
+                                    let top = row as u64 * ROW_HEIGHT;
+                                    let values: Vec<(u64, u32)> = waveform.values(signal).skip(skip).collect();
+                                    let at_cursor = cursor()
+                                        .and_then(|c| values.iter().find(|(cycle, _)| *cycle == c))
+                                        .map(|(_, value)| if signal.width == 1 { format!(" = {value}") } else { format!(" = {value:#x}") })
+                                        .unwrap_or_default();
+                                    // runs of equal values, drawn as one segment each
+                                    let mut runs: Vec<(u64, u64, u32)> = Vec::new();
+                                    for &(cycle, value) in &values {
+                                        match runs.last_mut() {
+                                            Some((_, end, run_value)) if *run_value == value => *end = cycle + 1,
+                                            _ => runs.push((cycle, cycle + 1, value)),
+                                        }
+                                    }
+                                    let level = |value: u32| top + if value != 0 { HIGH } else { LOW };
+                                    let points = runs
+                                        .iter()
+                                        .flat_map(|&(start, end, value)| [format!("{},{}", x(start), level(value)), format!("{},{}", x(end), level(value))])
+                                        .collect::<Vec<_>>()
+                                        .join(" ");
+                                    rsx! {
+                                        text { x: "2", y: "{top + 15}", font_family: "monospace", "{signal.name}{at_cursor}" }
+                                        line {
+                                            x1: "0", y1: "{top + ROW_HEIGHT}", x2: "{width}", y2: "{top + ROW_HEIGHT}",
+                                            stroke: "#f3f4f6",
+                                        }
+                                        if signal.width == 1 {
+                                            polyline { points, fill: "none", stroke: "#16a34a", stroke_width: "1.5" }
+                                        } else {
+                                            for (start, end, value) in runs {
+                                                polygon {
+                                                    points: "{x(start) + 2},{top + HIGH} {x(end) - 2},{top + HIGH} {x(end)},{top + 11} {x(end) - 2},{top + LOW} {x(start) + 2},{top + LOW} {x(start)},{top + 11}",
+                                                    fill: "#ecfdf5",
+                                                    stroke: "#16a34a",
+                                                }
+                                                // only label segments wide enough for the value
+                                                if (end - start) * CYCLE_WIDTH >= 8 * format!("{value:x}").len() as u64 {
+                                                    text {
+                                                        x: "{(x(start) + x(end)) / 2}",
+                                                        y: "{top + 14}",
+                                                        text_anchor: "middle",
+                                                        font_family: "monospace",
+                                                        "{value:x}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            for cycle in first_cycle..first_cycle + columns {
+                                rect {
+                                    x: "{x(cycle)}",
+                                    width: "{CYCLE_WIDTH}",
+                                    height: "{height}",
+                                    fill: if cursor() == Some(cycle) { "rgba(59, 130, 246, 0.15)" } else { "transparent" },
+                                    onmouseenter: move |_| cursor.set(Some(cycle)),
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use strum::{EnumIter, EnumString, IntoEnumIterator};
+
+use crate::bits;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct CsrDefinition {
+    pub name: &'static str,
+    pub address: u32,
+    /// Value the register holds out of reset
+    pub reset: u32,
+    /// Bits software (and hardware updates) are allowed to change, all others are WARL/read-only
+    pub write_mask: u32,
+}
+
+/// Machine-mode CSRs implemented by CVE2
+/// Taken from https://github.com/openhwgroup/cve2/blob/main/rtl/cve2_cs_registers.sv
+#[derive(EnumString, EnumIter, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[strum(ascii_case_insensitive)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum CSR {
+    MSTATUS,
+    MISA,
+    MIE,
+    MTVEC,
+    MCOUNTINHIBIT,
+    MSCRATCH,
+    MEPC,
+    MCAUSE,
+    MTVAL,
+    MIP,
+    MCYCLE,
+    MINSTRET,
+    MCYCLEH,
+    MINSTRETH,
+    CYCLE,
+    INSTRET,
+    CYCLEH,
+    INSTRETH,
+    MVENDORID,
+    MARCHID,
+    MIMPID,
+    MHARTID,
+}
+
+impl CSR {
+    pub fn definition(&self) -> CsrDefinition {
+        use CSR::*;
+        match self {
+            // MIE (3), MPIE (7), MPP (12:11) is hardwired to machine mode
+            MSTATUS => CsrDefinition {
+                name: "mstatus",
+                address: 0x300,
+                reset: 0b11 << 11,
+                write_mask: 1 << 3 | 1 << 7,
+            },
+            // MXL = 32-bit, I extension
+            MISA => CsrDefinition {
+                name: "misa",
+                address: 0x301,
+                reset: 0x4000_0100,
+                write_mask: 0,
+            },
+            // MSIE (3), MTIE (7), MEIE (11), fast interrupts (31:16)
+            MIE => CsrDefinition {
+                name: "mie",
+                address: 0x304,
+                reset: 0,
+                write_mask: 0xFFFF_0888,
+            },
+            // base must be 256-byte aligned, mode is hardwired to vectored
+            MTVEC => CsrDefinition {
+                name: "mtvec",
+                address: 0x305,
+                reset: 0x0000_0001,
+                write_mask: 0xFFFF_FF00,
+            },
+            // CY (0) and IR (2)
+            MCOUNTINHIBIT => CsrDefinition {
+                name: "mcountinhibit",
+                address: 0x320,
+                reset: 0,
+                write_mask: 0b101,
+            },
+            MSCRATCH => CsrDefinition {
+                name: "mscratch",
+                address: 0x340,
+                reset: 0,
+                write_mask: 0xFFFF_FFFF,
+            },
+            MEPC => CsrDefinition {
+                name: "mepc",
+                address: 0x341,
+                reset: 0,
+                write_mask: 0xFFFF_FFFC,
+            },
+            // interrupt flag (31) and exception code (4:0)
+            MCAUSE => CsrDefinition {
+                name: "mcause",
+                address: 0x342,
+                reset: 0,
+                write_mask: 0x8000_001F,
+            },
+            MTVAL => CsrDefinition {
+                name: "mtval",
+                address: 0x343,
+                reset: 0,
+                write_mask: 0xFFFF_FFFF,
+            },
+            // pending bits mirror the interrupt inputs and can't be written
+            MIP => CsrDefinition {
+                name: "mip",
+                address: 0x344,
+                reset: 0,
+                write_mask: 0,
+            },
+            MCYCLE => CsrDefinition {
+                name: "mcycle",
+                address: 0xB00,
+                reset: 0,
+                write_mask: 0xFFFF_FFFF,
+            },
+            MINSTRET => CsrDefinition {
+                name: "minstret",
+                address: 0xB02,
+                reset: 0,
+                write_mask: 0xFFFF_FFFF,
+            },
+            MCYCLEH => CsrDefinition {
+                name: "mcycleh",
+                address: 0xB80,
+                reset: 0,
+                write_mask: 0xFFFF_FFFF,
+            },
+            MINSTRETH => CsrDefinition {
+                name: "minstreth",
+                address: 0xB82,
+                reset: 0,
+                write_mask: 0xFFFF_FFFF,
+            },
+            // user-level counters are read-only shadows of the machine counters
+            CYCLE => CsrDefinition {
+                name: "cycle",
+                address: 0xC00,
+                reset: 0,
+                write_mask: 0,
+            },
+            INSTRET => CsrDefinition {
+                name: "instret",
+                address: 0xC02,
+                reset: 0,
+                write_mask: 0,
+            },
+            CYCLEH => CsrDefinition {
+                name: "cycleh",
+                address: 0xC80,
+                reset: 0,
+                write_mask: 0,
+            },
+            INSTRETH => CsrDefinition {
+                name: "instreth",
+                address: 0xC82,
+                reset: 0,
+                write_mask: 0,
+            },
+            MVENDORID => CsrDefinition {
+                name: "mvendorid",
+                address: 0xF11,
+                reset: 0,
+                write_mask: 0,
+            },
+            MARCHID => CsrDefinition {
+                name: "marchid",
+                address: 0xF12,
+                reset: 0,
+                write_mask: 0,
+            },
+            MIMPID => CsrDefinition {
+                name: "mimpid",
+                address: 0xF13,
+                reset: 0,
+                write_mask: 0,
+            },
+            MHARTID => CsrDefinition {
+                name: "mhartid",
+                address: 0xF14,
+                reset: 0,
+                write_mask: 0,
+            },
+        }
+    }
+
+    pub fn from_address(address: u32) -> Option<CSR> {
+        CSR::iter().find(|csr| csr.definition().address == address)
+    }
+
+    /// The top two address bits being set marks a CSR as read-only
+    pub fn is_read_only(&self) -> bool {
+        bits!(self.definition().address, 11;10) == 0b11
+    }
+
+    /// The CSR whose storage backs this one, if it is a read-only shadow
+    pub fn alias_of(&self) -> Option<CSR> {
+        use CSR::*;
+        match self {
+            CYCLE => Some(MCYCLE),
+            INSTRET => Some(MINSTRET),
+            CYCLEH => Some(MCYCLEH),
+            INSTRETH => Some(MINSTRETH),
+            _ => None,
+        }
+    }
+}
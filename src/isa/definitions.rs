@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use strum::EnumString;
 
 use super::Instruction;
@@ -25,7 +26,7 @@ impl InstructionDefinition {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum InstructionFormat {
     R,
     I,
@@ -74,6 +75,7 @@ pub enum ISA {
     PAUSE,
     ECALL,
     EBREAK,
+    MRET,
     SW,
     SH,
     SB,
@@ -350,6 +352,13 @@ impl ISA {
                 funct3: Some(0x0),
                 funct7: None,
             },
+            MRET => InstructionDefinition {
+                _name: "MRET",
+                format: InstructionFormat::I,
+                opcode: 0b1110011,
+                funct3: Some(0x0),
+                funct7: None,
+            },
 
             // S-type instructions
             SW => InstructionDefinition {
@@ -495,6 +504,10 @@ impl ISA {
                 0b0000_0000_0001_00000_000_00000_1110011 => Some(EBREAK),
                 _ => None,
             },
+            (0b1110011, 0b000, 0b0011000) => match instr.raw() {
+                0b0011_0000_0010_00000_000_00000_1110011 => Some(MRET),
+                _ => None,
+            },
             (0b1110011, 0b001, _) => Some(CSRRW),
             (0b1110011, 0b010, _) => Some(CSRRS),
             (0b1110011, 0b011, _) => Some(CSRRC),
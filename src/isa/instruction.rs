@@ -157,4 +157,9 @@ impl Instruction {
     pub fn funct7(&self) -> u8 {
         bits!(self.instr, 25, 7) as u8
     }
+
+    /// CSR address of a Zicsr instruction (unsigned, unlike the I-type immediate)
+    pub fn csr(&self) -> u32 {
+        bits!(self.instr, 20, 12)
+    }
 }
@@ -1,5 +1,7 @@
+mod csr;
 mod definitions;
 mod instruction;
 
+pub use csr::CSR;
 pub use definitions::{InstructionDefinition, InstructionFormat, Operands, ISA};
 pub use instruction::Instruction;
@@ -8,12 +8,12 @@ mod utils;
 
 use dioxus::prelude::*;
 use dioxus_logger::tracing::{info, Level};
-use interface::App;
+use interface::Route;
 
 fn main() {
     // Init logger
     dioxus_logger::init(Level::INFO).expect("failed to init logger");
     info!("starting app");
     code_editor::register_riscv_language();
-    launch(App);
+    launch(|| rsx! { Router::<Route> {} });
 }
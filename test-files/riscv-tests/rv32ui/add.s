@@ -0,0 +1,422 @@
+# rv32ui ADD conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/add.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_RR_OP(2, add, 0x00000000, 0x00000000, 0x00000000)
+    ADDI x1, x0, 0
+    ADDI x2, x0, 0
+    ADD x14, x1, x2
+    ADDI x7, x0, 0
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(3, add, 0x00000002, 0x00000001, 0x00000001)
+    ADDI x1, x0, 1
+    ADDI x2, x0, 1
+    ADD x14, x1, x2
+    ADDI x7, x0, 2
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(4, add, 0x0000000a, 0x00000003, 0x00000007)
+    ADDI x1, x0, 3
+    ADDI x2, x0, 7
+    ADD x14, x1, x2
+    ADDI x7, x0, 10
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(5, add, 0xffff8000, 0x00000000, 0xffff8000)
+    ADDI x1, x0, 0
+    LUI x2, 0xffff8
+    ADD x14, x1, x2
+    LUI x7, 0xffff8
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(6, add, 0x80000000, 0x80000000, 0x00000000)
+    LUI x1, 0x80000
+    ADDI x2, x0, 0
+    ADD x14, x1, x2
+    LUI x7, 0x80000
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(7, add, 0x7fff8000, 0x80000000, 0xffff8000)
+    LUI x1, 0x80000
+    LUI x2, 0xffff8
+    ADD x14, x1, x2
+    LUI x7, 0x7fff8
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(8, add, 0x00007fff, 0x00000000, 0x00007fff)
+    ADDI x1, x0, 0
+    LUI x2, 0x8
+    ADDI x2, x2, -1
+    ADD x14, x1, x2
+    LUI x7, 0x8
+    ADDI x7, x7, -1
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(9, add, 0x7fffffff, 0x7fffffff, 0x00000000)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    ADDI x2, x0, 0
+    ADD x14, x1, x2
+    LUI x7, 0x80000
+    ADDI x7, x7, -1
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(10, add, 0x80007ffe, 0x7fffffff, 0x00007fff)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    LUI x2, 0x8
+    ADDI x2, x2, -1
+    ADD x14, x1, x2
+    LUI x7, 0x80008
+    ADDI x7, x7, -2
+    ADDI x3, x0, 10
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(11, add, 0x80007fff, 0x80000000, 0x00007fff)
+    LUI x1, 0x80000
+    LUI x2, 0x8
+    ADDI x2, x2, -1
+    ADD x14, x1, x2
+    LUI x7, 0x80008
+    ADDI x7, x7, -1
+    ADDI x3, x0, 11
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(12, add, 0x7fff7fff, 0x7fffffff, 0xffff8000)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    LUI x2, 0xffff8
+    ADD x14, x1, x2
+    LUI x7, 0x7fff8
+    ADDI x7, x7, -1
+    ADDI x3, x0, 12
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(13, add, 0xffffffff, 0x00000000, 0xffffffff)
+    ADDI x1, x0, 0
+    ADDI x2, x0, -1
+    ADD x14, x1, x2
+    ADDI x7, x0, -1
+    ADDI x3, x0, 13
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(14, add, 0x00000000, 0xffffffff, 0x00000001)
+    ADDI x1, x0, -1
+    ADDI x2, x0, 1
+    ADD x14, x1, x2
+    ADDI x7, x0, 0
+    ADDI x3, x0, 14
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(15, add, 0xfffffffe, 0xffffffff, 0xffffffff)
+    ADDI x1, x0, -1
+    ADDI x2, x0, -1
+    ADD x14, x1, x2
+    ADDI x7, x0, -2
+    ADDI x3, x0, 15
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(16, add, 0x80000000, 0x00000001, 0x7fffffff)
+    ADDI x1, x0, 1
+    LUI x2, 0x80000
+    ADDI x2, x2, -1
+    ADD x14, x1, x2
+    LUI x7, 0x80000
+    ADDI x3, x0, 16
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC1_EQ_DEST(17)
+    ADDI x1, x0, 0
+    LUI x2, 0xffff8
+    ADD x1, x1, x2
+    LUI x7, 0xffff8
+    ADDI x3, x0, 17
+    BNE x1, x7, fail
+
+    # TEST_RR_SRC2_EQ_DEST(18)
+    ADDI x1, x0, 0
+    LUI x2, 0xffff8
+    ADD x2, x1, x2
+    LUI x7, 0xffff8
+    ADDI x3, x0, 18
+    BNE x2, x7, fail
+
+    # TEST_RR_SRC12_EQ_DEST(19)
+    ADDI x1, x0, 0
+    ADD x1, x1, x1
+    ADDI x7, x0, 0
+    ADDI x3, x0, 19
+    BNE x1, x7, fail
+
+    # TEST_RR_DEST_BYPASS(20, 0, add, 24, 13, 11)
+    ADDI x4, x0, 0
+test_20_1:
+    ADDI x1, x0, 13
+    ADDI x2, x0, 11
+    ADD x14, x1, x2
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+    ADDI x7, x0, 24
+    ADDI x3, x0, 20
+    BNE x6, x7, fail
+
+    # TEST_RR_DEST_BYPASS(21, 1, add, 25, 14, 11)
+    ADDI x4, x0, 0
+test_21_1:
+    ADDI x1, x0, 14
+    ADDI x2, x0, 11
+    ADD x14, x1, x2
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+    ADDI x7, x0, 25
+    ADDI x3, x0, 21
+    BNE x6, x7, fail
+
+    # TEST_RR_DEST_BYPASS(22, 2, add, 26, 15, 11)
+    ADDI x4, x0, 0
+test_22_1:
+    ADDI x1, x0, 15
+    ADDI x2, x0, 11
+    ADD x14, x1, x2
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+    ADDI x7, x0, 26
+    ADDI x3, x0, 22
+    BNE x6, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(23, 0, 0, add, 24, 13, 11)
+    ADDI x4, x0, 0
+test_23_1:
+    ADDI x1, x0, 13
+    ADDI x2, x0, 11
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+    ADDI x7, x0, 24
+    ADDI x3, x0, 23
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(24, 0, 1, add, 25, 14, 11)
+    ADDI x4, x0, 0
+test_24_1:
+    ADDI x1, x0, 14
+    ADDI x2, x0, 11
+    ADDI x0, x0, 0
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_24_1
+    ADDI x7, x0, 25
+    ADDI x3, x0, 24
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(25, 0, 2, add, 26, 15, 11)
+    ADDI x4, x0, 0
+test_25_1:
+    ADDI x1, x0, 15
+    ADDI x2, x0, 11
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_25_1
+    ADDI x7, x0, 26
+    ADDI x3, x0, 25
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(26, 1, 0, add, 24, 13, 11)
+    ADDI x4, x0, 0
+test_26_1:
+    ADDI x1, x0, 13
+    ADDI x0, x0, 0
+    ADDI x2, x0, 11
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_26_1
+    ADDI x7, x0, 24
+    ADDI x3, x0, 26
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(27, 1, 1, add, 25, 14, 11)
+    ADDI x4, x0, 0
+test_27_1:
+    ADDI x1, x0, 14
+    ADDI x0, x0, 0
+    ADDI x2, x0, 11
+    ADDI x0, x0, 0
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_27_1
+    ADDI x7, x0, 25
+    ADDI x3, x0, 27
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(28, 2, 0, add, 26, 15, 11)
+    ADDI x4, x0, 0
+test_28_1:
+    ADDI x1, x0, 15
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x2, x0, 11
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_28_1
+    ADDI x7, x0, 26
+    ADDI x3, x0, 28
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(29, 0, 0, add, 24, 13, 11)
+    ADDI x4, x0, 0
+test_29_1:
+    ADDI x2, x0, 11
+    ADDI x1, x0, 13
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_29_1
+    ADDI x7, x0, 24
+    ADDI x3, x0, 29
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(30, 0, 1, add, 25, 14, 11)
+    ADDI x4, x0, 0
+test_30_1:
+    ADDI x2, x0, 11
+    ADDI x1, x0, 14
+    ADDI x0, x0, 0
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_30_1
+    ADDI x7, x0, 25
+    ADDI x3, x0, 30
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(31, 0, 2, add, 26, 15, 11)
+    ADDI x4, x0, 0
+test_31_1:
+    ADDI x2, x0, 11
+    ADDI x1, x0, 15
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_31_1
+    ADDI x7, x0, 26
+    ADDI x3, x0, 31
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(32, 1, 0, add, 24, 13, 11)
+    ADDI x4, x0, 0
+test_32_1:
+    ADDI x2, x0, 11
+    ADDI x0, x0, 0
+    ADDI x1, x0, 13
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_32_1
+    ADDI x7, x0, 24
+    ADDI x3, x0, 32
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(33, 1, 1, add, 25, 14, 11)
+    ADDI x4, x0, 0
+test_33_1:
+    ADDI x2, x0, 11
+    ADDI x0, x0, 0
+    ADDI x1, x0, 14
+    ADDI x0, x0, 0
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_33_1
+    ADDI x7, x0, 25
+    ADDI x3, x0, 33
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(34, 2, 0, add, 26, 15, 11)
+    ADDI x4, x0, 0
+test_34_1:
+    ADDI x2, x0, 11
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x1, x0, 15
+    ADD x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_34_1
+    ADDI x7, x0, 26
+    ADDI x3, x0, 34
+    BNE x14, x7, fail
+
+    # TEST_RR_ZEROSRC1(35)
+    LUI x2, 0xffff8
+    ADD x1, x0, x2
+    LUI x7, 0xffff8
+    ADDI x3, x0, 35
+    BNE x1, x7, fail
+
+    # TEST_RR_ZEROSRC2(36)
+    ADDI x1, x0, 0
+    ADD x2, x1, x0
+    ADDI x7, x0, 0
+    ADDI x3, x0, 36
+    BNE x2, x7, fail
+
+    # TEST_RR_ZEROSRC12(37)
+    ADD x1, x0, x0
+    ADDI x7, x0, 0
+    ADDI x3, x0, 37
+    BNE x1, x7, fail
+
+    # TEST_RR_ZERODEST(38)
+    ADDI x1, x0, 0
+    LUI x2, 0xffff8
+    ADD x0, x1, x2
+    ADDI x7, x0, 0
+    ADDI x3, x0, 38
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
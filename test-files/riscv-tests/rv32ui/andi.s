@@ -0,0 +1,165 @@
+# rv32ui ANDI conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/andi.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_IMM_OP(2, andi, 0xff00ff00, 0xff00ff00, -241)
+    LUI x1, 0xff010
+    ADDI x1, x1, -256
+    ANDI x14, x1, -241
+    LUI x7, 0xff010
+    ADDI x7, x7, -256
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(3, andi, 0x000000f0, 0x0ff00ff0, 240)
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    ANDI x14, x1, 240
+    ADDI x7, x0, 240
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(4, andi, 0x0000000f, 0x00ff00ff, 1807)
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    ANDI x14, x1, 1807
+    ADDI x7, x0, 15
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(5, andi, 0x00000000, 0xf00ff00f, 240)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    ANDI x14, x1, 240
+    ADDI x7, x0, 0
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_EQ_DEST(6)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    ANDI x1, x1, 240
+    ADDI x7, x0, 0
+    ADDI x3, x0, 6
+    BNE x1, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(7, 0, andi, 0x00000700, 0x0ff00ff0, 1807)
+    ADDI x4, x0, 0
+test_7_1:
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    ANDI x14, x1, 1807
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_7_1
+    ADDI x7, x0, 1792
+    ADDI x3, x0, 7
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(8, 1, andi, 240, 0x00ff00ff, 240)
+    ADDI x4, x0, 0
+test_8_1:
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    ANDI x14, x1, 240
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_8_1
+    ADDI x7, x0, 240
+    ADDI x3, x0, 8
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(9, 2, andi, 0xf00ff00f, 0xf00ff00f, -241)
+    ADDI x4, x0, 0
+test_9_1:
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    ANDI x14, x1, -241
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_9_1
+    LUI x7, 0xf00ff
+    ADDI x7, x7, 15
+    ADDI x3, x0, 9
+    BNE x6, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(10, 0, andi, 0x00000700, 0x0ff00ff0, 1807)
+    ADDI x4, x0, 0
+test_10_1:
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    ANDI x14, x1, 1807
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_10_1
+    ADDI x7, x0, 1792
+    ADDI x3, x0, 10
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(11, 1, andi, 240, 0x00ff00ff, 240)
+    ADDI x4, x0, 0
+test_11_1:
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    ADDI x0, x0, 0
+    ANDI x14, x1, 240
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_11_1
+    ADDI x7, x0, 240
+    ADDI x3, x0, 11
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(12, 2, andi, 15, 0xf00ff00f, 1807)
+    ADDI x4, x0, 0
+test_12_1:
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ANDI x14, x1, 1807
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_12_1
+    ADDI x7, x0, 15
+    ADDI x3, x0, 12
+    BNE x14, x7, fail
+
+    # TEST_IMM_ZEROSRC1(13)
+    ANDI x1, x0, 240
+    ADDI x7, x0, 0
+    ADDI x3, x0, 13
+    BNE x1, x7, fail
+
+    # TEST_IMM_ZERODEST(14)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    ANDI x0, x1, 240
+    ADDI x7, x0, 0
+    ADDI x3, x0, 14
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
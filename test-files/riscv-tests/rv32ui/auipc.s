@@ -0,0 +1,42 @@
+# rv32ui AUIPC conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/auipc.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_CASE(2, x10, 0x00001ff8, auipc x10, 0x2; jal x11, 1f; sub x10, x10, x11)
+    AUIPC x10, 0x2
+    JAL x11, test_2_1
+test_2_1:
+    SUB x10, x10, x11
+    LUI x7, 0x2
+    ADDI x7, x7, -8
+    ADDI x3, x0, 2
+    BNE x10, x7, fail
+
+    # TEST_CASE(3, x10, 0xffffdff8, auipc x10, 0xffffe; jal x11, 1f; sub x10, x10, x11)
+    AUIPC x10, 0xffffe
+    JAL x11, test_3_1
+test_3_1:
+    SUB x10, x10, x11
+    LUI x7, 0xffffe
+    ADDI x7, x7, -8
+    ADDI x3, x0, 3
+    BNE x10, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
@@ -0,0 +1,271 @@
+# rv32ui BLT conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/blt.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_BR2_OP_TAKEN(2, blt, 0x00000000, 0x00000001)
+    ADDI x3, x0, 2
+    ADDI x1, x0, 0
+    ADDI x2, x0, 1
+    BLT x1, x2, test_2_2
+    BNE x0, x3, fail
+test_2_1:
+    BNE x0, x3, test_2_3
+test_2_2:
+    BLT x1, x2, test_2_1
+    BNE x0, x3, fail
+test_2_3:
+
+    # TEST_BR2_OP_TAKEN(3, blt, 0xffffffff, 0x00000001)
+    ADDI x3, x0, 3
+    ADDI x1, x0, -1
+    ADDI x2, x0, 1
+    BLT x1, x2, test_3_2
+    BNE x0, x3, fail
+test_3_1:
+    BNE x0, x3, test_3_3
+test_3_2:
+    BLT x1, x2, test_3_1
+    BNE x0, x3, fail
+test_3_3:
+
+    # TEST_BR2_OP_TAKEN(4, blt, 0xfffffffe, 0xffffffff)
+    ADDI x3, x0, 4
+    ADDI x1, x0, -2
+    ADDI x2, x0, -1
+    BLT x1, x2, test_4_2
+    BNE x0, x3, fail
+test_4_1:
+    BNE x0, x3, test_4_3
+test_4_2:
+    BLT x1, x2, test_4_1
+    BNE x0, x3, fail
+test_4_3:
+
+    # TEST_BR2_OP_NOTTAKEN(5, blt, 0x00000001, 0x00000000)
+    ADDI x3, x0, 5
+    ADDI x1, x0, 1
+    ADDI x2, x0, 0
+    BLT x1, x2, test_5_1
+    BNE x0, x3, test_5_2
+test_5_1:
+    BNE x0, x3, fail
+test_5_2:
+    BLT x1, x2, test_5_1
+test_5_3:
+
+    # TEST_BR2_OP_NOTTAKEN(6, blt, 0x00000001, 0xffffffff)
+    ADDI x3, x0, 6
+    ADDI x1, x0, 1
+    ADDI x2, x0, -1
+    BLT x1, x2, test_6_1
+    BNE x0, x3, test_6_2
+test_6_1:
+    BNE x0, x3, fail
+test_6_2:
+    BLT x1, x2, test_6_1
+test_6_3:
+
+    # TEST_BR2_OP_NOTTAKEN(7, blt, 0xffffffff, 0xfffffffe)
+    ADDI x3, x0, 7
+    ADDI x1, x0, -1
+    ADDI x2, x0, -2
+    BLT x1, x2, test_7_1
+    BNE x0, x3, test_7_2
+test_7_1:
+    BNE x0, x3, fail
+test_7_2:
+    BLT x1, x2, test_7_1
+test_7_3:
+
+    # TEST_BR2_OP_NOTTAKEN(8, blt, 0x00000001, 0xfffffffe)
+    ADDI x3, x0, 8
+    ADDI x1, x0, 1
+    ADDI x2, x0, -2
+    BLT x1, x2, test_8_1
+    BNE x0, x3, test_8_2
+test_8_1:
+    BNE x0, x3, fail
+test_8_2:
+    BLT x1, x2, test_8_1
+test_8_3:
+
+    # TEST_BR2_SRC12_BYPASS(9, 0, 0, blt, 0, -1)
+    ADDI x3, x0, 9
+    ADDI x4, x0, 0
+test_9_1:
+    ADDI x1, x0, 0
+    ADDI x2, x0, -1
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_9_1
+
+    # TEST_BR2_SRC12_BYPASS(10, 0, 1, blt, 0, -1)
+    ADDI x3, x0, 10
+    ADDI x4, x0, 0
+test_10_1:
+    ADDI x1, x0, 0
+    ADDI x2, x0, -1
+    ADDI x0, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_10_1
+
+    # TEST_BR2_SRC12_BYPASS(11, 0, 2, blt, 0, -1)
+    ADDI x3, x0, 11
+    ADDI x4, x0, 0
+test_11_1:
+    ADDI x1, x0, 0
+    ADDI x2, x0, -1
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_11_1
+
+    # TEST_BR2_SRC12_BYPASS(12, 1, 0, blt, 0, -1)
+    ADDI x3, x0, 12
+    ADDI x4, x0, 0
+test_12_1:
+    ADDI x1, x0, 0
+    ADDI x0, x0, 0
+    ADDI x2, x0, -1
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_12_1
+
+    # TEST_BR2_SRC12_BYPASS(13, 1, 1, blt, 0, -1)
+    ADDI x3, x0, 13
+    ADDI x4, x0, 0
+test_13_1:
+    ADDI x1, x0, 0
+    ADDI x0, x0, 0
+    ADDI x2, x0, -1
+    ADDI x0, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_13_1
+
+    # TEST_BR2_SRC12_BYPASS(14, 2, 0, blt, 0, -1)
+    ADDI x3, x0, 14
+    ADDI x4, x0, 0
+test_14_1:
+    ADDI x1, x0, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x2, x0, -1
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_14_1
+
+    # TEST_BR2_SRC21_BYPASS(15, 0, 0, blt, 0, -1)
+    ADDI x3, x0, 15
+    ADDI x4, x0, 0
+test_15_1:
+    ADDI x2, x0, -1
+    ADDI x1, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_15_1
+
+    # TEST_BR2_SRC21_BYPASS(16, 0, 1, blt, 0, -1)
+    ADDI x3, x0, 16
+    ADDI x4, x0, 0
+test_16_1:
+    ADDI x2, x0, -1
+    ADDI x1, x0, 0
+    ADDI x0, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_16_1
+
+    # TEST_BR2_SRC21_BYPASS(17, 0, 2, blt, 0, -1)
+    ADDI x3, x0, 17
+    ADDI x4, x0, 0
+test_17_1:
+    ADDI x2, x0, -1
+    ADDI x1, x0, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_17_1
+
+    # TEST_BR2_SRC21_BYPASS(18, 1, 0, blt, 0, -1)
+    ADDI x3, x0, 18
+    ADDI x4, x0, 0
+test_18_1:
+    ADDI x2, x0, -1
+    ADDI x0, x0, 0
+    ADDI x1, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+
+    # TEST_BR2_SRC21_BYPASS(19, 1, 1, blt, 0, -1)
+    ADDI x3, x0, 19
+    ADDI x4, x0, 0
+test_19_1:
+    ADDI x2, x0, -1
+    ADDI x0, x0, 0
+    ADDI x1, x0, 0
+    ADDI x0, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+
+    # TEST_BR2_SRC21_BYPASS(20, 2, 0, blt, 0, -1)
+    ADDI x3, x0, 20
+    ADDI x4, x0, 0
+test_20_1:
+    ADDI x2, x0, -1
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x1, x0, 0
+    BLT x1, x2, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+
+    # TEST_CASE(21, x1, 3)
+    ADDI x1, x0, 1
+    BLT x0, x1, test_21_1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+test_21_1:
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x7, x0, 3
+    ADDI x3, x0, 21
+    BNE x1, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
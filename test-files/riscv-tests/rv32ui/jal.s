@@ -0,0 +1,58 @@
+# rv32ui JAL conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/jal.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+    # Test 2: Basic test
+    ADDI x3, x0, 2
+    ADDI x1, x0, 0
+    JAL x4, test_2_target
+test_2_link:
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    JAL x0, fail
+test_2_target:
+    AUIPC x2, 0
+    ADDI x2, x2, -12
+    BNE x2, x4, fail
+
+    # TEST_CASE(3, x1, 3) - instructions after the jump are skipped
+    ADDI x1, x0, 1
+    JAL x0, test_3_1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+test_3_1:
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x7, x0, 3
+    ADDI x3, x0, 3
+    BNE x1, x7, fail
+
+    # TEST_CASE(4) - backward jump
+    ADDI x3, x0, 4
+    JAL x0, test_4_2
+test_4_1:
+    JAL x0, test_4_3
+test_4_2:
+    JAL x5, test_4_1
+    JAL x0, fail
+test_4_3:
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
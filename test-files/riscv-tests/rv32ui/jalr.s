@@ -0,0 +1,111 @@
+# rv32ui JALR conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/jalr.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+    # Test 2: Basic test
+    ADDI x3, x0, 2
+    ADDI x5, x0, 0
+    AUIPC x6, 0
+    ADDI x6, x6, 16
+    JALR x5, x6, 0
+test_2_link:
+    JAL x0, fail
+test_2_target:
+    AUIPC x1, 0
+    ADDI x1, x1, -4
+    BNE x1, x5, fail
+
+    # Test 3: Basic test2, rs = rd
+    ADDI x3, x0, 3
+    AUIPC x5, 0
+    ADDI x5, x5, 16
+    JALR x5, x5, 0
+test_3_link:
+    JAL x0, fail
+test_3_target:
+    AUIPC x1, 0
+    ADDI x1, x1, -4
+    BNE x1, x5, fail
+
+    # TEST_JALR_SRC1_BYPASS(4, 0, jalr)
+    ADDI x3, x0, 4
+    ADDI x4, x0, 0
+test_4_1:
+    AUIPC x6, 0
+    ADDI x6, x6, 16
+    JALR x13, x6, 0
+    BNE x4, x3, fail
+test_4_2:
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_4_1
+
+    # TEST_JALR_SRC1_BYPASS(5, 1, jalr)
+    ADDI x3, x0, 5
+    ADDI x4, x0, 0
+test_5_1:
+    AUIPC x6, 0
+    ADDI x6, x6, 20
+    ADDI x0, x0, 0
+    JALR x13, x6, 0
+    BNE x4, x3, fail
+test_5_2:
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_5_1
+
+    # TEST_JALR_SRC1_BYPASS(6, 2, jalr)
+    ADDI x3, x0, 6
+    ADDI x4, x0, 0
+test_6_1:
+    AUIPC x6, 0
+    ADDI x6, x6, 24
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    JALR x13, x6, 0
+    BNE x4, x3, fail
+test_6_2:
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_6_1
+
+    # TEST_CASE(7, x1, 3) - instructions after the jump are skipped
+    ADDI x3, x0, 7
+    ADDI x1, x0, 1
+    AUIPC x6, 0
+    JALR x0, x6, 24
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x1, x1, 1
+    ADDI x7, x0, 3
+    ADDI x3, x0, 7
+    BNE x1, x7, fail
+
+    # TEST_CASE(8) - the lowest bit of the target is cleared
+    ADDI x3, x0, 8
+    AUIPC x6, 0
+    ADDI x6, x6, 25
+    JALR x0, x6, -8
+    JAL x0, fail
+test_8_target:
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
@@ -0,0 +1,221 @@
+# rv32ui LBU conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/lbu.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.data 0x2000
+tdat: .byte 0xff, 0x00, 0xf0, 0x0f
+
+.text
+main:
+
+    # TEST_LD_OP(2, lbu, 0x000000ff, 0, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LBU x14, 0(x1)
+    ADDI x7, x0, 255
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(3, lbu, 0x00000000, 1, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LBU x14, 1(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(4, lbu, 0x000000f0, 2, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LBU x14, 2(x1)
+    ADDI x7, x0, 240
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(5, lbu, 0x0000000f, 3, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LBU x14, 3(x1)
+    ADDI x7, x0, 15
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(6, lbu, 0x000000ff, -3, tdat4)
+    LUI x1, 0x2
+    ADDI x1, x1, 3
+    LBU x14, -3(x1)
+    ADDI x7, x0, 255
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(7, lbu, 0x00000000, -2, tdat4)
+    LUI x1, 0x2
+    ADDI x1, x1, 3
+    LBU x14, -2(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(8, lbu, 0x000000f0, -1, tdat4)
+    LUI x1, 0x2
+    ADDI x1, x1, 3
+    LBU x14, -1(x1)
+    ADDI x7, x0, 240
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(9, lbu, 0x0000000f, 0, tdat4)
+    LUI x1, 0x2
+    ADDI x1, x1, 3
+    LBU x14, 0(x1)
+    ADDI x7, x0, 15
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_CASE(10, x5, 0x000000ff) - negative base
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x1, x1, -32
+    LBU x5, 32(x1)
+    ADDI x7, x0, 255
+    ADDI x3, x0, 10
+    BNE x5, x7, fail
+
+    # TEST_CASE(11, x5, 0x00000000) - unaligned base
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x1, x1, -3
+    LBU x5, 4(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 11
+    BNE x5, x7, fail
+
+    # TEST_LD_DEST_BYPASS(12, 0, lbu, 240, 1, tdat2)
+    ADDI x3, x0, 12
+    ADDI x4, x0, 0
+test_12_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 1
+    LBU x14, 1(x13)
+    ADDI x6, x14, 0
+    ADDI x7, x0, 240
+    BNE x6, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_12_1
+
+    # TEST_LD_DEST_BYPASS(13, 1, lbu, 15, 1, tdat3)
+    ADDI x3, x0, 13
+    ADDI x4, x0, 0
+test_13_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 2
+    LBU x14, 1(x13)
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x7, x0, 15
+    BNE x6, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_13_1
+
+    # TEST_LD_DEST_BYPASS(14, 2, lbu, 0, 1, tdat1)
+    ADDI x3, x0, 14
+    ADDI x4, x0, 0
+test_14_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 0
+    LBU x14, 1(x13)
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x7, x0, 0
+    BNE x6, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_14_1
+
+    # TEST_LD_SRC1_BYPASS(15, 0, lbu, 240, 1, tdat2)
+    ADDI x3, x0, 15
+    ADDI x4, x0, 0
+test_15_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 1
+    LBU x14, 1(x13)
+    ADDI x7, x0, 240
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_15_1
+
+    # TEST_LD_SRC1_BYPASS(16, 1, lbu, 15, 1, tdat3)
+    ADDI x3, x0, 16
+    ADDI x4, x0, 0
+test_16_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 2
+    ADDI x0, x0, 0
+    LBU x14, 1(x13)
+    ADDI x7, x0, 15
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_16_1
+
+    # TEST_LD_SRC1_BYPASS(17, 2, lbu, 0, 1, tdat1)
+    ADDI x3, x0, 17
+    ADDI x4, x0, 0
+test_17_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LBU x14, 1(x13)
+    ADDI x7, x0, 0
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_17_1
+
+    # TEST_CASE(18, x2, 2) - write-after-write hazard
+    LUI x5, 0x2
+    ADDI x5, x5, 0
+    LBU x2, 0(x5)
+    ADDI x2, x0, 2
+    ADDI x7, x0, 2
+    ADDI x3, x0, 18
+    BNE x2, x7, fail
+
+    # TEST_CASE(19, x2, 2) - write-after-write hazard
+    LUI x5, 0x2
+    ADDI x5, x5, 0
+    LBU x2, 0(x5)
+    ADDI x0, x0, 0
+    ADDI x2, x0, 2
+    ADDI x7, x0, 2
+    ADDI x3, x0, 19
+    BNE x2, x7, fail
+
+    # TEST_CASE(20) - load writes back over its base register
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LBU x1, 1(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 20
+    BNE x1, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
@@ -0,0 +1,56 @@
+# rv32ui LUI conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/lui.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_CASE(2, x1, 0x00000000, lui x1, 0x0; sra x1, x1, 0)
+    LUI x1, 0x0
+    SRAI x1, x1, 0
+    ADDI x7, x0, 0
+    ADDI x3, x0, 2
+    BNE x1, x7, fail
+
+    # TEST_CASE(3, x1, 0xfffff800, lui x1, 0xfffff; sra x1, x1, 1)
+    LUI x1, 0xfffff
+    SRAI x1, x1, 1
+    ADDI x7, x0, -2048
+    ADDI x3, x0, 3
+    BNE x1, x7, fail
+
+    # TEST_CASE(4, x1, 0x000007ff, lui x1, 0x7ffff; sra x1, x1, 20)
+    LUI x1, 0x7ffff
+    SRAI x1, x1, 20
+    ADDI x7, x0, 2047
+    ADDI x3, x0, 4
+    BNE x1, x7, fail
+
+    # TEST_CASE(5, x1, 0xfffff800, lui x1, 0x80000; sra x1, x1, 20)
+    LUI x1, 0x80000
+    SRAI x1, x1, 20
+    ADDI x7, x0, -2048
+    ADDI x3, x0, 5
+    BNE x1, x7, fail
+
+    # TEST_CASE(6, x0, 0, lui x0, 0x80000)
+    LUI x0, 0x80000
+    ADDI x7, x0, 0
+    ADDI x3, x0, 6
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
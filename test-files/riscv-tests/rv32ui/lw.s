@@ -0,0 +1,238 @@
+# rv32ui LW conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/lw.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.data 0x2000
+tdat: .byte 0xff, 0x00, 0xff, 0x00, 0x00, 0xff, 0x00, 0xff, 0xf0, 0x0f, 0xf0, 0x0f, 0x0f, 0xf0, 0x0f, 0xf0
+
+.text
+main:
+
+    # TEST_LD_OP(2, lw, 0x00ff00ff, 0, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LW x14, 0(x1)
+    LUI x7, 0xff0
+    ADDI x7, x7, 255
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(3, lw, 0xff00ff00, 4, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LW x14, 4(x1)
+    LUI x7, 0xff010
+    ADDI x7, x7, -256
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(4, lw, 0x0ff00ff0, 8, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LW x14, 8(x1)
+    LUI x7, 0xff01
+    ADDI x7, x7, -16
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(5, lw, 0xf00ff00f, 12, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LW x14, 12(x1)
+    LUI x7, 0xf00ff
+    ADDI x7, x7, 15
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(6, lw, 0x00ff00ff, -12, tdat4)
+    LUI x1, 0x2
+    ADDI x1, x1, 12
+    LW x14, -12(x1)
+    LUI x7, 0xff0
+    ADDI x7, x7, 255
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(7, lw, 0xff00ff00, -8, tdat4)
+    LUI x1, 0x2
+    ADDI x1, x1, 12
+    LW x14, -8(x1)
+    LUI x7, 0xff010
+    ADDI x7, x7, -256
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(8, lw, 0x0ff00ff0, -4, tdat4)
+    LUI x1, 0x2
+    ADDI x1, x1, 12
+    LW x14, -4(x1)
+    LUI x7, 0xff01
+    ADDI x7, x7, -16
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_LD_OP(9, lw, 0xf00ff00f, 0, tdat4)
+    LUI x1, 0x2
+    ADDI x1, x1, 12
+    LW x14, 0(x1)
+    LUI x7, 0xf00ff
+    ADDI x7, x7, 15
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_CASE(10, x5, 0x00ff00ff) - negative base
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x1, x1, -32
+    LW x5, 32(x1)
+    LUI x7, 0xff0
+    ADDI x7, x7, 255
+    ADDI x3, x0, 10
+    BNE x5, x7, fail
+
+    # TEST_CASE(11, x5, 0xff00ff00) - unaligned base
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x1, x1, -3
+    LW x5, 7(x1)
+    LUI x7, 0xff010
+    ADDI x7, x7, -256
+    ADDI x3, x0, 11
+    BNE x5, x7, fail
+
+    # TEST_LD_DEST_BYPASS(12, 0, lw, 0x0ff00ff0, 4, tdat2)
+    ADDI x3, x0, 12
+    ADDI x4, x0, 0
+test_12_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 4
+    LW x14, 4(x13)
+    ADDI x6, x14, 0
+    LUI x7, 0xff01
+    ADDI x7, x7, -16
+    BNE x6, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_12_1
+
+    # TEST_LD_DEST_BYPASS(13, 1, lw, 0xf00ff00f, 4, tdat3)
+    ADDI x3, x0, 13
+    ADDI x4, x0, 0
+test_13_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 8
+    LW x14, 4(x13)
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    LUI x7, 0xf00ff
+    ADDI x7, x7, 15
+    BNE x6, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_13_1
+
+    # TEST_LD_DEST_BYPASS(14, 2, lw, 0xff00ff00, 4, tdat1)
+    ADDI x3, x0, 14
+    ADDI x4, x0, 0
+test_14_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 0
+    LW x14, 4(x13)
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    LUI x7, 0xff010
+    ADDI x7, x7, -256
+    BNE x6, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_14_1
+
+    # TEST_LD_SRC1_BYPASS(15, 0, lw, 0x0ff00ff0, 4, tdat2)
+    ADDI x3, x0, 15
+    ADDI x4, x0, 0
+test_15_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 4
+    LW x14, 4(x13)
+    LUI x7, 0xff01
+    ADDI x7, x7, -16
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_15_1
+
+    # TEST_LD_SRC1_BYPASS(16, 1, lw, 0xf00ff00f, 4, tdat3)
+    ADDI x3, x0, 16
+    ADDI x4, x0, 0
+test_16_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 8
+    ADDI x0, x0, 0
+    LW x14, 4(x13)
+    LUI x7, 0xf00ff
+    ADDI x7, x7, 15
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_16_1
+
+    # TEST_LD_SRC1_BYPASS(17, 2, lw, 0xff00ff00, 4, tdat1)
+    ADDI x3, x0, 17
+    ADDI x4, x0, 0
+test_17_1:
+    LUI x13, 0x2
+    ADDI x13, x13, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LW x14, 4(x13)
+    LUI x7, 0xff010
+    ADDI x7, x7, -256
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_17_1
+
+    # TEST_CASE(18, x2, 2) - write-after-write hazard
+    LUI x5, 0x2
+    ADDI x5, x5, 0
+    LW x2, 0(x5)
+    ADDI x2, x0, 2
+    ADDI x7, x0, 2
+    ADDI x3, x0, 18
+    BNE x2, x7, fail
+
+    # TEST_CASE(19, x2, 2) - write-after-write hazard
+    LUI x5, 0x2
+    ADDI x5, x5, 0
+    LW x2, 0(x5)
+    ADDI x0, x0, 0
+    ADDI x2, x0, 2
+    ADDI x7, x0, 2
+    ADDI x3, x0, 19
+    BNE x2, x7, fail
+
+    # TEST_CASE(20) - load writes back over its base register
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LW x1, 4(x1)
+    LUI x7, 0xff010
+    ADDI x7, x7, -256
+    ADDI x3, x0, 20
+    BNE x1, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
@@ -0,0 +1,393 @@
+# rv32ui OR conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/or.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_RR_OP(2, or, 0xff0fff0f, 0xff00ff00, 0x0f0f0f0f)
+    LUI x1, 0xff010
+    ADDI x1, x1, -256
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    OR x14, x1, x2
+    LUI x7, 0xff100
+    ADDI x7, x7, -241
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(3, or, 0xfff0fff0, 0x0ff00ff0, 0xf0f0f0f0)
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    OR x14, x1, x2
+    LUI x7, 0xfff10
+    ADDI x7, x7, -16
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(4, or, 0x0fff0fff, 0x00ff00ff, 0x0f0f0f0f)
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    OR x14, x1, x2
+    LUI x7, 0xfff1
+    ADDI x7, x7, -1
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(5, or, 0xf0fff0ff, 0xf00ff00f, 0xf0f0f0f0)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    OR x14, x1, x2
+    LUI x7, 0xf0fff
+    ADDI x7, x7, 255
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC1_EQ_DEST(6)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    OR x1, x1, x2
+    LUI x7, 0xf0fff
+    ADDI x7, x7, 255
+    ADDI x3, x0, 6
+    BNE x1, x7, fail
+
+    # TEST_RR_SRC2_EQ_DEST(7)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    OR x2, x1, x2
+    LUI x7, 0xf0fff
+    ADDI x7, x7, 255
+    ADDI x3, x0, 7
+    BNE x2, x7, fail
+
+    # TEST_RR_SRC12_EQ_DEST(8)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    OR x1, x1, x1
+    LUI x7, 0xf00ff
+    ADDI x7, x7, 15
+    ADDI x3, x0, 8
+    BNE x1, x7, fail
+
+    # TEST_RR_DEST_BYPASS(9, 0, or, 0xff0fff0f, 0xff00ff00, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_9_1:
+    LUI x1, 0xff010
+    ADDI x1, x1, -256
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    OR x14, x1, x2
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_9_1
+    LUI x7, 0xff100
+    ADDI x7, x7, -241
+    ADDI x3, x0, 9
+    BNE x6, x7, fail
+
+    # TEST_RR_DEST_BYPASS(10, 1, or, 0xfff0fff0, 0x0ff00ff0, 0xf0f0f0f0)
+    ADDI x4, x0, 0
+test_10_1:
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    OR x14, x1, x2
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_10_1
+    LUI x7, 0xfff10
+    ADDI x7, x7, -16
+    ADDI x3, x0, 10
+    BNE x6, x7, fail
+
+    # TEST_RR_DEST_BYPASS(11, 2, or, 0x0fff0fff, 0x00ff00ff, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_11_1:
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    OR x14, x1, x2
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_11_1
+    LUI x7, 0xfff1
+    ADDI x7, x7, -1
+    ADDI x3, x0, 11
+    BNE x6, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(12, 0, 0, or, 0xff0fff0f, 0xff00ff00, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_12_1:
+    LUI x1, 0xff010
+    ADDI x1, x1, -256
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_12_1
+    LUI x7, 0xff100
+    ADDI x7, x7, -241
+    ADDI x3, x0, 12
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(13, 0, 1, or, 0xfff0fff0, 0x0ff00ff0, 0xf0f0f0f0)
+    ADDI x4, x0, 0
+test_13_1:
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    ADDI x0, x0, 0
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_13_1
+    LUI x7, 0xfff10
+    ADDI x7, x7, -16
+    ADDI x3, x0, 13
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(14, 0, 2, or, 0x0fff0fff, 0x00ff00ff, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_14_1:
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_14_1
+    LUI x7, 0xfff1
+    ADDI x7, x7, -1
+    ADDI x3, x0, 14
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(15, 1, 0, or, 0xff0fff0f, 0xff00ff00, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_15_1:
+    LUI x1, 0xff010
+    ADDI x1, x1, -256
+    ADDI x0, x0, 0
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_15_1
+    LUI x7, 0xff100
+    ADDI x7, x7, -241
+    ADDI x3, x0, 15
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(16, 1, 1, or, 0xfff0fff0, 0x0ff00ff0, 0xf0f0f0f0)
+    ADDI x4, x0, 0
+test_16_1:
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    ADDI x0, x0, 0
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    ADDI x0, x0, 0
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_16_1
+    LUI x7, 0xfff10
+    ADDI x7, x7, -16
+    ADDI x3, x0, 16
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(17, 2, 0, or, 0x0fff0fff, 0x00ff00ff, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_17_1:
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_17_1
+    LUI x7, 0xfff1
+    ADDI x7, x7, -1
+    ADDI x3, x0, 17
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(18, 0, 0, or, 0xff0fff0f, 0xff00ff00, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_18_1:
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    LUI x1, 0xff010
+    ADDI x1, x1, -256
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+    LUI x7, 0xff100
+    ADDI x7, x7, -241
+    ADDI x3, x0, 18
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(19, 0, 1, or, 0xfff0fff0, 0x0ff00ff0, 0xf0f0f0f0)
+    ADDI x4, x0, 0
+test_19_1:
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    ADDI x0, x0, 0
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+    LUI x7, 0xfff10
+    ADDI x7, x7, -16
+    ADDI x3, x0, 19
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(20, 0, 2, or, 0x0fff0fff, 0x00ff00ff, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_20_1:
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+    LUI x7, 0xfff1
+    ADDI x7, x7, -1
+    ADDI x3, x0, 20
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(21, 1, 0, or, 0xff0fff0f, 0xff00ff00, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_21_1:
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    ADDI x0, x0, 0
+    LUI x1, 0xff010
+    ADDI x1, x1, -256
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+    LUI x7, 0xff100
+    ADDI x7, x7, -241
+    ADDI x3, x0, 21
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(22, 1, 1, or, 0xfff0fff0, 0x0ff00ff0, 0xf0f0f0f0)
+    ADDI x4, x0, 0
+test_22_1:
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    ADDI x0, x0, 0
+    LUI x1, 0xff01
+    ADDI x1, x1, -16
+    ADDI x0, x0, 0
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+    LUI x7, 0xfff10
+    ADDI x7, x7, -16
+    ADDI x3, x0, 22
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(23, 2, 0, or, 0x0fff0fff, 0x00ff00ff, 0x0f0f0f0f)
+    ADDI x4, x0, 0
+test_23_1:
+    LUI x2, 0xf0f1
+    ADDI x2, x2, -241
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LUI x1, 0xff0
+    ADDI x1, x1, 255
+    OR x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+    LUI x7, 0xfff1
+    ADDI x7, x7, -1
+    ADDI x3, x0, 23
+    BNE x14, x7, fail
+
+    # TEST_RR_ZEROSRC1(24)
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    OR x1, x0, x2
+    LUI x7, 0xf0f0f
+    ADDI x7, x7, 240
+    ADDI x3, x0, 24
+    BNE x1, x7, fail
+
+    # TEST_RR_ZEROSRC2(25)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    OR x2, x1, x0
+    LUI x7, 0xf00ff
+    ADDI x7, x7, 15
+    ADDI x3, x0, 25
+    BNE x2, x7, fail
+
+    # TEST_RR_ZEROSRC12(26)
+    OR x1, x0, x0
+    ADDI x7, x0, 0
+    ADDI x3, x0, 26
+    BNE x1, x7, fail
+
+    # TEST_RR_ZERODEST(27)
+    LUI x1, 0xf00ff
+    ADDI x1, x1, 15
+    LUI x2, 0xf0f0f
+    ADDI x2, x2, 240
+    OR x0, x1, x2
+    ADDI x7, x0, 0
+    ADDI x3, x0, 27
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
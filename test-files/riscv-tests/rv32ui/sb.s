@@ -0,0 +1,338 @@
+# rv32ui SB conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/sb.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.data 0x2000
+tdat: .byte 0xef, 0xef, 0xef, 0xef, 0xef, 0xef, 0xef, 0xef, 0xef, 0xef
+
+.text
+main:
+
+    # TEST_ST_OP(2, lb, sb, 0xffffffaa, 0, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x2, x0, -86
+    SB x2, 0(x1)
+    LB x14, 0(x1)
+    ADDI x7, x0, -86
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(3, lb, sb, 0x00000000, 1, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x2, x0, 0
+    SB x2, 1(x1)
+    LB x14, 1(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(4, lb, sb, 0xffffffa0, 2, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x2, x0, -96
+    SB x2, 2(x1)
+    LB x14, 2(x1)
+    ADDI x7, x0, -96
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(5, lb, sb, 0x0000000a, 3, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x2, x0, 10
+    SB x2, 3(x1)
+    LB x14, 3(x1)
+    ADDI x7, x0, 10
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(6, lb, sb, 0xffffffaa, -3, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 8
+    ADDI x2, x0, -86
+    SB x2, -3(x1)
+    LB x14, -3(x1)
+    ADDI x7, x0, -86
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(7, lb, sb, 0x00000000, -2, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 8
+    ADDI x2, x0, 0
+    SB x2, -2(x1)
+    LB x14, -2(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(8, lb, sb, 0xffffffa0, -1, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 8
+    ADDI x2, x0, -96
+    SB x2, -1(x1)
+    LB x14, -1(x1)
+    ADDI x7, x0, -96
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(9, lb, sb, 0x0000000a, 0, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 8
+    ADDI x2, x0, 10
+    SB x2, 0(x1)
+    LB x14, 0(x1)
+    ADDI x7, x0, 10
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_CASE(10, x5, 0x78) - negative base
+    LUI x1, 0x2
+    ADDI x1, x1, 8
+    LUI x2, 0x12345
+    ADDI x2, x2, 1656
+    ADDI x4, x1, -32
+    SB x2, 32(x4)
+    LB x5, 0(x1)
+    ADDI x7, x0, 120
+    ADDI x3, x0, 10
+    BNE x5, x7, fail
+
+    # TEST_CASE(11, x5, 0xffffff98) - unaligned base
+    LUI x1, 0x2
+    ADDI x1, x1, 8
+    LUI x2, 0x3
+    ADDI x2, x2, 152
+    ADDI x1, x1, -6
+    SB x2, 7(x1)
+    LUI x4, 0x2
+    ADDI x4, x4, 9
+    LB x5, 0(x4)
+    ADDI x7, x0, -104
+    ADDI x3, x0, 11
+    BNE x5, x7, fail
+
+    # TEST_ST_SRC12_BYPASS(12, 0, 0, lb, sb, 0xffffffdd, 0, tdat)
+    ADDI x3, x0, 12
+    ADDI x4, x0, 0
+test_12_1:
+    ADDI x1, x0, -35
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SB x1, 0(x2)
+    LB x14, 0(x2)
+    ADDI x7, x0, -35
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_12_1
+
+    # TEST_ST_SRC12_BYPASS(13, 0, 1, lb, sb, 0xffffffcd, 1, tdat)
+    ADDI x3, x0, 13
+    ADDI x4, x0, 0
+test_13_1:
+    ADDI x1, x0, -51
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    SB x1, 1(x2)
+    LB x14, 1(x2)
+    ADDI x7, x0, -51
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_13_1
+
+    # TEST_ST_SRC12_BYPASS(14, 0, 2, lb, sb, 0xffffffcc, 2, tdat)
+    ADDI x3, x0, 14
+    ADDI x4, x0, 0
+test_14_1:
+    ADDI x1, x0, -52
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SB x1, 2(x2)
+    LB x14, 2(x2)
+    ADDI x7, x0, -52
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_14_1
+
+    # TEST_ST_SRC12_BYPASS(15, 1, 0, lb, sb, 0xffffffbc, 3, tdat)
+    ADDI x3, x0, 15
+    ADDI x4, x0, 0
+test_15_1:
+    ADDI x1, x0, -68
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SB x1, 3(x2)
+    LB x14, 3(x2)
+    ADDI x7, x0, -68
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_15_1
+
+    # TEST_ST_SRC12_BYPASS(16, 1, 1, lb, sb, 0xffffffbb, 4, tdat)
+    ADDI x3, x0, 16
+    ADDI x4, x0, 0
+test_16_1:
+    ADDI x1, x0, -69
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    SB x1, 4(x2)
+    LB x14, 4(x2)
+    ADDI x7, x0, -69
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_16_1
+
+    # TEST_ST_SRC12_BYPASS(17, 2, 0, lb, sb, 0xffffffab, 5, tdat)
+    ADDI x3, x0, 17
+    ADDI x4, x0, 0
+test_17_1:
+    ADDI x1, x0, -85
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SB x1, 5(x2)
+    LB x14, 5(x2)
+    ADDI x7, x0, -85
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_17_1
+
+    # TEST_ST_SRC21_BYPASS(18, 0, 0, lb, sb, 0x33, 0, tdat)
+    ADDI x3, x0, 18
+    ADDI x4, x0, 0
+test_18_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x1, x0, 51
+    SB x1, 0(x2)
+    LB x14, 0(x2)
+    ADDI x7, x0, 51
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+
+    # TEST_ST_SRC21_BYPASS(19, 0, 1, lb, sb, 0x23, 1, tdat)
+    ADDI x3, x0, 19
+    ADDI x4, x0, 0
+test_19_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x1, x0, 35
+    ADDI x0, x0, 0
+    SB x1, 1(x2)
+    LB x14, 1(x2)
+    ADDI x7, x0, 35
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+
+    # TEST_ST_SRC21_BYPASS(20, 0, 2, lb, sb, 0x22, 2, tdat)
+    ADDI x3, x0, 20
+    ADDI x4, x0, 0
+test_20_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x1, x0, 34
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SB x1, 2(x2)
+    LB x14, 2(x2)
+    ADDI x7, x0, 34
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+
+    # TEST_ST_SRC21_BYPASS(21, 1, 0, lb, sb, 0x12, 3, tdat)
+    ADDI x3, x0, 21
+    ADDI x4, x0, 0
+test_21_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x1, x0, 18
+    SB x1, 3(x2)
+    LB x14, 3(x2)
+    ADDI x7, x0, 18
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+
+    # TEST_ST_SRC21_BYPASS(22, 1, 1, lb, sb, 0x11, 4, tdat)
+    ADDI x3, x0, 22
+    ADDI x4, x0, 0
+test_22_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x1, x0, 17
+    ADDI x0, x0, 0
+    SB x1, 4(x2)
+    LB x14, 4(x2)
+    ADDI x7, x0, 17
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+
+    # TEST_ST_SRC21_BYPASS(23, 2, 0, lb, sb, 0x1, 5, tdat)
+    ADDI x3, x0, 23
+    ADDI x4, x0, 0
+test_23_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x1, x0, 1
+    SB x1, 5(x2)
+    LB x14, 5(x2)
+    ADDI x7, x0, 1
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+
+    # TEST_CASE(24) - store does not touch the neighbouring element
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x2, x0, -1
+    SB x0, 5(x1)
+    SB x2, 4(x1)
+    LB x14, 5(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 24
+    BNE x14, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
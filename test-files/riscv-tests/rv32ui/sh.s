@@ -0,0 +1,372 @@
+# rv32ui SH conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/sh.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.data 0x2000
+tdat: .byte 0xef, 0xbe, 0xef, 0xbe, 0xef, 0xbe, 0xef, 0xbe, 0xef, 0xbe, 0xef, 0xbe, 0xef, 0xbe, 0xef, 0xbe, 0xef, 0xbe, 0xef, 0xbe
+
+.text
+main:
+
+    # TEST_ST_OP(2, lh, sh, 0x000000aa, 0, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x2, x0, 170
+    SH x2, 0(x1)
+    LH x14, 0(x1)
+    ADDI x7, x0, 170
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(3, lh, sh, 0xffffaa00, 2, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LUI x2, 0xffffb
+    ADDI x2, x2, -1536
+    SH x2, 2(x1)
+    LH x14, 2(x1)
+    LUI x7, 0xffffb
+    ADDI x7, x7, -1536
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(4, lh, sh, 0x00000aa0, 4, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LUI x2, 0x1
+    ADDI x2, x2, -1376
+    SH x2, 4(x1)
+    LH x14, 4(x1)
+    LUI x7, 0x1
+    ADDI x7, x7, -1376
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(5, lh, sh, 0xffffa00a, 6, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LUI x2, 0xffffa
+    ADDI x2, x2, 10
+    SH x2, 6(x1)
+    LH x14, 6(x1)
+    LUI x7, 0xffffa
+    ADDI x7, x7, 10
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(6, lh, sh, 0x000000aa, -6, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 16
+    ADDI x2, x0, 170
+    SH x2, -6(x1)
+    LH x14, -6(x1)
+    ADDI x7, x0, 170
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(7, lh, sh, 0xffffaa00, -4, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 16
+    LUI x2, 0xffffb
+    ADDI x2, x2, -1536
+    SH x2, -4(x1)
+    LH x14, -4(x1)
+    LUI x7, 0xffffb
+    ADDI x7, x7, -1536
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(8, lh, sh, 0x00000aa0, -2, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 16
+    LUI x2, 0x1
+    ADDI x2, x2, -1376
+    SH x2, -2(x1)
+    LH x14, -2(x1)
+    LUI x7, 0x1
+    ADDI x7, x7, -1376
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(9, lh, sh, 0xffffa00a, 0, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 16
+    LUI x2, 0xffffa
+    ADDI x2, x2, 10
+    SH x2, 0(x1)
+    LH x14, 0(x1)
+    LUI x7, 0xffffa
+    ADDI x7, x7, 10
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_CASE(10, x5, 0x5678) - negative base
+    LUI x1, 0x2
+    ADDI x1, x1, 16
+    LUI x2, 0x12345
+    ADDI x2, x2, 1656
+    ADDI x4, x1, -32
+    SH x2, 32(x4)
+    LH x5, 0(x1)
+    LUI x7, 0x5
+    ADDI x7, x7, 1656
+    ADDI x3, x0, 10
+    BNE x5, x7, fail
+
+    # TEST_CASE(11, x5, 0x3098) - unaligned base
+    LUI x1, 0x2
+    ADDI x1, x1, 16
+    LUI x2, 0x3
+    ADDI x2, x2, 152
+    ADDI x1, x1, -5
+    SH x2, 7(x1)
+    LUI x4, 0x2
+    ADDI x4, x4, 18
+    LH x5, 0(x4)
+    LUI x7, 0x3
+    ADDI x7, x7, 152
+    ADDI x3, x0, 11
+    BNE x5, x7, fail
+
+    # TEST_ST_SRC12_BYPASS(12, 0, 0, lh, sh, 0xffffccdd, 0, tdat)
+    ADDI x3, x0, 12
+    ADDI x4, x0, 0
+test_12_1:
+    LUI x1, 0xffffd
+    ADDI x1, x1, -803
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SH x1, 0(x2)
+    LH x14, 0(x2)
+    LUI x7, 0xffffd
+    ADDI x7, x7, -803
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_12_1
+
+    # TEST_ST_SRC12_BYPASS(13, 0, 1, lh, sh, 0xffffbccd, 2, tdat)
+    ADDI x3, x0, 13
+    ADDI x4, x0, 0
+test_13_1:
+    LUI x1, 0xffffc
+    ADDI x1, x1, -819
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    SH x1, 2(x2)
+    LH x14, 2(x2)
+    LUI x7, 0xffffc
+    ADDI x7, x7, -819
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_13_1
+
+    # TEST_ST_SRC12_BYPASS(14, 0, 2, lh, sh, 0xffffbbcc, 4, tdat)
+    ADDI x3, x0, 14
+    ADDI x4, x0, 0
+test_14_1:
+    LUI x1, 0xffffc
+    ADDI x1, x1, -1076
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SH x1, 4(x2)
+    LH x14, 4(x2)
+    LUI x7, 0xffffc
+    ADDI x7, x7, -1076
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_14_1
+
+    # TEST_ST_SRC12_BYPASS(15, 1, 0, lh, sh, 0xffffabbc, 6, tdat)
+    ADDI x3, x0, 15
+    ADDI x4, x0, 0
+test_15_1:
+    LUI x1, 0xffffb
+    ADDI x1, x1, -1092
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SH x1, 6(x2)
+    LH x14, 6(x2)
+    LUI x7, 0xffffb
+    ADDI x7, x7, -1092
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_15_1
+
+    # TEST_ST_SRC12_BYPASS(16, 1, 1, lh, sh, 0xffffaabb, 8, tdat)
+    ADDI x3, x0, 16
+    ADDI x4, x0, 0
+test_16_1:
+    LUI x1, 0xffffb
+    ADDI x1, x1, -1349
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    SH x1, 8(x2)
+    LH x14, 8(x2)
+    LUI x7, 0xffffb
+    ADDI x7, x7, -1349
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_16_1
+
+    # TEST_ST_SRC12_BYPASS(17, 2, 0, lh, sh, 0xffffdaab, 10, tdat)
+    ADDI x3, x0, 17
+    ADDI x4, x0, 0
+test_17_1:
+    LUI x1, 0xffffe
+    ADDI x1, x1, -1365
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SH x1, 10(x2)
+    LH x14, 10(x2)
+    LUI x7, 0xffffe
+    ADDI x7, x7, -1365
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_17_1
+
+    # TEST_ST_SRC21_BYPASS(18, 0, 0, lh, sh, 0x2233, 0, tdat)
+    ADDI x3, x0, 18
+    ADDI x4, x0, 0
+test_18_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    LUI x1, 0x2
+    ADDI x1, x1, 563
+    SH x1, 0(x2)
+    LH x14, 0(x2)
+    LUI x7, 0x2
+    ADDI x7, x7, 563
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+
+    # TEST_ST_SRC21_BYPASS(19, 0, 1, lh, sh, 0x1223, 2, tdat)
+    ADDI x3, x0, 19
+    ADDI x4, x0, 0
+test_19_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    LUI x1, 0x1
+    ADDI x1, x1, 547
+    ADDI x0, x0, 0
+    SH x1, 2(x2)
+    LH x14, 2(x2)
+    LUI x7, 0x1
+    ADDI x7, x7, 547
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+
+    # TEST_ST_SRC21_BYPASS(20, 0, 2, lh, sh, 0x1122, 4, tdat)
+    ADDI x3, x0, 20
+    ADDI x4, x0, 0
+test_20_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    LUI x1, 0x1
+    ADDI x1, x1, 290
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SH x1, 4(x2)
+    LH x14, 4(x2)
+    LUI x7, 0x1
+    ADDI x7, x7, 290
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+
+    # TEST_ST_SRC21_BYPASS(21, 1, 0, lh, sh, 0x112, 6, tdat)
+    ADDI x3, x0, 21
+    ADDI x4, x0, 0
+test_21_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x1, x0, 274
+    SH x1, 6(x2)
+    LH x14, 6(x2)
+    ADDI x7, x0, 274
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+
+    # TEST_ST_SRC21_BYPASS(22, 1, 1, lh, sh, 0x11, 8, tdat)
+    ADDI x3, x0, 22
+    ADDI x4, x0, 0
+test_22_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x1, x0, 17
+    ADDI x0, x0, 0
+    SH x1, 8(x2)
+    LH x14, 8(x2)
+    ADDI x7, x0, 17
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+
+    # TEST_ST_SRC21_BYPASS(23, 2, 0, lh, sh, 0x3001, 10, tdat)
+    ADDI x3, x0, 23
+    ADDI x4, x0, 0
+test_23_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LUI x1, 0x3
+    ADDI x1, x1, 1
+    SH x1, 10(x2)
+    LH x14, 10(x2)
+    LUI x7, 0x3
+    ADDI x7, x7, 1
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+
+    # TEST_CASE(24) - store does not touch the neighbouring element
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x2, x0, -1
+    SH x0, 10(x1)
+    SH x2, 8(x1)
+    LH x14, 10(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 24
+    BNE x14, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
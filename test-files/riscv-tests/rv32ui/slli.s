@@ -0,0 +1,236 @@
+# rv32ui SLLI conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/slli.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_IMM_OP(2, slli, 0x00000001, 0x00000001, 0)
+    ADDI x1, x0, 1
+    SLLI x14, x1, 0
+    ADDI x7, x0, 1
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(3, slli, 0x00000002, 0x00000001, 1)
+    ADDI x1, x0, 1
+    SLLI x14, x1, 1
+    ADDI x7, x0, 2
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(4, slli, 0x00000080, 0x00000001, 7)
+    ADDI x1, x0, 1
+    SLLI x14, x1, 7
+    ADDI x7, x0, 128
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(5, slli, 0x00004000, 0x00000001, 14)
+    ADDI x1, x0, 1
+    SLLI x14, x1, 14
+    LUI x7, 0x4
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(6, slli, 0x80000000, 0x00000001, 31)
+    ADDI x1, x0, 1
+    SLLI x14, x1, 31
+    LUI x7, 0x80000
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(7, slli, 0xffffffff, 0xffffffff, 0)
+    ADDI x1, x0, -1
+    SLLI x14, x1, 0
+    ADDI x7, x0, -1
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(8, slli, 0xfffffffe, 0xffffffff, 1)
+    ADDI x1, x0, -1
+    SLLI x14, x1, 1
+    ADDI x7, x0, -2
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(9, slli, 0xffffff80, 0xffffffff, 7)
+    ADDI x1, x0, -1
+    SLLI x14, x1, 7
+    ADDI x7, x0, -128
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(10, slli, 0xffffc000, 0xffffffff, 14)
+    ADDI x1, x0, -1
+    SLLI x14, x1, 14
+    LUI x7, 0xffffc
+    ADDI x3, x0, 10
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(11, slli, 0x80000000, 0xffffffff, 31)
+    ADDI x1, x0, -1
+    SLLI x14, x1, 31
+    LUI x7, 0x80000
+    ADDI x3, x0, 11
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(12, slli, 0x21212121, 0x21212121, 0)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SLLI x14, x1, 0
+    LUI x7, 0x21212
+    ADDI x7, x7, 289
+    ADDI x3, x0, 12
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(13, slli, 0x42424242, 0x21212121, 1)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SLLI x14, x1, 1
+    LUI x7, 0x42424
+    ADDI x7, x7, 578
+    ADDI x3, x0, 13
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(14, slli, 0x90909080, 0x21212121, 7)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SLLI x14, x1, 7
+    LUI x7, 0x90909
+    ADDI x7, x7, 128
+    ADDI x3, x0, 14
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(15, slli, 0x48484000, 0x21212121, 14)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SLLI x14, x1, 14
+    LUI x7, 0x48484
+    ADDI x3, x0, 15
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(16, slli, 0x80000000, 0x21212121, 31)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SLLI x14, x1, 31
+    LUI x7, 0x80000
+    ADDI x3, x0, 16
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_EQ_DEST(17)
+    ADDI x1, x0, 1
+    SLLI x1, x1, 14
+    LUI x7, 0x4
+    ADDI x3, x0, 17
+    BNE x1, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(18, 0, slli, 128, 1, 7)
+    ADDI x4, x0, 0
+test_18_1:
+    ADDI x1, x0, 1
+    SLLI x14, x1, 7
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+    ADDI x7, x0, 128
+    ADDI x3, x0, 18
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(19, 1, slli, 0x00004000, 1, 14)
+    ADDI x4, x0, 0
+test_19_1:
+    ADDI x1, x0, 1
+    SLLI x14, x1, 14
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+    LUI x7, 0x4
+    ADDI x3, x0, 19
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(20, 2, slli, 0x80000000, 1, 31)
+    ADDI x4, x0, 0
+test_20_1:
+    ADDI x1, x0, 1
+    SLLI x14, x1, 31
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+    LUI x7, 0x80000
+    ADDI x3, x0, 20
+    BNE x6, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(21, 0, slli, 128, 1, 7)
+    ADDI x4, x0, 0
+test_21_1:
+    ADDI x1, x0, 1
+    SLLI x14, x1, 7
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+    ADDI x7, x0, 128
+    ADDI x3, x0, 21
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(22, 1, slli, 0x00004000, 1, 14)
+    ADDI x4, x0, 0
+test_22_1:
+    ADDI x1, x0, 1
+    ADDI x0, x0, 0
+    SLLI x14, x1, 14
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+    LUI x7, 0x4
+    ADDI x3, x0, 22
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(23, 2, slli, 0x80000000, 1, 31)
+    ADDI x4, x0, 0
+test_23_1:
+    ADDI x1, x0, 1
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SLLI x14, x1, 31
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+    LUI x7, 0x80000
+    ADDI x3, x0, 23
+    BNE x14, x7, fail
+
+    # TEST_IMM_ZEROSRC1(24)
+    SLLI x1, x0, 14
+    ADDI x7, x0, 0
+    ADDI x3, x0, 24
+    BNE x1, x7, fail
+
+    # TEST_IMM_ZERODEST(25)
+    ADDI x1, x0, 1
+    SLLI x0, x1, 14
+    ADDI x7, x0, 0
+    ADDI x3, x0, 25
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
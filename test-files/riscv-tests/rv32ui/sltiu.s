@@ -0,0 +1,231 @@
+# rv32ui SLTIU conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/sltiu.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_IMM_OP(2, sltiu, 0x00000000, 0x00000000, 0)
+    ADDI x1, x0, 0
+    SLTIU x14, x1, 0
+    ADDI x7, x0, 0
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(3, sltiu, 0x00000000, 0x00000001, 1)
+    ADDI x1, x0, 1
+    SLTIU x14, x1, 1
+    ADDI x7, x0, 0
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(4, sltiu, 0x00000001, 0x00000003, 7)
+    ADDI x1, x0, 3
+    SLTIU x14, x1, 7
+    ADDI x7, x0, 1
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(5, sltiu, 0x00000000, 0x00000007, 3)
+    ADDI x1, x0, 7
+    SLTIU x14, x1, 3
+    ADDI x7, x0, 0
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(6, sltiu, 0x00000001, 0x00000000, -2048)
+    ADDI x1, x0, 0
+    SLTIU x14, x1, -2048
+    ADDI x7, x0, 1
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(7, sltiu, 0x00000000, 0x80000000, 0)
+    LUI x1, 0x80000
+    SLTIU x14, x1, 0
+    ADDI x7, x0, 0
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(8, sltiu, 0x00000001, 0x80000000, -2048)
+    LUI x1, 0x80000
+    SLTIU x14, x1, -2048
+    ADDI x7, x0, 1
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(9, sltiu, 0x00000001, 0x00000000, 2047)
+    ADDI x1, x0, 0
+    SLTIU x14, x1, 2047
+    ADDI x7, x0, 1
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(10, sltiu, 0x00000000, 0x7fffffff, 0)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    SLTIU x14, x1, 0
+    ADDI x7, x0, 0
+    ADDI x3, x0, 10
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(11, sltiu, 0x00000000, 0x7fffffff, 2047)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    SLTIU x14, x1, 2047
+    ADDI x7, x0, 0
+    ADDI x3, x0, 11
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(12, sltiu, 0x00000000, 0x80000000, 2047)
+    LUI x1, 0x80000
+    SLTIU x14, x1, 2047
+    ADDI x7, x0, 0
+    ADDI x3, x0, 12
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(13, sltiu, 0x00000001, 0x7fffffff, -2048)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    SLTIU x14, x1, -2048
+    ADDI x7, x0, 1
+    ADDI x3, x0, 13
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(14, sltiu, 0x00000001, 0x00000000, -1)
+    ADDI x1, x0, 0
+    SLTIU x14, x1, -1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 14
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(15, sltiu, 0x00000000, 0xffffffff, 1)
+    ADDI x1, x0, -1
+    SLTIU x14, x1, 1
+    ADDI x7, x0, 0
+    ADDI x3, x0, 15
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(16, sltiu, 0x00000000, 0xffffffff, -1)
+    ADDI x1, x0, -1
+    SLTIU x14, x1, -1
+    ADDI x7, x0, 0
+    ADDI x3, x0, 16
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_EQ_DEST(17)
+    ADDI x1, x0, 7
+    SLTIU x1, x1, 3
+    ADDI x7, x0, 0
+    ADDI x3, x0, 17
+    BNE x1, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(18, 0, sltiu, 0, 15, 10)
+    ADDI x4, x0, 0
+test_18_1:
+    ADDI x1, x0, 15
+    SLTIU x14, x1, 10
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+    ADDI x7, x0, 0
+    ADDI x3, x0, 18
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(19, 1, sltiu, 1, 10, 16)
+    ADDI x4, x0, 0
+test_19_1:
+    ADDI x1, x0, 10
+    SLTIU x14, x1, 16
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 19
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(20, 2, sltiu, 0, 16, 9)
+    ADDI x4, x0, 0
+test_20_1:
+    ADDI x1, x0, 16
+    SLTIU x14, x1, 9
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+    ADDI x7, x0, 0
+    ADDI x3, x0, 20
+    BNE x6, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(21, 0, sltiu, 1, 11, 15)
+    ADDI x4, x0, 0
+test_21_1:
+    ADDI x1, x0, 11
+    SLTIU x14, x1, 15
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 21
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(22, 1, sltiu, 0, 17, 8)
+    ADDI x4, x0, 0
+test_22_1:
+    ADDI x1, x0, 17
+    ADDI x0, x0, 0
+    SLTIU x14, x1, 8
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+    ADDI x7, x0, 0
+    ADDI x3, x0, 22
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(23, 2, sltiu, 1, 12, 14)
+    ADDI x4, x0, 0
+test_23_1:
+    ADDI x1, x0, 12
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SLTIU x14, x1, 14
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 23
+    BNE x14, x7, fail
+
+    # TEST_IMM_ZEROSRC1(24)
+    SLTIU x1, x0, 3
+    ADDI x7, x0, 1
+    ADDI x3, x0, 24
+    BNE x1, x7, fail
+
+    # TEST_IMM_ZERODEST(25)
+    ADDI x1, x0, 7
+    SLTIU x0, x1, 3
+    ADDI x7, x0, 0
+    ADDI x3, x0, 25
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
@@ -0,0 +1,249 @@
+# rv32ui SRAI conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/srai.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_IMM_OP(2, srai, 0x80000000, 0x80000000, 0)
+    LUI x1, 0x80000
+    SRAI x14, x1, 0
+    LUI x7, 0x80000
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(3, srai, 0xc0000000, 0x80000000, 1)
+    LUI x1, 0x80000
+    SRAI x14, x1, 1
+    LUI x7, 0xc0000
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(4, srai, 0xff000000, 0x80000000, 7)
+    LUI x1, 0x80000
+    SRAI x14, x1, 7
+    LUI x7, 0xff000
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(5, srai, 0xfffe0000, 0x80000000, 14)
+    LUI x1, 0x80000
+    SRAI x14, x1, 14
+    LUI x7, 0xfffe0
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(6, srai, 0xffffffff, 0x80000001, 31)
+    LUI x1, 0x80000
+    ADDI x1, x1, 1
+    SRAI x14, x1, 31
+    ADDI x7, x0, -1
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(7, srai, 0x7fffffff, 0x7fffffff, 0)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    SRAI x14, x1, 0
+    LUI x7, 0x80000
+    ADDI x7, x7, -1
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(8, srai, 0x3fffffff, 0x7fffffff, 1)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    SRAI x14, x1, 1
+    LUI x7, 0x40000
+    ADDI x7, x7, -1
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(9, srai, 0x00ffffff, 0x7fffffff, 7)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    SRAI x14, x1, 7
+    LUI x7, 0x1000
+    ADDI x7, x7, -1
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(10, srai, 0x0001ffff, 0x7fffffff, 14)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    SRAI x14, x1, 14
+    LUI x7, 0x20
+    ADDI x7, x7, -1
+    ADDI x3, x0, 10
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(11, srai, 0x00000000, 0x7fffffff, 31)
+    LUI x1, 0x80000
+    ADDI x1, x1, -1
+    SRAI x14, x1, 31
+    ADDI x7, x0, 0
+    ADDI x3, x0, 11
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(12, srai, 0x81818181, 0x81818181, 0)
+    LUI x1, 0x81818
+    ADDI x1, x1, 385
+    SRAI x14, x1, 0
+    LUI x7, 0x81818
+    ADDI x7, x7, 385
+    ADDI x3, x0, 12
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(13, srai, 0xc0c0c0c0, 0x81818181, 1)
+    LUI x1, 0x81818
+    ADDI x1, x1, 385
+    SRAI x14, x1, 1
+    LUI x7, 0xc0c0c
+    ADDI x7, x7, 192
+    ADDI x3, x0, 13
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(14, srai, 0xff030303, 0x81818181, 7)
+    LUI x1, 0x81818
+    ADDI x1, x1, 385
+    SRAI x14, x1, 7
+    LUI x7, 0xff030
+    ADDI x7, x7, 771
+    ADDI x3, x0, 14
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(15, srai, 0xfffe0606, 0x81818181, 14)
+    LUI x1, 0x81818
+    ADDI x1, x1, 385
+    SRAI x14, x1, 14
+    LUI x7, 0xfffe0
+    ADDI x7, x7, 1542
+    ADDI x3, x0, 15
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(16, srai, 0xffffffff, 0x81818181, 31)
+    LUI x1, 0x81818
+    ADDI x1, x1, 385
+    SRAI x14, x1, 31
+    ADDI x7, x0, -1
+    ADDI x3, x0, 16
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_EQ_DEST(17)
+    LUI x1, 0x80000
+    SRAI x1, x1, 14
+    LUI x7, 0xfffe0
+    ADDI x3, x0, 17
+    BNE x1, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(18, 0, srai, 0xff000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_18_1:
+    LUI x1, 0x80000
+    SRAI x14, x1, 7
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+    LUI x7, 0xff000
+    ADDI x3, x0, 18
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(19, 1, srai, 0xfffe0000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_19_1:
+    LUI x1, 0x80000
+    SRAI x14, x1, 14
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+    LUI x7, 0xfffe0
+    ADDI x3, x0, 19
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(20, 2, srai, 0xffffffff, 0x80000001, 31)
+    ADDI x4, x0, 0
+test_20_1:
+    LUI x1, 0x80000
+    ADDI x1, x1, 1
+    SRAI x14, x1, 31
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+    ADDI x7, x0, -1
+    ADDI x3, x0, 20
+    BNE x6, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(21, 0, srai, 0xff000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_21_1:
+    LUI x1, 0x80000
+    SRAI x14, x1, 7
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+    LUI x7, 0xff000
+    ADDI x3, x0, 21
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(22, 1, srai, 0xfffe0000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_22_1:
+    LUI x1, 0x80000
+    ADDI x0, x0, 0
+    SRAI x14, x1, 14
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+    LUI x7, 0xfffe0
+    ADDI x3, x0, 22
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(23, 2, srai, 0xffffffff, 0x80000001, 31)
+    ADDI x4, x0, 0
+test_23_1:
+    LUI x1, 0x80000
+    ADDI x1, x1, 1
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SRAI x14, x1, 31
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+    ADDI x7, x0, -1
+    ADDI x3, x0, 23
+    BNE x14, x7, fail
+
+    # TEST_IMM_ZEROSRC1(24)
+    SRAI x1, x0, 14
+    ADDI x7, x0, 0
+    ADDI x3, x0, 24
+    BNE x1, x7, fail
+
+    # TEST_IMM_ZERODEST(25)
+    LUI x1, 0x80000
+    SRAI x0, x1, 14
+    ADDI x7, x0, 0
+    ADDI x3, x0, 25
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
@@ -0,0 +1,463 @@
+# rv32ui SRL conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/srl.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_RR_OP(2, srl, 0x80000000, 0x80000000, 0x00000000)
+    LUI x1, 0x80000
+    ADDI x2, x0, 0
+    SRL x14, x1, x2
+    LUI x7, 0x80000
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(3, srl, 0x40000000, 0x80000000, 0x00000001)
+    LUI x1, 0x80000
+    ADDI x2, x0, 1
+    SRL x14, x1, x2
+    LUI x7, 0x40000
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(4, srl, 0x01000000, 0x80000000, 0x00000007)
+    LUI x1, 0x80000
+    ADDI x2, x0, 7
+    SRL x14, x1, x2
+    LUI x7, 0x1000
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(5, srl, 0x00020000, 0x80000000, 0x0000000e)
+    LUI x1, 0x80000
+    ADDI x2, x0, 14
+    SRL x14, x1, x2
+    LUI x7, 0x20
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(6, srl, 0x00000001, 0x80000001, 0x0000001f)
+    LUI x1, 0x80000
+    ADDI x1, x1, 1
+    ADDI x2, x0, 31
+    SRL x14, x1, x2
+    ADDI x7, x0, 1
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(7, srl, 0xffffffff, 0xffffffff, 0x00000000)
+    ADDI x1, x0, -1
+    ADDI x2, x0, 0
+    SRL x14, x1, x2
+    ADDI x7, x0, -1
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(8, srl, 0x7fffffff, 0xffffffff, 0x00000001)
+    ADDI x1, x0, -1
+    ADDI x2, x0, 1
+    SRL x14, x1, x2
+    LUI x7, 0x80000
+    ADDI x7, x7, -1
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(9, srl, 0x01ffffff, 0xffffffff, 0x00000007)
+    ADDI x1, x0, -1
+    ADDI x2, x0, 7
+    SRL x14, x1, x2
+    LUI x7, 0x2000
+    ADDI x7, x7, -1
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(10, srl, 0x0003ffff, 0xffffffff, 0x0000000e)
+    ADDI x1, x0, -1
+    ADDI x2, x0, 14
+    SRL x14, x1, x2
+    LUI x7, 0x40
+    ADDI x7, x7, -1
+    ADDI x3, x0, 10
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(11, srl, 0x00000001, 0xffffffff, 0x0000001f)
+    ADDI x1, x0, -1
+    ADDI x2, x0, 31
+    SRL x14, x1, x2
+    ADDI x7, x0, 1
+    ADDI x3, x0, 11
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(12, srl, 0x21212121, 0x21212121, 0x00000000)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, 0
+    SRL x14, x1, x2
+    LUI x7, 0x21212
+    ADDI x7, x7, 289
+    ADDI x3, x0, 12
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(13, srl, 0x10909090, 0x21212121, 0x00000001)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, 1
+    SRL x14, x1, x2
+    LUI x7, 0x10909
+    ADDI x7, x7, 144
+    ADDI x3, x0, 13
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(14, srl, 0x00424242, 0x21212121, 0x00000007)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, 7
+    SRL x14, x1, x2
+    LUI x7, 0x424
+    ADDI x7, x7, 578
+    ADDI x3, x0, 14
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(15, srl, 0x00008484, 0x21212121, 0x0000000e)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, 14
+    SRL x14, x1, x2
+    LUI x7, 0x8
+    ADDI x7, x7, 1156
+    ADDI x3, x0, 15
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(16, srl, 0x00000000, 0x21212121, 0x0000001f)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, 31
+    SRL x14, x1, x2
+    ADDI x7, x0, 0
+    ADDI x3, x0, 16
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(17, srl, 0x21212121, 0x21212121, 0xffffffc0)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, -64
+    SRL x14, x1, x2
+    LUI x7, 0x21212
+    ADDI x7, x7, 289
+    ADDI x3, x0, 17
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(18, srl, 0x10909090, 0x21212121, 0xffffffc1)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, -63
+    SRL x14, x1, x2
+    LUI x7, 0x10909
+    ADDI x7, x7, 144
+    ADDI x3, x0, 18
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(19, srl, 0x00424242, 0x21212121, 0xffffffc7)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, -57
+    SRL x14, x1, x2
+    LUI x7, 0x424
+    ADDI x7, x7, 578
+    ADDI x3, x0, 19
+    BNE x14, x7, fail
+
+    # TEST_RR_OP(20, srl, 0x00008484, 0x21212121, 0xffffffce)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    ADDI x2, x0, -50
+    SRL x14, x1, x2
+    LUI x7, 0x8
+    ADDI x7, x7, 1156
+    ADDI x3, x0, 20
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC1_EQ_DEST(22)
+    LUI x1, 0x80000
+    ADDI x2, x0, 14
+    SRL x1, x1, x2
+    LUI x7, 0x20
+    ADDI x3, x0, 22
+    BNE x1, x7, fail
+
+    # TEST_RR_SRC2_EQ_DEST(23)
+    LUI x1, 0x80000
+    ADDI x2, x0, 14
+    SRL x2, x1, x2
+    LUI x7, 0x20
+    ADDI x3, x0, 23
+    BNE x2, x7, fail
+
+    # TEST_RR_SRC12_EQ_DEST(24)
+    LUI x1, 0x80000
+    SRL x1, x1, x1
+    LUI x7, 0x80000
+    ADDI x3, x0, 24
+    BNE x1, x7, fail
+
+    # TEST_RR_DEST_BYPASS(25, 0, srl, 0x01000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_25_1:
+    LUI x1, 0x80000
+    ADDI x2, x0, 7
+    SRL x14, x1, x2
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_25_1
+    LUI x7, 0x1000
+    ADDI x3, x0, 25
+    BNE x6, x7, fail
+
+    # TEST_RR_DEST_BYPASS(26, 1, srl, 0x00020000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_26_1:
+    LUI x1, 0x80000
+    ADDI x2, x0, 14
+    SRL x14, x1, x2
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_26_1
+    LUI x7, 0x20
+    ADDI x3, x0, 26
+    BNE x6, x7, fail
+
+    # TEST_RR_DEST_BYPASS(27, 2, srl, 1, 0x80000000, 31)
+    ADDI x4, x0, 0
+test_27_1:
+    LUI x1, 0x80000
+    ADDI x2, x0, 31
+    SRL x14, x1, x2
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_27_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 27
+    BNE x6, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(28, 0, 0, srl, 0x01000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_28_1:
+    LUI x1, 0x80000
+    ADDI x2, x0, 7
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_28_1
+    LUI x7, 0x1000
+    ADDI x3, x0, 28
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(29, 0, 1, srl, 0x00020000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_29_1:
+    LUI x1, 0x80000
+    ADDI x2, x0, 14
+    ADDI x0, x0, 0
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_29_1
+    LUI x7, 0x20
+    ADDI x3, x0, 29
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(30, 0, 2, srl, 1, 0x80000000, 31)
+    ADDI x4, x0, 0
+test_30_1:
+    LUI x1, 0x80000
+    ADDI x2, x0, 31
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_30_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 30
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(31, 1, 0, srl, 0x01000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_31_1:
+    LUI x1, 0x80000
+    ADDI x0, x0, 0
+    ADDI x2, x0, 7
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_31_1
+    LUI x7, 0x1000
+    ADDI x3, x0, 31
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(32, 1, 1, srl, 0x00020000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_32_1:
+    LUI x1, 0x80000
+    ADDI x0, x0, 0
+    ADDI x2, x0, 14
+    ADDI x0, x0, 0
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_32_1
+    LUI x7, 0x20
+    ADDI x3, x0, 32
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC12_BYPASS(33, 2, 0, srl, 1, 0x80000000, 31)
+    ADDI x4, x0, 0
+test_33_1:
+    LUI x1, 0x80000
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x2, x0, 31
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_33_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 33
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(34, 0, 0, srl, 0x01000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_34_1:
+    ADDI x2, x0, 7
+    LUI x1, 0x80000
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_34_1
+    LUI x7, 0x1000
+    ADDI x3, x0, 34
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(35, 0, 1, srl, 0x00020000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_35_1:
+    ADDI x2, x0, 14
+    LUI x1, 0x80000
+    ADDI x0, x0, 0
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_35_1
+    LUI x7, 0x20
+    ADDI x3, x0, 35
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(36, 0, 2, srl, 1, 0x80000000, 31)
+    ADDI x4, x0, 0
+test_36_1:
+    ADDI x2, x0, 31
+    LUI x1, 0x80000
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_36_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 36
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(37, 1, 0, srl, 0x01000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_37_1:
+    ADDI x2, x0, 7
+    ADDI x0, x0, 0
+    LUI x1, 0x80000
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_37_1
+    LUI x7, 0x1000
+    ADDI x3, x0, 37
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(38, 1, 1, srl, 0x00020000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_38_1:
+    ADDI x2, x0, 14
+    ADDI x0, x0, 0
+    LUI x1, 0x80000
+    ADDI x0, x0, 0
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_38_1
+    LUI x7, 0x20
+    ADDI x3, x0, 38
+    BNE x14, x7, fail
+
+    # TEST_RR_SRC21_BYPASS(39, 2, 0, srl, 1, 0x80000000, 31)
+    ADDI x4, x0, 0
+test_39_1:
+    ADDI x2, x0, 31
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LUI x1, 0x80000
+    SRL x14, x1, x2
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_39_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 39
+    BNE x14, x7, fail
+
+    # TEST_RR_ZEROSRC1(40)
+    ADDI x2, x0, 14
+    SRL x1, x0, x2
+    ADDI x7, x0, 0
+    ADDI x3, x0, 40
+    BNE x1, x7, fail
+
+    # TEST_RR_ZEROSRC2(41)
+    LUI x1, 0x80000
+    SRL x2, x1, x0
+    LUI x7, 0x80000
+    ADDI x3, x0, 41
+    BNE x2, x7, fail
+
+    # TEST_RR_ZEROSRC12(42)
+    SRL x1, x0, x0
+    ADDI x7, x0, 0
+    ADDI x3, x0, 42
+    BNE x1, x7, fail
+
+    # TEST_RR_ZERODEST(43)
+    LUI x1, 0x80000
+    ADDI x2, x0, 14
+    SRL x0, x1, x2
+    ADDI x7, x0, 0
+    ADDI x3, x0, 43
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
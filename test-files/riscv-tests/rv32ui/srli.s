@@ -0,0 +1,243 @@
+# rv32ui SRLI conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/srli.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.text
+main:
+
+    # TEST_IMM_OP(2, srli, 0x80000000, 0x80000000, 0)
+    LUI x1, 0x80000
+    SRLI x14, x1, 0
+    LUI x7, 0x80000
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(3, srli, 0x40000000, 0x80000000, 1)
+    LUI x1, 0x80000
+    SRLI x14, x1, 1
+    LUI x7, 0x40000
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(4, srli, 0x01000000, 0x80000000, 7)
+    LUI x1, 0x80000
+    SRLI x14, x1, 7
+    LUI x7, 0x1000
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(5, srli, 0x00020000, 0x80000000, 14)
+    LUI x1, 0x80000
+    SRLI x14, x1, 14
+    LUI x7, 0x20
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(6, srli, 0x00000001, 0x80000001, 31)
+    LUI x1, 0x80000
+    ADDI x1, x1, 1
+    SRLI x14, x1, 31
+    ADDI x7, x0, 1
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(7, srli, 0xffffffff, 0xffffffff, 0)
+    ADDI x1, x0, -1
+    SRLI x14, x1, 0
+    ADDI x7, x0, -1
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(8, srli, 0x7fffffff, 0xffffffff, 1)
+    ADDI x1, x0, -1
+    SRLI x14, x1, 1
+    LUI x7, 0x80000
+    ADDI x7, x7, -1
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(9, srli, 0x01ffffff, 0xffffffff, 7)
+    ADDI x1, x0, -1
+    SRLI x14, x1, 7
+    LUI x7, 0x2000
+    ADDI x7, x7, -1
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(10, srli, 0x0003ffff, 0xffffffff, 14)
+    ADDI x1, x0, -1
+    SRLI x14, x1, 14
+    LUI x7, 0x40
+    ADDI x7, x7, -1
+    ADDI x3, x0, 10
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(11, srli, 0x00000001, 0xffffffff, 31)
+    ADDI x1, x0, -1
+    SRLI x14, x1, 31
+    ADDI x7, x0, 1
+    ADDI x3, x0, 11
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(12, srli, 0x21212121, 0x21212121, 0)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SRLI x14, x1, 0
+    LUI x7, 0x21212
+    ADDI x7, x7, 289
+    ADDI x3, x0, 12
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(13, srli, 0x10909090, 0x21212121, 1)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SRLI x14, x1, 1
+    LUI x7, 0x10909
+    ADDI x7, x7, 144
+    ADDI x3, x0, 13
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(14, srli, 0x00424242, 0x21212121, 7)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SRLI x14, x1, 7
+    LUI x7, 0x424
+    ADDI x7, x7, 578
+    ADDI x3, x0, 14
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(15, srli, 0x00008484, 0x21212121, 14)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SRLI x14, x1, 14
+    LUI x7, 0x8
+    ADDI x7, x7, 1156
+    ADDI x3, x0, 15
+    BNE x14, x7, fail
+
+    # TEST_IMM_OP(16, srli, 0x00000000, 0x21212121, 31)
+    LUI x1, 0x21212
+    ADDI x1, x1, 289
+    SRLI x14, x1, 31
+    ADDI x7, x0, 0
+    ADDI x3, x0, 16
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_EQ_DEST(17)
+    LUI x1, 0x80000
+    SRLI x1, x1, 14
+    LUI x7, 0x20
+    ADDI x3, x0, 17
+    BNE x1, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(18, 0, srli, 0x01000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_18_1:
+    LUI x1, 0x80000
+    SRLI x14, x1, 7
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+    LUI x7, 0x1000
+    ADDI x3, x0, 18
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(19, 1, srli, 0x00020000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_19_1:
+    LUI x1, 0x80000
+    SRLI x14, x1, 14
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+    LUI x7, 0x20
+    ADDI x3, x0, 19
+    BNE x6, x7, fail
+
+    # TEST_IMM_DEST_BYPASS(20, 2, srli, 1, 0x80000001, 31)
+    ADDI x4, x0, 0
+test_20_1:
+    LUI x1, 0x80000
+    ADDI x1, x1, 1
+    SRLI x14, x1, 31
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    ADDI x6, x14, 0
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 20
+    BNE x6, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(21, 0, srli, 0x01000000, 0x80000000, 7)
+    ADDI x4, x0, 0
+test_21_1:
+    LUI x1, 0x80000
+    SRLI x14, x1, 7
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+    LUI x7, 0x1000
+    ADDI x3, x0, 21
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(22, 1, srli, 0x00020000, 0x80000000, 14)
+    ADDI x4, x0, 0
+test_22_1:
+    LUI x1, 0x80000
+    ADDI x0, x0, 0
+    SRLI x14, x1, 14
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+    LUI x7, 0x20
+    ADDI x3, x0, 22
+    BNE x14, x7, fail
+
+    # TEST_IMM_SRC1_BYPASS(23, 2, srli, 1, 0x80000001, 31)
+    ADDI x4, x0, 0
+test_23_1:
+    LUI x1, 0x80000
+    ADDI x1, x1, 1
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SRLI x14, x1, 31
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+    ADDI x7, x0, 1
+    ADDI x3, x0, 23
+    BNE x14, x7, fail
+
+    # TEST_IMM_ZEROSRC1(24)
+    SRLI x1, x0, 14
+    ADDI x7, x0, 0
+    ADDI x3, x0, 24
+    BNE x1, x7, fail
+
+    # TEST_IMM_ZERODEST(25)
+    LUI x1, 0x80000
+    SRLI x0, x1, 14
+    ADDI x7, x0, 0
+    ADDI x3, x0, 25
+    BNE x0, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
@@ -0,0 +1,380 @@
+# rv32ui SW conformance tests
+#
+# Adapted from riscv-tests isa/rv64ui/sw.S for EmuGator's
+# assembler. Each TEST_* macro is expanded inline; `li`/`la` become
+# LUI/ADDI pairs. On exit gp (x3) is 1 on success, or (n << 1) | 1
+# when test case n failed.
+
+.data 0x2000
+tdat: .byte 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xef, 0xbe, 0xad, 0xde
+
+.text
+main:
+
+    # TEST_ST_OP(2, lw, sw, 0x00aa00aa, 0, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LUI x2, 0xaa0
+    ADDI x2, x2, 170
+    SW x2, 0(x1)
+    LW x14, 0(x1)
+    LUI x7, 0xaa0
+    ADDI x7, x7, 170
+    ADDI x3, x0, 2
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(3, lw, sw, 0xaa00aa00, 4, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LUI x2, 0xaa00b
+    ADDI x2, x2, -1536
+    SW x2, 4(x1)
+    LW x14, 4(x1)
+    LUI x7, 0xaa00b
+    ADDI x7, x7, -1536
+    ADDI x3, x0, 3
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(4, lw, sw, 0x0aa00aa0, 8, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LUI x2, 0xaa01
+    ADDI x2, x2, -1376
+    SW x2, 8(x1)
+    LW x14, 8(x1)
+    LUI x7, 0xaa01
+    ADDI x7, x7, -1376
+    ADDI x3, x0, 4
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(5, lw, sw, 0xa00aa00a, 12, tdat)
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    LUI x2, 0xa00aa
+    ADDI x2, x2, 10
+    SW x2, 12(x1)
+    LW x14, 12(x1)
+    LUI x7, 0xa00aa
+    ADDI x7, x7, 10
+    ADDI x3, x0, 5
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(6, lw, sw, 0x00aa00aa, -12, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 32
+    LUI x2, 0xaa0
+    ADDI x2, x2, 170
+    SW x2, -12(x1)
+    LW x14, -12(x1)
+    LUI x7, 0xaa0
+    ADDI x7, x7, 170
+    ADDI x3, x0, 6
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(7, lw, sw, 0xaa00aa00, -8, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 32
+    LUI x2, 0xaa00b
+    ADDI x2, x2, -1536
+    SW x2, -8(x1)
+    LW x14, -8(x1)
+    LUI x7, 0xaa00b
+    ADDI x7, x7, -1536
+    ADDI x3, x0, 7
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(8, lw, sw, 0x0aa00aa0, -4, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 32
+    LUI x2, 0xaa01
+    ADDI x2, x2, -1376
+    SW x2, -4(x1)
+    LW x14, -4(x1)
+    LUI x7, 0xaa01
+    ADDI x7, x7, -1376
+    ADDI x3, x0, 8
+    BNE x14, x7, fail
+
+    # TEST_ST_OP(9, lw, sw, 0xa00aa00a, 0, tdat9)
+    LUI x1, 0x2
+    ADDI x1, x1, 32
+    LUI x2, 0xa00aa
+    ADDI x2, x2, 10
+    SW x2, 0(x1)
+    LW x14, 0(x1)
+    LUI x7, 0xa00aa
+    ADDI x7, x7, 10
+    ADDI x3, x0, 9
+    BNE x14, x7, fail
+
+    # TEST_CASE(10, x5, 0x12345678) - negative base
+    LUI x1, 0x2
+    ADDI x1, x1, 32
+    LUI x2, 0x12345
+    ADDI x2, x2, 1656
+    ADDI x4, x1, -32
+    SW x2, 32(x4)
+    LW x5, 0(x1)
+    LUI x7, 0x12345
+    ADDI x7, x7, 1656
+    ADDI x3, x0, 10
+    BNE x5, x7, fail
+
+    # TEST_CASE(11, x5, 0x58213098) - unaligned base
+    LUI x1, 0x2
+    ADDI x1, x1, 32
+    LUI x2, 0x58213
+    ADDI x2, x2, 152
+    ADDI x1, x1, -3
+    SW x2, 7(x1)
+    LUI x4, 0x2
+    ADDI x4, x4, 36
+    LW x5, 0(x4)
+    LUI x7, 0x58213
+    ADDI x7, x7, 152
+    ADDI x3, x0, 11
+    BNE x5, x7, fail
+
+    # TEST_ST_SRC12_BYPASS(12, 0, 0, lw, sw, 0xaabbccdd, 0, tdat)
+    ADDI x3, x0, 12
+    ADDI x4, x0, 0
+test_12_1:
+    LUI x1, 0xaabbd
+    ADDI x1, x1, -803
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SW x1, 0(x2)
+    LW x14, 0(x2)
+    LUI x7, 0xaabbd
+    ADDI x7, x7, -803
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_12_1
+
+    # TEST_ST_SRC12_BYPASS(13, 0, 1, lw, sw, 0xdaabbccd, 4, tdat)
+    ADDI x3, x0, 13
+    ADDI x4, x0, 0
+test_13_1:
+    LUI x1, 0xdaabc
+    ADDI x1, x1, -819
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    SW x1, 4(x2)
+    LW x14, 4(x2)
+    LUI x7, 0xdaabc
+    ADDI x7, x7, -819
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_13_1
+
+    # TEST_ST_SRC12_BYPASS(14, 0, 2, lw, sw, 0xddaabbcc, 8, tdat)
+    ADDI x3, x0, 14
+    ADDI x4, x0, 0
+test_14_1:
+    LUI x1, 0xddaac
+    ADDI x1, x1, -1076
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SW x1, 8(x2)
+    LW x14, 8(x2)
+    LUI x7, 0xddaac
+    ADDI x7, x7, -1076
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_14_1
+
+    # TEST_ST_SRC12_BYPASS(15, 1, 0, lw, sw, 0xcddaabbc, 12, tdat)
+    ADDI x3, x0, 15
+    ADDI x4, x0, 0
+test_15_1:
+    LUI x1, 0xcddab
+    ADDI x1, x1, -1092
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SW x1, 12(x2)
+    LW x14, 12(x2)
+    LUI x7, 0xcddab
+    ADDI x7, x7, -1092
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_15_1
+
+    # TEST_ST_SRC12_BYPASS(16, 1, 1, lw, sw, 0xccddaabb, 16, tdat)
+    ADDI x3, x0, 16
+    ADDI x4, x0, 0
+test_16_1:
+    LUI x1, 0xccddb
+    ADDI x1, x1, -1349
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    SW x1, 16(x2)
+    LW x14, 16(x2)
+    LUI x7, 0xccddb
+    ADDI x7, x7, -1349
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_16_1
+
+    # TEST_ST_SRC12_BYPASS(17, 2, 0, lw, sw, 0xbccddaab, 20, tdat)
+    ADDI x3, x0, 17
+    ADDI x4, x0, 0
+test_17_1:
+    LUI x1, 0xbccde
+    ADDI x1, x1, -1365
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    SW x1, 20(x2)
+    LW x14, 20(x2)
+    LUI x7, 0xbccde
+    ADDI x7, x7, -1365
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_17_1
+
+    # TEST_ST_SRC21_BYPASS(18, 0, 0, lw, sw, 0x112233, 0, tdat)
+    ADDI x3, x0, 18
+    ADDI x4, x0, 0
+test_18_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    LUI x1, 0x112
+    ADDI x1, x1, 563
+    SW x1, 0(x2)
+    LW x14, 0(x2)
+    LUI x7, 0x112
+    ADDI x7, x7, 563
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_18_1
+
+    # TEST_ST_SRC21_BYPASS(19, 0, 1, lw, sw, 0x30011223, 4, tdat)
+    ADDI x3, x0, 19
+    ADDI x4, x0, 0
+test_19_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    LUI x1, 0x30011
+    ADDI x1, x1, 547
+    ADDI x0, x0, 0
+    SW x1, 4(x2)
+    LW x14, 4(x2)
+    LUI x7, 0x30011
+    ADDI x7, x7, 547
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_19_1
+
+    # TEST_ST_SRC21_BYPASS(20, 0, 2, lw, sw, 0x33001122, 8, tdat)
+    ADDI x3, x0, 20
+    ADDI x4, x0, 0
+test_20_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    LUI x1, 0x33001
+    ADDI x1, x1, 290
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    SW x1, 8(x2)
+    LW x14, 8(x2)
+    LUI x7, 0x33001
+    ADDI x7, x7, 290
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_20_1
+
+    # TEST_ST_SRC21_BYPASS(21, 1, 0, lw, sw, 0x23300112, 12, tdat)
+    ADDI x3, x0, 21
+    ADDI x4, x0, 0
+test_21_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    LUI x1, 0x23300
+    ADDI x1, x1, 274
+    SW x1, 12(x2)
+    LW x14, 12(x2)
+    LUI x7, 0x23300
+    ADDI x7, x7, 274
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_21_1
+
+    # TEST_ST_SRC21_BYPASS(22, 1, 1, lw, sw, 0x22330011, 16, tdat)
+    ADDI x3, x0, 22
+    ADDI x4, x0, 0
+test_22_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    LUI x1, 0x22330
+    ADDI x1, x1, 17
+    ADDI x0, x0, 0
+    SW x1, 16(x2)
+    LW x14, 16(x2)
+    LUI x7, 0x22330
+    ADDI x7, x7, 17
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_22_1
+
+    # TEST_ST_SRC21_BYPASS(23, 2, 0, lw, sw, 0x12233001, 20, tdat)
+    ADDI x3, x0, 23
+    ADDI x4, x0, 0
+test_23_1:
+    LUI x2, 0x2
+    ADDI x2, x2, 0
+    ADDI x0, x0, 0
+    ADDI x0, x0, 0
+    LUI x1, 0x12233
+    ADDI x1, x1, 1
+    SW x1, 20(x2)
+    LW x14, 20(x2)
+    LUI x7, 0x12233
+    ADDI x7, x7, 1
+    BNE x14, x7, fail
+    ADDI x4, x4, 1
+    ADDI x5, x0, 2
+    BNE x4, x5, test_23_1
+
+    # TEST_CASE(24) - store does not touch the neighbouring element
+    LUI x1, 0x2
+    ADDI x1, x1, 0
+    ADDI x2, x0, -1
+    SW x0, 20(x1)
+    SW x2, 16(x1)
+    LW x14, 20(x1)
+    ADDI x7, x0, 0
+    ADDI x3, x0, 24
+    BNE x14, x7, fail
+
+    BNE x0, x3, pass
+fail:
+    SLLI x3, x3, 1
+    ORI x3, x3, 1
+    ADDI x17, x0, 93
+    ADDI x10, x3, 0
+    ECALL
+pass:
+    ADDI x3, x0, 1
+    ADDI x17, x0, 93
+    ADDI x10, x0, 0
+    ECALL
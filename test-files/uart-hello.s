@@ -0,0 +1,26 @@
+# Prints a string by polling the UART, then echoes received bytes
+.data
+msg: .string "Hello from the UART!\n"
+
+.text
+    lui x5, 0x10000         # UART base address
+    addi x6, x0, 0          # address of msg
+
+print:
+    lbu x7, 0(x6)
+    beq x7, x0, echo
+tx_wait:
+    lw x8, 4(x5)            # STATUS
+    andi x8, x8, 2          # TX ready
+    beq x8, x0, tx_wait
+    sb x7, 0(x5)            # DATA
+    addi x6, x6, 1
+    jal x0, print
+
+echo:
+    lw x8, 4(x5)            # STATUS
+    andi x8, x8, 1          # RX valid
+    beq x8, x0, echo
+    lbu x7, 0(x5)           # DATA
+    sb x7, 0(x5)
+    jal x0, echo